@@ -0,0 +1,77 @@
+use std::{error::Error, fs, path::Path, time::Duration};
+use reqwest::{Certificate, Proxy};
+
+/// Settings for the HTTP client requests are sent with, for networks that need them, such as a proxy.<br>
+/// A config is used by `MalClient::config`, for MyAnimeList and `login()`, and by `JikanClient::config`, for Jikan.
+/// Settings that aren't given keep reqwest's defaults, which use the `HTTP_PROXY` and `HTTPS_PROXY` environment variables.
+/// ### Example usage:
+/// ```
+/// use std::time::Duration;
+/// use mal_query::{config::ClientConfig, myanimelist::{self, client::MalClient}};
+/// fn config_example() {
+///     let config = ClientConfig::new()
+///         .proxy("socks5://localhost:1080")
+///         .expect("Proxy URL is not valid")
+///         .connect_timeout(Duration::from_secs(5))
+///         .timeout(Duration::from_secs(30))
+///         .user_agent("my-app/1.0")
+///         .clone();
+///     myanimelist::set_client(MalClient::new().config(&config).expect("Client could not be built"));
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ClientConfig {
+    proxy: Option<Proxy>,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    root_certificates: Vec<Certificate>,
+}
+
+impl ClientConfig {
+    pub fn new() -> Self {
+        ClientConfig::default()
+    }
+    /// Sends every request through a proxy, such as `http://proxy.example.com:8080` or `socks5://localhost:1080`.
+    /// A username and password can be given in the URL
+    pub fn proxy(&mut self, url: &str) -> Result<&mut Self, Box<dyn Error>> {
+        self.proxy = Some(Proxy::all(url)?);
+        Ok(self)
+    }
+    /// Sets how long connecting to a server can take
+    pub fn connect_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+    /// Sets how long a request can take, from connecting until the whole response is read
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    /// Sets the `User-Agent` header every request is sent with
+    pub fn user_agent(&mut self, user_agent: &str) -> &mut Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+    /// Trusts a root certificate in PEM, along with the system's, such as a corporate proxy's certificate authority
+    pub fn root_certificate(&mut self, pem: &[u8]) -> Result<&mut Self, Box<dyn Error>> {
+        self.root_certificates.push(Certificate::from_pem(pem)?);
+        Ok(self)
+    }
+    /// Reads a root certificate in PEM from a file, and trusts it like `root_certificate()`
+    pub fn root_certificate_file(&mut self, path: impl AsRef<Path>) -> Result<&mut Self, Box<dyn Error>> {
+        self.root_certificate(&fs::read(path)?)
+    }
+    /// Builds a `reqwest::Client` with the settings
+    pub fn build(&self) -> Result<reqwest::Client, Box<dyn Error>> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(proxy) = &self.proxy { builder = builder.proxy(proxy.clone()) }
+        if let Some(timeout) = self.connect_timeout { builder = builder.connect_timeout(timeout) }
+        if let Some(timeout) = self.timeout { builder = builder.timeout(timeout) }
+        if let Some(user_agent) = &self.user_agent { builder = builder.user_agent(user_agent) }
+        for certificate in &self.root_certificates {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+        Ok(builder.build()?)
+    }
+}
@@ -0,0 +1,271 @@
+use std::error::Error;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::myanimelist::models::*;
+use super::{date_parts, join_date};
+
+/// How an AniList user rates, which AniList sends scores in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScoreFormat {
+    /// 0 to 100
+    Point100,
+    /// 0.0 to 10.0
+    Point10Decimal,
+    /// 0 to 10, the same as MyAnimeList
+    Point10,
+    /// 0 to 5 stars
+    Point5,
+    /// 0 to 3 smileys
+    Point3,
+}
+
+impl ScoreFormat {
+    /// Converts a MyAnimeList score (0 to 10) to this format. 0 is unscored in both
+    pub fn from_mal(&self, score: u32) -> f32 {
+        let score = score.min(10);
+        match self {
+            ScoreFormat::Point100 => (score * 10) as f32,
+            ScoreFormat::Point10Decimal | ScoreFormat::Point10 => score as f32,
+            ScoreFormat::Point5 => score.div_ceil(2) as f32,
+            ScoreFormat::Point3 => match score {
+                0 => 0.0,
+                1..=4 => 1.0,
+                5..=7 => 2.0,
+                _ => 3.0,
+            },
+        }
+    }
+    /// Converts a score in this format to a MyAnimeList score (0 to 10), rounding to the nearest point
+    pub fn to_mal(&self, score: f32) -> u32 {
+        let score = score.max(0.0);
+        let mal = match self {
+            ScoreFormat::Point100 => (score / 10.0).round(),
+            ScoreFormat::Point10Decimal | ScoreFormat::Point10 => score.round(),
+            ScoreFormat::Point5 => score.round() * 2.0,
+            // The middle of the MyAnimeList scores each smiley covers
+            ScoreFormat::Point3 => match score.round() as u32 {
+                0 => 0.0,
+                1 => 3.0,
+                2 => 6.0,
+                _ => 9.0,
+            },
+        };
+        (mal as u32).min(10)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AniListStatus {
+    Current,
+    Planning,
+    Completed,
+    Dropped,
+    Paused,
+    /// Rewatching a completed anime
+    Repeating,
+}
+
+/// AniList's date, where any part can be unknown
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct FuzzyDate {
+    pub year: Option<i32>,
+    pub month: Option<u32>,
+    pub day: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct AniListTitle {
+    pub romaji: Option<String>,
+    pub english: Option<String>,
+    pub native: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AniListMedia {
+    /// AniList's ID, which isn't known when converting from MyAnimeList
+    pub id: Option<u32>,
+    pub id_mal: Option<u32>,
+    pub title: AniListTitle,
+    pub episodes: Option<u32>,
+}
+
+/// One entry of an AniList list, in the shape of AniList's `MediaList`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AniListEntry {
+    pub status: AniListStatus,
+    /// In the list's `ScoreFormat`
+    pub score: f32,
+    pub progress: u32,
+    pub repeat: u32,
+    pub priority: u32,
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub started_at: FuzzyDate,
+    #[serde(default)]
+    pub completed_at: FuzzyDate,
+    /// Seconds since the Unix epoch
+    pub updated_at: Option<i64>,
+    pub media: AniListMedia,
+}
+
+/// One list of a collection, such as "Watching"
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct AniListGroup {
+    pub name: String,
+    pub status: AniListStatus,
+    pub entries: Vec<AniListEntry>,
+}
+
+/// A user's anime lists, in the shape of AniList's `MediaListCollection`, grouped by status
+/// ### Example usage:
+/// ```
+/// use mal_query::convert::anilist::{AniListCollection, ScoreFormat};
+/// use mal_query::myanimelist::models::MalAnimeData;
+/// fn anilist_example() {
+///     // A list saved with `MalAnimeSearch::write_json_lines`, or any JSON array of `MalAnimeData`
+///     let json = std::fs::read_to_string("animelist.json").unwrap();
+///     let anime: Vec<MalAnimeData> = serde_json::from_str(&json).unwrap();
+///     let collection = AniListCollection::from_mal(&anime, ScoreFormat::Point100);
+///     std::fs::write("anilist.json", collection.to_json().unwrap()).unwrap();
+///
+///     let back: Vec<MalAnimeData> = collection.to_mal(ScoreFormat::Point100);
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct AniListCollection {
+    pub lists: Vec<AniListGroup>,
+}
+
+impl AniListEntry {
+    /// Converts a MyAnimeList entry, which needs its `list_status`.<br>
+    /// Returns `None` for anime that aren't on the user's list
+    pub fn from_mal(anime: &MalAnimeData, format: ScoreFormat) -> Option<Self> {
+        let status = anime.list_status.as_ref()?;
+        let mut entry = AniListEntry::from_list_status(status, format);
+        entry.media = AniListMedia {
+            id: None,
+            id_mal: Some(anime.id),
+            title: AniListTitle {
+                romaji: Some(anime.title.clone()),
+                english: anime.alternative_titles.as_ref().map(|t| t.en.clone()).filter(|t| !t.is_empty()),
+                native: anime.alternative_titles.as_ref().map(|t| t.ja.clone()).filter(|t| !t.is_empty()),
+            },
+            episodes: anime.num_episodes.filter(|&n| n > 0),
+        };
+        Some(entry)
+    }
+    /// Converts the user's status for an anime. The `media` is left empty
+    pub fn from_list_status(status: &ListStatus, format: ScoreFormat) -> Self {
+        AniListEntry {
+            status: match (status.status, status.is_rewatching) {
+                (_, true) => AniListStatus::Repeating,
+                (Status::Watching, _) => AniListStatus::Current,
+                (Status::Completed, _) => AniListStatus::Completed,
+                (Status::OnHold, _) => AniListStatus::Paused,
+                (Status::Dropped, _) => AniListStatus::Dropped,
+                (Status::PlanToWatch, _) => AniListStatus::Planning,
+            },
+            score: format.from_mal(status.score),
+            progress: status.num_episodes_watched,
+            repeat: status.num_times_rewatched.unwrap_or(0),
+            priority: status.priority.unwrap_or(0),
+            notes: status.comments.clone().filter(|c| !c.is_empty()),
+            started_at: fuzzy_date(status.start_date.as_deref()),
+            completed_at: fuzzy_date(status.finish_date.as_deref()),
+            updated_at: DateTime::parse_from_rfc3339(&status.updated_at).ok().map(|t| t.timestamp()),
+            media: AniListMedia::default(),
+        }
+    }
+    /// Converts the entry to the user's status on MyAnimeList.
+    /// `updated_at` is empty when the entry has no `updated_at`
+    pub fn to_list_status(&self, format: ScoreFormat) -> ListStatus {
+        ListStatus {
+            status: match self.status {
+                AniListStatus::Current => Status::Watching,
+                AniListStatus::Planning => Status::PlanToWatch,
+                AniListStatus::Completed | AniListStatus::Repeating => Status::Completed,
+                AniListStatus::Dropped => Status::Dropped,
+                AniListStatus::Paused => Status::OnHold,
+            },
+            score: format.to_mal(self.score),
+            num_episodes_watched: self.progress,
+            is_rewatching: self.status == AniListStatus::Repeating,
+            start_date: join_date(self.started_at.year, self.started_at.month, self.started_at.day),
+            finish_date: join_date(self.completed_at.year, self.completed_at.month, self.completed_at.day),
+            priority: Some(self.priority.min(2)),
+            num_times_rewatched: Some(self.repeat),
+            rewatch_value: None,
+            tags: None,
+            comments: self.notes.clone(),
+            updated_at: self.updated_at
+                .and_then(|t| DateTime::<Utc>::from_timestamp(t, 0))
+                .map(|t| t.to_rfc3339())
+                .unwrap_or_default(),
+        }
+    }
+    /// Converts the entry to a MyAnimeList entry with its `list_status`.<br>
+    /// Returns a `Result<>`, with an error if the entry has no MyAnimeList ID
+    pub fn to_mal(&self, format: ScoreFormat) -> Result<MalAnimeData, Box<dyn Error>> {
+        let id = self.media.id_mal.ok_or("AniList entry has no MyAnimeList ID")?;
+        Ok(MalAnimeData {
+            id,
+            title: self.media.title.romaji.clone().unwrap_or_default(),
+            num_episodes: self.media.episodes,
+            list_status: Some(self.to_list_status(format)),
+            ..Default::default()
+        })
+    }
+}
+
+impl AniListCollection {
+    /// Converts a MyAnimeList list, grouping the entries by status. Anime without a `list_status` are left out
+    pub fn from_mal(anime: &[MalAnimeData], format: ScoreFormat) -> Self {
+        let mut collection = AniListCollection::default();
+        for entry in anime.iter().filter_map(|a| AniListEntry::from_mal(a, format)) {
+            match collection.lists.iter_mut().find(|list| list.status == entry.status) {
+                Some(list) => list.entries.push(entry),
+                None => collection.lists.push(AniListGroup {
+                    name: group_name(entry.status).to_string(),
+                    status: entry.status,
+                    entries: vec![entry],
+                }),
+            }
+        }
+        collection
+    }
+    /// Converts every entry with a MyAnimeList ID back to MyAnimeList entries. Entries without one are left out
+    pub fn to_mal(&self, format: ScoreFormat) -> Vec<MalAnimeData> {
+        self.lists
+            .iter()
+            .flat_map(|list| &list.entries)
+            .filter_map(|entry| entry.to_mal(format).ok())
+            .collect()
+    }
+    /// Reads a collection saved as JSON, such as from `to_json()` or AniList's API
+    pub fn from_json(json: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(json)?)
+    }
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+// The names AniList gives its default lists
+fn group_name(status: AniListStatus) -> &'static str {
+    match status {
+        AniListStatus::Current => "Watching",
+        AniListStatus::Planning => "Planning",
+        AniListStatus::Completed => "Completed",
+        AniListStatus::Dropped => "Dropped",
+        AniListStatus::Paused => "Paused",
+        AniListStatus::Repeating => "Rewatching",
+    }
+}
+
+fn fuzzy_date(date: Option<&str>) -> FuzzyDate {
+    let (year, month, day) = date.map(date_parts).unwrap_or_default();
+    FuzzyDate { year, month, day }
+}
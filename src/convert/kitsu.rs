@@ -0,0 +1,260 @@
+use std::{collections::HashMap, error::Error};
+use serde::{Deserialize, Serialize};
+use crate::myanimelist::models::*;
+use super::{date_parts, join_date};
+
+// The `externalSite` of Kitsu's mappings to MyAnimeList anime
+const MAL_ANIME_SITE: &str = "myanimelist/anime";
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum KitsuStatus {
+    Current,
+    Planned,
+    Completed,
+    OnHold,
+    Dropped,
+}
+
+/// The user's status for an anime, the `attributes` of a Kitsu `libraryEntries` resource
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KitsuAttributes {
+    pub status: KitsuStatus,
+    pub progress: u32,
+    /// Rewatching, which Kitsu shows as `Current`
+    pub reconsuming: bool,
+    pub reconsume_count: u32,
+    pub notes: Option<String>,
+    pub private: bool,
+    /// 2 to 20, or `None` when unrated. See `rating_to_mal`
+    pub rating_twenty: Option<u32>,
+    /// ISO 8601 times, such as `2019-08-14T00:00:00.000Z`
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct KitsuRef {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub id: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct KitsuRelationship {
+    pub data: Option<KitsuRef>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct KitsuEntryRelationships {
+    pub anime: KitsuRelationship,
+}
+
+/// A Kitsu `libraryEntries` resource
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct KitsuEntry {
+    /// Kitsu's ID for the entry, which isn't known when converting from MyAnimeList
+    pub id: Option<String>,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub attributes: KitsuAttributes,
+    pub relationships: KitsuEntryRelationships,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KitsuAnimeAttributes {
+    pub canonical_title: Option<String>,
+    pub episode_count: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KitsuMappingAttributes {
+    pub external_site: String,
+    pub external_id: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct KitsuMappingRelationships {
+    pub item: KitsuRelationship,
+}
+
+/// The resources included with the library, which link Kitsu's anime to MyAnimeList's
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum KitsuIncluded {
+    #[serde(rename = "anime")]
+    Anime { id: String, attributes: KitsuAnimeAttributes },
+    #[serde(rename = "mappings")]
+    Mapping { id: String, attributes: KitsuMappingAttributes, relationships: KitsuMappingRelationships },
+    /// Any other resource, which is ignored
+    #[serde(other)]
+    Other,
+}
+
+/// A user's Kitsu library, as the JSON:API document of Kitsu's `/library-entries?include=anime,anime.mappings`.<br>
+/// MyAnimeList IDs are found through the `mappings`, so entries without a mapping to MyAnimeList can't be converted.
+/// Kitsu's own anime IDs aren't known when converting from MyAnimeList, so the MyAnimeList ID is used in their place,
+/// with a mapping saying so.
+/// ### Example usage:
+/// ```
+/// use mal_query::convert::kitsu::KitsuLibrary;
+/// use mal_query::myanimelist::models::MalAnimeData;
+/// fn kitsu_example() {
+///     let json = std::fs::read_to_string("kitsu.json").unwrap();
+///     let anime: Vec<MalAnimeData> = KitsuLibrary::from_json(&json).unwrap().to_mal();
+/// }
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct KitsuLibrary {
+    pub data: Vec<KitsuEntry>,
+    #[serde(default)]
+    pub included: Vec<KitsuIncluded>,
+}
+
+impl KitsuAttributes {
+    /// Converts the user's status for an anime
+    pub fn from_list_status(status: &ListStatus) -> Self {
+        KitsuAttributes {
+            status: match (status.status, status.is_rewatching) {
+                (_, true) | (Status::Watching, _) => KitsuStatus::Current,
+                (Status::Completed, _) => KitsuStatus::Completed,
+                (Status::OnHold, _) => KitsuStatus::OnHold,
+                (Status::Dropped, _) => KitsuStatus::Dropped,
+                (Status::PlanToWatch, _) => KitsuStatus::Planned,
+            },
+            progress: status.num_episodes_watched,
+            reconsuming: status.is_rewatching,
+            reconsume_count: status.num_times_rewatched.unwrap_or(0),
+            notes: status.comments.clone().filter(|c| !c.is_empty()),
+            private: false,
+            rating_twenty: rating_from_mal(status.score),
+            started_at: status.start_date.as_deref().map(kitsu_time),
+            finished_at: status.finish_date.as_deref().map(kitsu_time),
+            updated_at: Some(status.updated_at.clone()).filter(|t| !t.is_empty()),
+        }
+    }
+    /// Converts the entry to the user's status on MyAnimeList
+    pub fn to_list_status(&self) -> ListStatus {
+        ListStatus {
+            status: match (self.status, self.reconsuming) {
+                (_, true) => Status::Completed,
+                (KitsuStatus::Current, _) => Status::Watching,
+                (KitsuStatus::Planned, _) => Status::PlanToWatch,
+                (KitsuStatus::Completed, _) => Status::Completed,
+                (KitsuStatus::OnHold, _) => Status::OnHold,
+                (KitsuStatus::Dropped, _) => Status::Dropped,
+            },
+            score: rating_to_mal(self.rating_twenty),
+            num_episodes_watched: self.progress,
+            is_rewatching: self.reconsuming,
+            start_date: self.started_at.as_deref().and_then(mal_date),
+            finish_date: self.finished_at.as_deref().and_then(mal_date),
+            priority: None,
+            num_times_rewatched: Some(self.reconsume_count),
+            rewatch_value: None,
+            tags: None,
+            comments: self.notes.clone(),
+            updated_at: self.updated_at.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl KitsuLibrary {
+    /// Converts a MyAnimeList list, which needs each anime's `list_status`. Anime without one are left out
+    pub fn from_mal(anime: &[MalAnimeData]) -> Self {
+        let mut library = KitsuLibrary::default();
+        for a in anime {
+            let Some(status) = &a.list_status else { continue };
+            let anime_id = a.id.to_string();
+            library.data.push(KitsuEntry {
+                id: None,
+                kind: "libraryEntries".to_string(),
+                attributes: KitsuAttributes::from_list_status(status),
+                relationships: KitsuEntryRelationships { anime: anime_ref(&anime_id) },
+            });
+            library.included.push(KitsuIncluded::Anime {
+                id: anime_id.clone(),
+                attributes: KitsuAnimeAttributes {
+                    canonical_title: Some(a.title.clone()),
+                    episode_count: a.num_episodes.filter(|&n| n > 0),
+                },
+            });
+            library.included.push(KitsuIncluded::Mapping {
+                id: format!("mal-{anime_id}"),
+                attributes: KitsuMappingAttributes { external_site: MAL_ANIME_SITE.to_string(), external_id: anime_id.clone() },
+                relationships: KitsuMappingRelationships { item: anime_ref(&anime_id) },
+            });
+        }
+        library
+    }
+    /// Converts every entry with a mapping to MyAnimeList back to MyAnimeList entries, with their `list_status`
+    pub fn to_mal(&self) -> Vec<MalAnimeData> {
+        let mut mal_ids: HashMap<&str, u32> = HashMap::new();
+        let mut anime: HashMap<&str, &KitsuAnimeAttributes> = HashMap::new();
+        for included in &self.included {
+            match included {
+                KitsuIncluded::Mapping { attributes, relationships, .. } if attributes.external_site == MAL_ANIME_SITE => {
+                    if let (Some(item), Ok(mal_id)) = (&relationships.item.data, attributes.external_id.parse()) {
+                        mal_ids.insert(&item.id, mal_id);
+                    }
+                },
+                KitsuIncluded::Anime { id, attributes } => { anime.insert(id, attributes); },
+                _ => {},
+            }
+        }
+        self.data
+            .iter()
+            .filter_map(|entry| {
+                let anime_id = entry.relationships.anime.data.as_ref()?.id.as_str();
+                let details = anime.get(anime_id);
+                Some(MalAnimeData {
+                    id: *mal_ids.get(anime_id)?,
+                    title: details.and_then(|d| d.canonical_title.clone()).unwrap_or_default(),
+                    num_episodes: details.and_then(|d| d.episode_count),
+                    list_status: Some(entry.attributes.to_list_status()),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+    /// Reads a library saved as JSON, such as from `to_json()` or Kitsu's API
+    pub fn from_json(json: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(json)?)
+    }
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Converts a MyAnimeList score (0 to 10) to Kitsu's `ratingTwenty`, which is `None` for unscored.<br>
+/// Kitsu shows the rating as stars (`ratingTwenty / 4`) or a decimal out of 10 (`ratingTwenty / 2`), by the user's setting
+pub fn rating_from_mal(score: u32) -> Option<u32> {
+    match score.min(10) {
+        0 => None,
+        score => Some(score * 2),
+    }
+}
+
+/// Converts Kitsu's `ratingTwenty` to a MyAnimeList score (0 to 10). Half points round up
+pub fn rating_to_mal(rating_twenty: Option<u32>) -> u32 {
+    rating_twenty.map(|r| r.div_ceil(2).min(10)).unwrap_or(0)
+}
+
+fn anime_ref(id: &str) -> KitsuRelationship {
+    KitsuRelationship { data: Some(KitsuRef { kind: "anime".to_string(), id: id.to_string() }) }
+}
+
+// Kitsu needs a full date, so unknown months and days are the first
+fn kitsu_time(date: &str) -> String {
+    let (year, month, day) = date_parts(date);
+    format!("{:04}-{:02}-{:02}T00:00:00.000Z", year.unwrap_or(0), month.unwrap_or(1), day.unwrap_or(1))
+}
+
+fn mal_date(time: &str) -> Option<String> {
+    let (year, month, day) = date_parts(time.get(..10)?);
+    join_date(year, month, day)
+}
@@ -0,0 +1,25 @@
+//! Converts MyAnimeList list entries to and from the list formats of other trackers, without calling any API,
+//! so lists can be moved between sites from saved files.<br>
+//! Only the fields both sites have are kept. Tags, and the rewatch value, have no equivalent and are lost.
+
+pub mod anilist;
+pub mod kitsu;
+
+// Splits a MyAnimeList date, which can be only a year, or a year and month
+fn date_parts(date: &str) -> (Option<i32>, Option<u32>, Option<u32>) {
+    let mut parts = date.split('-');
+    let year = parts.next().and_then(|y| y.parse().ok());
+    let month = parts.next().and_then(|m| m.parse().ok());
+    let day = parts.next().and_then(|d| d.parse().ok());
+    (year, month, day)
+}
+
+// A MyAnimeList date from its parts, leaving out the parts after the first unknown one
+fn join_date(year: Option<i32>, month: Option<u32>, day: Option<u32>) -> Option<String> {
+    match (year, month, day) {
+        (Some(y), Some(m), Some(d)) => Some(format!("{y:04}-{m:02}-{d:02}")),
+        (Some(y), Some(m), None) => Some(format!("{y:04}-{m:02}")),
+        (Some(y), None, _) => Some(format!("{y:04}")),
+        _ => None,
+    }
+}
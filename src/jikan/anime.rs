@@ -0,0 +1,115 @@
+use std::error::Error;
+use super::{models::*, run_get, run_get_page};
+
+/// Takes an anime ID, and gets the full result of the data from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanAnime`
+pub async fn get_anime(id: u32) -> Result<JikanAnime, Box<dyn Error>> {
+    let path = format!("/anime/{id}/full");
+    run_get(&path).await
+}
+
+/// Takes an anime ID, and gets its characters and their voice actors from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a Vector of `AnimeCharacter`
+pub async fn get_anime_characters(id: u32) -> Result<Vec<AnimeCharacter>, Box<dyn Error>> {
+    let path = format!("/anime/{id}/characters");
+    run_get(&path).await
+}
+
+/// Takes an anime ID, and gets its staff and their positions from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a Vector of `AnimeStaff`
+pub async fn get_anime_staff(id: u32) -> Result<Vec<AnimeStaff>, Box<dyn Error>> {
+    let path = format!("/anime/{id}/staff");
+    run_get(&path).await
+}
+
+/// Takes an anime ID and a page number (starting at 1), and gets that page of the anime's episodes from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanPage` of `Episode`
+pub async fn get_anime_episodes(id: u32, page: u32) -> Result<JikanPage<Episode>, Box<dyn Error>> {
+    let path = format!("/anime/{id}/episodes?page={page}");
+    run_get_page(&path).await
+}
+
+/// Takes an anime ID and an episode number, and gets that episode's details from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing an `EpisodeDetails`
+pub async fn get_anime_episode(id: u32, episode: u32) -> Result<EpisodeDetails, Box<dyn Error>> {
+    let path = format!("/anime/{id}/episodes/{episode}");
+    run_get(&path).await
+}
+
+/// Takes an anime ID and a page number (starting at 1), and gets that page of the anime's news from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanPage` of `News`
+pub async fn get_anime_news(id: u32, page: u32) -> Result<JikanPage<News>, Box<dyn Error>> {
+    let path = format!("/anime/{id}/news?page={page}");
+    run_get_page(&path).await
+}
+
+/// Takes an anime ID, and gets its promotional, episode and music videos from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing an `AnimeVideos`
+pub async fn get_anime_videos(id: u32) -> Result<AnimeVideos, Box<dyn Error>> {
+    let path = format!("/anime/{id}/videos");
+    run_get(&path).await
+}
+
+/// Takes an anime ID, and gets its pictures from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a Vector of `Images`
+pub async fn get_anime_pictures(id: u32) -> Result<Vec<Images>, Box<dyn Error>> {
+    let path = format!("/anime/{id}/pictures");
+    run_get(&path).await
+}
+
+/// Takes an anime ID, and gets how many users have it in each list status, and its score distribution, from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing an `AnimeStatistics`
+pub async fn get_anime_statistics(id: u32) -> Result<AnimeStatistics, Box<dyn Error>> {
+    let path = format!("/anime/{id}/statistics");
+    run_get(&path).await
+}
+
+/// Takes an anime ID, and gets its "More Info" text from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing the text, or `None` if the anime has none
+pub async fn get_anime_moreinfo(id: u32) -> Result<Option<String>, Box<dyn Error>> {
+    let path = format!("/anime/{id}/moreinfo");
+    let data: MoreInfo = run_get(&path).await?;
+    Ok(data.moreinfo)
+}
+
+/// Takes an anime ID, and gets the anime users recommend with it from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a Vector of `Recommendation`
+pub async fn get_anime_recommendations(id: u32) -> Result<Vec<Recommendation>, Box<dyn Error>> {
+    let path = format!("/anime/{id}/recommendations");
+    run_get(&path).await
+}
+
+/// Takes an anime ID and a page number (starting at 1), and gets that page of the anime's reviews from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanPage` of `Review`
+pub async fn get_anime_reviews(id: u32, page: u32) -> Result<JikanPage<Review>, Box<dyn Error>> {
+    let path = format!("/anime/{id}/reviews?page={page}");
+    run_get_page(&path).await
+}
+
+/// Takes an anime ID, and gets its opening and ending themes from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `Theme`
+pub async fn get_anime_themes(id: u32) -> Result<Theme, Box<dyn Error>> {
+    let path = format!("/anime/{id}/themes");
+    run_get(&path).await
+}
+
+/// Takes an anime ID, and gets its external links, such as its official website, from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a Vector of `External`
+pub async fn get_anime_external(id: u32) -> Result<Vec<External>, Box<dyn Error>> {
+    let path = format!("/anime/{id}/external");
+    run_get(&path).await
+}
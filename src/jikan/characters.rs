@@ -0,0 +1,10 @@
+use std::error::Error;
+use super::{models::*, run_get};
+
+/// Takes a character ID, and gets the character, their anime and manga, and their voice actors from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `Character`
+pub async fn get_character(id: u32) -> Result<Character, Box<dyn Error>> {
+    let path = format!("/characters/{id}/full");
+    run_get(&path).await
+}
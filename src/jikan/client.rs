@@ -0,0 +1,123 @@
+use std::{error::Error, sync::Arc, time::Duration};
+use reqwest::{Response, StatusCode, header::RETRY_AFTER};
+use serde::de::DeserializeOwned;
+use crate::{config::ClientConfig, myanimelist::client::is_connection_error, rate_limit::RateLimiter};
+use super::{models::JikanPage, JikanResponse};
+
+/// The client every Jikan request is sent through. It limits requests to what Jikan allows,
+/// and retries requests Jikan rejects from rate limits (429) or server errors (5xx), or that couldn't connect.<br>
+/// The default client uses `https://api.jikan.moe/v4`, with Jikan's limits of 3 requests per second and 60 per minute.
+/// Another can be used for every `jikan` function with `jikan::set_client`.
+/// ### Example usage:
+/// ```
+/// use mal_query::jikan::{self, JikanClient};
+/// async fn client_example() {
+///     // A self-hosted Jikan instance, without its rate limits
+///     jikan::set_client(JikanClient::new()
+///         .base_url("http://localhost:8000/v4")
+///         .rate_limit(&[])
+///     );
+///     let anime = jikan::get_anime(21).await.unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct JikanClient {
+    http: reqwest::Client,
+    base_url: String,
+    limiter: Arc<RateLimiter>,
+    retries: u32,
+    backoff: Duration,
+}
+
+impl Default for JikanClient {
+    fn default() -> Self {
+        JikanClient::new()
+    }
+}
+
+impl JikanClient {
+    /// Creates a client for `https://api.jikan.moe/v4`, limited to 3 requests per second and 60 per minute,
+    /// which retries a request up to 3 times
+    pub fn new() -> Self {
+        JikanClient {
+            http: reqwest::Client::new(),
+            base_url: "https://api.jikan.moe/v4".to_string(),
+            limiter: Arc::new(RateLimiter::new(&[
+                (3, Duration::from_secs(1)),
+                (60, Duration::from_secs(60)),
+            ])),
+            retries: 3,
+            backoff: Duration::from_secs(1),
+        }
+    }
+    /// Changes the URL requests are sent to, such as a self-hosted Jikan instance
+    pub fn base_url(&mut self, base_url: &str) -> &mut Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+    /// Sends requests with a client built from the config, with its proxy, timeouts, user agent and certificates
+    pub fn config(&mut self, config: &ClientConfig) -> Result<&mut Self, Box<dyn Error>> {
+        self.http = config.build()?;
+        Ok(self)
+    }
+    /// Replaces the rate limits, as a list of `(requests, window)`. An empty list removes the limits
+    pub fn rate_limit(&mut self, limits: &[(u32, Duration)]) -> &mut Self {
+        self.limiter = Arc::new(RateLimiter::new(limits));
+        self
+    }
+    /// Sets how many times a request will be retried after a 429 or 5xx response, or a connection error. Defaults to 3
+    pub fn retries(&mut self, retries: u32) -> &mut Self {
+        self.retries = retries;
+        self
+    }
+    /// Sets the wait before the first retry, which doubles for each retry after. Defaults to 1 second.<br>
+    /// A `Retry-After` header from Jikan is used instead, when it sends one, up to the longest backoff.
+    pub fn backoff(&mut self, backoff: Duration) -> &mut Self {
+        self.backoff = backoff;
+        self
+    }
+    /// Gets a Jikan path, such as `/anime/21/full`, and reads the `data` of the response
+    pub async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, Box<dyn Error>> {
+        let res = self.call(path).await?;
+        let data: JikanResponse<T> = res.json().await?;
+        Ok(data.data)
+    }
+    /// Gets a paginated Jikan path, such as `/anime/21/episodes?page=1`, and reads the page
+    pub async fn get_page<T: DeserializeOwned>(&self, path: &str) -> Result<JikanPage<T>, Box<dyn Error>> {
+        let res = self.call(path).await?;
+        let data: JikanPage<T> = res.json().await?;
+        Ok(data)
+    }
+
+    async fn call(&self, path: &str) -> Result<Response, Box<dyn Error>> {
+        let url = format!("{}{path}", self.base_url);
+        let mut attempt = 0;
+        loop {
+            self.limiter.acquire().await;
+            let retry_after = match self.http.get(&url).send().await {
+                Ok(res) if res.status().is_success() => return Ok(res),
+                Ok(res) => {
+                    let status = res.status();
+                    let retryable = status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error();
+                    if !retryable || attempt >= self.retries {
+                        return Err(format!("Request failed with status {status:?}"))?
+                    }
+                    // Jikan sends Retry-After in seconds
+                    res.headers()
+                        .get(RETRY_AFTER)
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(|v| v.trim().parse::<u64>().ok())
+                        .map(Duration::from_secs)
+                }
+                Err(e) if !is_connection_error(&e) || attempt >= self.retries => return Err(e)?,
+                Err(_) => None,
+            };
+            let delay = match retry_after {
+                Some(retry_after) => retry_after.min(self.backoff * 2u32.pow(self.retries.saturating_sub(1))),
+                None => self.backoff * 2u32.pow(attempt),
+            };
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
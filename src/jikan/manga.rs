@@ -0,0 +1,18 @@
+use std::error::Error;
+use super::{models::*, run_get};
+
+/// Takes a manga ID, and gets the full result of the data from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanManga`
+pub async fn get_manga(id: u32) -> Result<JikanManga, Box<dyn Error>> {
+    let path = format!("/manga/{id}/full");
+    run_get(&path).await
+}
+
+/// Takes a manga ID, and gets its characters from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a Vector of `MangaCharacter`
+pub async fn get_manga_characters(id: u32) -> Result<Vec<MangaCharacter>, Box<dyn Error>> {
+    let path = format!("/manga/{id}/characters");
+    run_get(&path).await
+}
@@ -0,0 +1,65 @@
+use std::{error::Error, sync::RwLock};
+use lazy_static::lazy_static;
+use serde::{Deserialize, de::DeserializeOwned};
+use self::models::JikanPage;
+
+pub use anime::*;
+pub use manga::*;
+pub use characters::*;
+pub use people::*;
+pub use producers::*;
+pub use schedules::*;
+pub use seasons::*;
+pub use top::*;
+pub use random::*;
+pub use users::*;
+pub use search::{AnimeSearchBuilder, MangaSearchBuilder};
+pub use client::JikanClient;
+pub use pager::JikanPager;
+pub use crate::rate_limit::RateLimiter;
+
+pub mod anime;
+pub mod manga;
+pub mod characters;
+pub mod people;
+pub mod producers;
+pub mod schedules;
+pub mod seasons;
+pub mod top;
+pub mod random;
+pub mod users;
+pub mod search;
+pub mod client;
+pub mod pager;
+pub mod models;
+
+lazy_static! {
+    static ref CLIENT: RwLock<JikanClient> = RwLock::new(JikanClient::new());
+}
+
+/// Replaces the client every `jikan` function sends its requests through.<br>
+/// Its rate limits are shared by every request, including ones already waiting.
+pub fn set_client(client: &JikanClient) {
+    *CLIENT.write().unwrap() = client.clone();
+}
+
+// The client is cloned out, so the lock isn't held while requests wait
+fn client() -> JikanClient {
+    CLIENT.read().unwrap().clone()
+}
+
+// Every Jikan response is wrapped in a `data` field
+#[derive(Deserialize)]
+struct JikanResponse<T> {
+    data: T,
+}
+
+// To get one item
+async fn run_get<T: DeserializeOwned>(path: &str) -> Result<T, Box<dyn Error>> {
+    client().get(path).await
+}
+
+// To get one page of a paginated list
+async fn run_get_page<T: DeserializeOwned>(path: &str) -> Result<JikanPage<T>, Box<dyn Error>> {
+    client().get_page(path).await
+}
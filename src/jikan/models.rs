@@ -0,0 +1,915 @@
+use std::collections::HashMap;
+use serde::Deserialize;
+use crate::myanimelist::models::Season;
+
+// -------- enums --------
+
+/// A day of Jikan's broadcast schedule. Anime without a weekly broadcast are under `Other` or `Unknown`
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum ScheduleDay {
+    #[serde(rename = "monday")]
+    Monday,
+    #[serde(rename = "tuesday")]
+    Tuesday,
+    #[serde(rename = "wednesday")]
+    Wednesday,
+    #[serde(rename = "thursday")]
+    Thursday,
+    #[serde(rename = "friday")]
+    Friday,
+    #[serde(rename = "saturday")]
+    Saturday,
+    #[serde(rename = "sunday")]
+    Sunday,
+    #[serde(rename = "other")]
+    Other,
+    #[serde(rename = "unknown")]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum AnimeType {
+    TV,
+    Movie,
+    OVA,
+    Special,
+    ONA,
+    Music,
+    CM,
+    PV,
+    TVSpecial,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum MangaType {
+    Manga,
+    Novel,
+    LightNovel,
+    OneShot,
+    Doujin,
+    Manhwa,
+    Manhua,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum TopAnimeFilter {
+    Airing,
+    Upcoming,
+    ByPopularity,
+    Favorite,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum TopMangaFilter {
+    Publishing,
+    Upcoming,
+    ByPopularity,
+    Favorite,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum AnimeStatus {
+    Airing,
+    Complete,
+    Upcoming,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum MangaStatus {
+    Publishing,
+    Complete,
+    Hiatus,
+    Discontinued,
+    Upcoming,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum AnimeOrderBy {
+    MalId,
+    Title,
+    StartDate,
+    EndDate,
+    Episodes,
+    Score,
+    ScoredBy,
+    Rank,
+    Popularity,
+    Members,
+    Favorites,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum MangaOrderBy {
+    MalId,
+    Title,
+    StartDate,
+    EndDate,
+    Chapters,
+    Volumes,
+    Score,
+    ScoredBy,
+    Rank,
+    Popularity,
+    Members,
+    Favorites,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub enum HistoryType {
+    Anime,
+    Manga,
+}
+
+// The values of each enum in Jikan's query strings
+impl ScheduleDay {
+    pub(crate) fn as_query(&self) -> &'static str {
+        match self {
+            ScheduleDay::Monday => "monday",
+            ScheduleDay::Tuesday => "tuesday",
+            ScheduleDay::Wednesday => "wednesday",
+            ScheduleDay::Thursday => "thursday",
+            ScheduleDay::Friday => "friday",
+            ScheduleDay::Saturday => "saturday",
+            ScheduleDay::Sunday => "sunday",
+            ScheduleDay::Other => "other",
+            ScheduleDay::Unknown => "unknown",
+        }
+    }
+}
+
+impl AnimeType {
+    pub(crate) fn as_query(&self) -> &'static str {
+        match self {
+            AnimeType::TV => "tv",
+            AnimeType::Movie => "movie",
+            AnimeType::OVA => "ova",
+            AnimeType::Special => "special",
+            AnimeType::ONA => "ona",
+            AnimeType::Music => "music",
+            AnimeType::CM => "cm",
+            AnimeType::PV => "pv",
+            AnimeType::TVSpecial => "tv_special",
+        }
+    }
+}
+
+impl MangaType {
+    pub(crate) fn as_query(&self) -> &'static str {
+        match self {
+            MangaType::Manga => "manga",
+            MangaType::Novel => "novel",
+            MangaType::LightNovel => "lightnovel",
+            MangaType::OneShot => "oneshot",
+            MangaType::Doujin => "doujin",
+            MangaType::Manhwa => "manhwa",
+            MangaType::Manhua => "manhua",
+        }
+    }
+}
+
+impl TopAnimeFilter {
+    pub(crate) fn as_query(&self) -> &'static str {
+        match self {
+            TopAnimeFilter::Airing => "airing",
+            TopAnimeFilter::Upcoming => "upcoming",
+            TopAnimeFilter::ByPopularity => "bypopularity",
+            TopAnimeFilter::Favorite => "favorite",
+        }
+    }
+}
+
+impl TopMangaFilter {
+    pub(crate) fn as_query(&self) -> &'static str {
+        match self {
+            TopMangaFilter::Publishing => "publishing",
+            TopMangaFilter::Upcoming => "upcoming",
+            TopMangaFilter::ByPopularity => "bypopularity",
+            TopMangaFilter::Favorite => "favorite",
+        }
+    }
+}
+
+impl AnimeStatus {
+    pub(crate) fn as_query(&self) -> &'static str {
+        match self {
+            AnimeStatus::Airing => "airing",
+            AnimeStatus::Complete => "complete",
+            AnimeStatus::Upcoming => "upcoming",
+        }
+    }
+}
+
+impl MangaStatus {
+    pub(crate) fn as_query(&self) -> &'static str {
+        match self {
+            MangaStatus::Publishing => "publishing",
+            MangaStatus::Complete => "complete",
+            MangaStatus::Hiatus => "hiatus",
+            MangaStatus::Discontinued => "discontinued",
+            MangaStatus::Upcoming => "upcoming",
+        }
+    }
+}
+
+impl AnimeOrderBy {
+    pub(crate) fn as_query(&self) -> &'static str {
+        match self {
+            AnimeOrderBy::MalId => "mal_id",
+            AnimeOrderBy::Title => "title",
+            AnimeOrderBy::StartDate => "start_date",
+            AnimeOrderBy::EndDate => "end_date",
+            AnimeOrderBy::Episodes => "episodes",
+            AnimeOrderBy::Score => "score",
+            AnimeOrderBy::ScoredBy => "scored_by",
+            AnimeOrderBy::Rank => "rank",
+            AnimeOrderBy::Popularity => "popularity",
+            AnimeOrderBy::Members => "members",
+            AnimeOrderBy::Favorites => "favorites",
+        }
+    }
+}
+
+impl MangaOrderBy {
+    pub(crate) fn as_query(&self) -> &'static str {
+        match self {
+            MangaOrderBy::MalId => "mal_id",
+            MangaOrderBy::Title => "title",
+            MangaOrderBy::StartDate => "start_date",
+            MangaOrderBy::EndDate => "end_date",
+            MangaOrderBy::Chapters => "chapters",
+            MangaOrderBy::Volumes => "volumes",
+            MangaOrderBy::Score => "score",
+            MangaOrderBy::ScoredBy => "scored_by",
+            MangaOrderBy::Rank => "rank",
+            MangaOrderBy::Popularity => "popularity",
+            MangaOrderBy::Members => "members",
+            MangaOrderBy::Favorites => "favorites",
+        }
+    }
+}
+
+impl SortOrder {
+    pub(crate) fn as_query(&self) -> &'static str {
+        match self {
+            SortOrder::Ascending => "asc",
+            SortOrder::Descending => "desc",
+        }
+    }
+}
+
+impl HistoryType {
+    pub(crate) fn as_query(&self) -> &'static str {
+        match self {
+            HistoryType::Anime => "anime",
+            HistoryType::Manga => "manga",
+        }
+    }
+}
+
+// -------- Shared --------
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Images {
+    pub jpg: ImageUrls,
+    pub webp: Option<ImageUrls>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ImageUrls {
+    pub image_url: Option<String>,
+    pub small_image_url: Option<String>,
+    pub large_image_url: Option<String>,
+}
+
+/// A link to another MyAnimeList entry, such as a genre, studio, or related anime
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct MalUrl {
+    pub mal_id: u32,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub name: String,
+    pub url: String,
+}
+
+/// A short anime or manga entry, used where Jikan links to one
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct EntryMeta {
+    pub mal_id: u32,
+    pub url: String,
+    pub images: Images,
+    pub title: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct CharacterMeta {
+    pub mal_id: u32,
+    pub url: String,
+    pub images: Images,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct PersonMeta {
+    pub mal_id: u32,
+    pub url: String,
+    pub images: Images,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct UserMeta {
+    pub username: String,
+    pub url: String,
+    pub images: Option<Images>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Title {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub title: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct DateRange {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub string: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Trailer {
+    pub youtube_id: Option<String>,
+    pub url: Option<String>,
+    pub embed_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Relation {
+    pub relation: String,
+    pub entry: Vec<MalUrl>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct External {
+    pub name: String,
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Pagination {
+    pub last_visible_page: u32,
+    pub has_next_page: bool,
+    pub current_page: Option<u32>,
+    pub items: Option<PaginationItems>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct PaginationItems {
+    pub count: u32,
+    pub total: u32,
+    pub per_page: u32,
+}
+
+/// One page of a paginated Jikan list
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct JikanPage<T> {
+    pub data: Vec<T>,
+    pub pagination: Pagination,
+}
+
+/// A year, and the seasons of it that MyAnimeList has anime for, from Jikan's `/seasons`
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct SeasonYear {
+    pub year: u32,
+    pub seasons: Vec<Season>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ScoreCount {
+    pub score: u32,
+    pub votes: u32,
+    pub percentage: f32,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct News {
+    pub mal_id: u32,
+    pub url: String,
+    pub title: String,
+    pub date: String,
+    pub author_username: String,
+    pub author_url: String,
+    pub forum_url: String,
+    pub images: Images,
+    pub comments: u32,
+    pub excerpt: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Recommendation {
+    pub entry: EntryMeta,
+    pub url: String,
+    pub votes: u32,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Review {
+    pub mal_id: u32,
+    pub url: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub reactions: HashMap<String, u32>,
+    pub date: String,
+    pub review: String,
+    pub score: u32,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub is_spoiler: bool,
+    pub is_preliminary: bool,
+    pub episodes_watched: Option<u32>,
+    pub user: UserMeta,
+}
+
+// -------- JikanAnime --------
+
+/// An anime from Jikan's `/anime/{id}/full`.<br>
+/// `relations`, `theme`, `external` and `streaming` are only given by the full endpoint, and are empty elsewhere.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct JikanAnime {
+    pub mal_id: u32,
+    pub url: String,
+    pub images: Images,
+    pub trailer: Option<Trailer>,
+    pub approved: Option<bool>,
+    #[serde(default)]
+    pub titles: Vec<Title>,
+    pub title: String,
+    pub title_english: Option<String>,
+    pub title_japanese: Option<String>,
+    #[serde(default)]
+    pub title_synonyms: Vec<String>,
+    #[serde(rename = "type")]
+    pub media_type: Option<String>,
+    pub source: Option<String>,
+    pub episodes: Option<u32>,
+    pub status: Option<String>,
+    #[serde(default)]
+    pub airing: bool,
+    pub aired: Option<DateRange>,
+    pub duration: Option<String>,
+    pub rating: Option<String>,
+    pub score: Option<f32>,
+    pub scored_by: Option<u32>,
+    pub rank: Option<u32>,
+    pub popularity: Option<u32>,
+    pub members: Option<u32>,
+    pub favorites: Option<u32>,
+    pub synopsis: Option<String>,
+    pub background: Option<String>,
+    pub season: Option<String>,
+    pub year: Option<u32>,
+    pub broadcast: Option<JikanBroadcast>,
+    #[serde(default)]
+    pub producers: Vec<MalUrl>,
+    #[serde(default)]
+    pub licensors: Vec<MalUrl>,
+    #[serde(default)]
+    pub studios: Vec<MalUrl>,
+    #[serde(default)]
+    pub genres: Vec<MalUrl>,
+    #[serde(default)]
+    pub explicit_genres: Vec<MalUrl>,
+    #[serde(default)]
+    pub themes: Vec<MalUrl>,
+    #[serde(default)]
+    pub demographics: Vec<MalUrl>,
+    #[serde(default)]
+    pub relations: Vec<Relation>,
+    pub theme: Option<Theme>,
+    #[serde(default)]
+    pub external: Vec<External>,
+    #[serde(default)]
+    pub streaming: Vec<External>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct JikanBroadcast {
+    pub day: Option<String>,
+    pub time: Option<String>,
+    pub timezone: Option<String>,
+    pub string: Option<String>,
+}
+
+/// Opening and ending themes
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Theme {
+    #[serde(default)]
+    pub openings: Vec<String>,
+    #[serde(default)]
+    pub endings: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct AnimeCharacter {
+    pub character: CharacterMeta,
+    pub role: String,
+    pub favorites: Option<u32>,
+    #[serde(default)]
+    pub voice_actors: Vec<VoiceActor>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct VoiceActor {
+    pub person: PersonMeta,
+    pub language: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct AnimeStaff {
+    pub person: PersonMeta,
+    pub positions: Vec<String>,
+}
+
+/// An episode from an anime's episode list
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Episode {
+    pub mal_id: u32,
+    pub url: Option<String>,
+    pub title: String,
+    pub title_japanese: Option<String>,
+    pub title_romanji: Option<String>,
+    pub aired: Option<String>,
+    pub score: Option<f32>,
+    #[serde(default)]
+    pub filler: bool,
+    #[serde(default)]
+    pub recap: bool,
+    pub forum_url: Option<String>,
+}
+
+/// A single episode, with its synopsis and duration
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct EpisodeDetails {
+    pub mal_id: u32,
+    pub url: String,
+    pub title: String,
+    pub title_japanese: Option<String>,
+    pub title_romanji: Option<String>,
+    pub duration: Option<u32>,
+    pub aired: Option<String>,
+    #[serde(default)]
+    pub filler: bool,
+    #[serde(default)]
+    pub recap: bool,
+    pub synopsis: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct AnimeVideos {
+    #[serde(default)]
+    pub promo: Vec<PromoVideo>,
+    #[serde(default)]
+    pub episodes: Vec<EpisodeVideo>,
+    #[serde(default)]
+    pub music_videos: Vec<MusicVideo>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct PromoVideo {
+    pub title: String,
+    pub trailer: Trailer,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct EpisodeVideo {
+    pub mal_id: u32,
+    pub url: String,
+    pub title: String,
+    pub episode: String,
+    pub images: Option<Images>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct MusicVideo {
+    pub title: String,
+    pub video: Trailer,
+    pub meta: MusicVideoMeta,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct MusicVideoMeta {
+    pub title: Option<String>,
+    pub author: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct AnimeStatistics {
+    pub watching: u32,
+    pub completed: u32,
+    pub on_hold: u32,
+    pub dropped: u32,
+    pub plan_to_watch: u32,
+    pub total: u32,
+    #[serde(default)]
+    pub scores: Vec<ScoreCount>,
+}
+
+// Jikan's `/moreinfo` is an object with one field
+#[derive(Debug, Deserialize)]
+pub(crate) struct MoreInfo {
+    pub moreinfo: Option<String>,
+}
+
+// -------- JikanManga --------
+
+/// A manga from Jikan's `/manga/{id}/full`.<br>
+/// `relations` and `external` are only given by the full endpoint, and are empty elsewhere.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct JikanManga {
+    pub mal_id: u32,
+    pub url: String,
+    pub images: Images,
+    pub approved: Option<bool>,
+    #[serde(default)]
+    pub titles: Vec<Title>,
+    pub title: String,
+    pub title_english: Option<String>,
+    pub title_japanese: Option<String>,
+    #[serde(default)]
+    pub title_synonyms: Vec<String>,
+    #[serde(rename = "type")]
+    pub media_type: Option<String>,
+    pub chapters: Option<u32>,
+    pub volumes: Option<u32>,
+    pub status: Option<String>,
+    #[serde(default)]
+    pub publishing: bool,
+    pub published: Option<DateRange>,
+    pub score: Option<f32>,
+    pub scored_by: Option<u32>,
+    pub rank: Option<u32>,
+    pub popularity: Option<u32>,
+    pub members: Option<u32>,
+    pub favorites: Option<u32>,
+    pub synopsis: Option<String>,
+    pub background: Option<String>,
+    #[serde(default)]
+    pub authors: Vec<MalUrl>,
+    #[serde(default)]
+    pub serializations: Vec<MalUrl>,
+    #[serde(default)]
+    pub genres: Vec<MalUrl>,
+    #[serde(default)]
+    pub explicit_genres: Vec<MalUrl>,
+    #[serde(default)]
+    pub themes: Vec<MalUrl>,
+    #[serde(default)]
+    pub demographics: Vec<MalUrl>,
+    #[serde(default)]
+    pub relations: Vec<Relation>,
+    #[serde(default)]
+    pub external: Vec<External>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct MangaCharacter {
+    pub character: CharacterMeta,
+    pub role: String,
+}
+
+// -------- Characters and People --------
+
+/// A character from Jikan's `/characters/{id}/full`.<br>
+/// The `mal_id` of each anime and manga can be used with `myanimelist::retrieval::get_anime` and `get_manga`.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Character {
+    pub mal_id: u32,
+    pub url: String,
+    pub images: Images,
+    pub name: String,
+    pub name_kanji: Option<String>,
+    #[serde(default)]
+    pub nicknames: Vec<String>,
+    pub favorites: u32,
+    pub about: Option<String>,
+    #[serde(default)]
+    pub anime: Vec<CharacterAnime>,
+    #[serde(default)]
+    pub manga: Vec<CharacterManga>,
+    #[serde(default)]
+    pub voices: Vec<VoiceActor>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct CharacterAnime {
+    pub role: String,
+    pub anime: EntryMeta,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct CharacterManga {
+    pub role: String,
+    pub manga: EntryMeta,
+}
+
+/// A person from Jikan's `/people/{id}/full`, with their voice acting roles and staff positions.<br>
+/// The `mal_id` of each anime and manga can be used with `myanimelist::retrieval::get_anime` and `get_manga`.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Person {
+    pub mal_id: u32,
+    pub url: String,
+    pub website_url: Option<String>,
+    pub images: Images,
+    pub name: String,
+    pub given_name: Option<String>,
+    pub family_name: Option<String>,
+    #[serde(default)]
+    pub alternate_names: Vec<String>,
+    pub birthday: Option<String>,
+    pub favorites: u32,
+    pub about: Option<String>,
+    #[serde(default)]
+    pub anime: Vec<PersonAnime>,
+    #[serde(default)]
+    pub manga: Vec<PersonManga>,
+    #[serde(default)]
+    pub voices: Vec<PersonVoice>,
+}
+
+/// A staff position of a person on an anime
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct PersonAnime {
+    pub position: String,
+    pub anime: EntryMeta,
+}
+
+/// A position of a person on a manga, such as the story or art
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct PersonManga {
+    pub position: String,
+    pub manga: EntryMeta,
+}
+
+/// A voice acting role of a person
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct PersonVoice {
+    pub role: String,
+    pub anime: EntryMeta,
+    pub character: CharacterMeta,
+}
+
+// -------- Producers and Magazines --------
+
+/// A producer, studio or licensor, from Jikan's `/producers/{id}/full`
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Producer {
+    pub mal_id: u32,
+    pub url: String,
+    #[serde(default)]
+    pub titles: Vec<Title>,
+    pub images: Option<Images>,
+    pub favorites: u32,
+    pub established: Option<String>,
+    pub about: Option<String>,
+    /// The number of anime the producer is credited on
+    pub count: u32,
+    #[serde(default)]
+    pub external: Vec<External>,
+}
+
+/// A magazine that serializes manga
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Magazine {
+    pub mal_id: u32,
+    pub name: String,
+    pub url: String,
+    /// The number of manga the magazine has serialized
+    pub count: u32,
+}
+
+// -------- Users --------
+
+/// A user's profile, from Jikan's `/users/{username}/full`
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct UserProfile {
+    pub mal_id: Option<u32>,
+    pub username: String,
+    pub url: String,
+    pub images: Option<Images>,
+    pub last_online: Option<String>,
+    pub gender: Option<String>,
+    pub birthday: Option<String>,
+    pub location: Option<String>,
+    pub joined: Option<String>,
+    pub statistics: Option<UserStatistics>,
+    #[serde(default)]
+    pub external: Vec<External>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct UserStatistics {
+    pub anime: UserAnimeStatistics,
+    pub manga: UserMangaStatistics,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct UserAnimeStatistics {
+    pub days_watched: f32,
+    pub mean_score: f32,
+    pub watching: u32,
+    pub completed: u32,
+    pub on_hold: u32,
+    pub dropped: u32,
+    pub plan_to_watch: u32,
+    pub total_entries: u32,
+    pub rewatched: u32,
+    pub episodes_watched: u32,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct UserMangaStatistics {
+    pub days_read: f32,
+    pub mean_score: f32,
+    pub reading: u32,
+    pub completed: u32,
+    pub on_hold: u32,
+    pub dropped: u32,
+    pub plan_to_read: u32,
+    pub total_entries: u32,
+    pub reread: u32,
+    pub chapters_read: u32,
+    pub volumes_read: u32,
+}
+
+/// The anime, manga, characters and people a user has favorited
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct UserFavorites {
+    #[serde(default)]
+    pub anime: Vec<FavoriteEntry>,
+    #[serde(default)]
+    pub manga: Vec<FavoriteEntry>,
+    #[serde(default)]
+    pub characters: Vec<CharacterMeta>,
+    #[serde(default)]
+    pub people: Vec<PersonMeta>,
+}
+
+/// A favorited anime or manga
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct FavoriteEntry {
+    pub mal_id: u32,
+    pub url: String,
+    pub images: Images,
+    pub title: String,
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    pub start_year: Option<u32>,
+}
+
+/// An update to a user's list, such as watching an episode.<br>
+/// `increment` is the episode or chapter the user reached.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub entry: MalUrl,
+    pub increment: u32,
+    pub date: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Friend {
+    pub user: UserMeta,
+    pub last_online: Option<String>,
+    pub friends_since: Option<String>,
+}
+
+/// A review written by a user, with the anime or manga it reviews
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct UserReview {
+    pub entry: EntryMeta,
+    #[serde(flatten)]
+    pub review: Review,
+}
+
+/// A pair of anime or manga a user recommends together, with why
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct UserRecommendation {
+    pub mal_id: String,
+    pub entry: Vec<EntryMeta>,
+    pub content: String,
+    pub date: Option<String>,
+    pub user: UserMeta,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct UserClub {
+    pub mal_id: u32,
+    pub name: String,
+    pub url: String,
+}
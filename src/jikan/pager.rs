@@ -0,0 +1,100 @@
+use std::{collections::VecDeque, error::Error, future::Future};
+use super::models::JikanPage;
+
+/// Pages through any paginated `jikan` function, requesting the next page only once the last one is used up.<br>
+/// It takes a closure from a page number to that page, and stops after the page with no `has_next_page`.
+/// Every request still goes through the client's rate limits.
+/// ### Example usage:
+/// ```
+/// use mal_query::jikan::{self, JikanPager, models::TopAnimeFilter};
+/// async fn pager_example() {
+///     let mut pager = JikanPager::new(|page| jikan::get_top_anime(None, Some(TopAnimeFilter::Airing), page));
+///     // One anime at a time
+///     while let Some(anime) = pager.next().await.unwrap() {
+///         println!("{}", anime.title);
+///     }
+///
+///     // Or the first 50 episodes, however many pages that is
+///     let episodes = JikanPager::new(|page| jikan::get_anime_episodes(21, page))
+///         .take(50)
+///         .await
+///         .unwrap();
+/// }
+/// ```
+pub struct JikanPager<T, F> {
+    fetch: F,
+    page: u32,
+    has_next_page: bool,
+    last_visible_page: Option<u32>,
+    buffer: VecDeque<T>,
+}
+
+impl<T, F, Fut> JikanPager<T, F>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<JikanPage<T>, Box<dyn Error>>>,
+{
+    /// Takes a closure that gets a page, such as `|page| jikan::get_season_now(page)`, starting at page 1
+    pub fn new(fetch: F) -> Self {
+        JikanPager {
+            fetch,
+            page: 1,
+            has_next_page: true,
+            last_visible_page: None,
+            buffer: VecDeque::new(),
+        }
+    }
+    /// Changes the page the pager starts from, before it is used
+    pub fn start_page(&mut self, page: u32) -> &mut Self {
+        self.page = page.max(1);
+        self
+    }
+    /// Async function, function must be called with `.await`<br>
+    /// Returns a `Result<>`, with a success containing the next whole page, or `None` after the last page.<br>
+    /// Items left from a page partly read with `.next()` are skipped.
+    pub async fn next_page(&mut self) -> Result<Option<JikanPage<T>>, Box<dyn Error>> {
+        self.buffer.clear();
+        if !self.has_next_page { return Ok(None) }
+        let page = (self.fetch)(self.page).await?;
+        self.has_next_page = page.pagination.has_next_page;
+        self.last_visible_page = Some(page.pagination.last_visible_page);
+        self.page += 1;
+        Ok(Some(page))
+    }
+    /// Async function, function must be called with `.await`<br>
+    /// Returns a `Result<>`, with a success containing the next item, or `None` after the last item of the last page
+    pub async fn next(&mut self) -> Result<Option<T>, Box<dyn Error>> {
+        while self.buffer.is_empty() {
+            match self.next_page().await? {
+                Some(page) => self.buffer.extend(page.data),
+                None => return Ok(None),
+            }
+        }
+        Ok(self.buffer.pop_front())
+    }
+    /// Async function, function must be called with `.await`<br>
+    /// Returns a `Result<>`, with a success containing up to `count` of the next items
+    pub async fn take(&mut self, count: usize) -> Result<Vec<T>, Box<dyn Error>> {
+        let mut items = Vec::new();
+        while items.len() < count {
+            match self.next().await? {
+                Some(item) => items.push(item),
+                None => break,
+            }
+        }
+        Ok(items)
+    }
+    /// Async function, function must be called with `.await`<br>
+    /// Returns a `Result<>`, with a success containing every remaining item, from every remaining page
+    pub async fn collect_all(&mut self) -> Result<Vec<T>, Box<dyn Error>> {
+        self.take(usize::MAX).await
+    }
+    /// The number of the last page, once the first page has been requested
+    pub fn last_visible_page(&self) -> Option<u32> {
+        self.last_visible_page
+    }
+    /// Whether there are pages left to request
+    pub fn has_next_page(&self) -> bool {
+        self.has_next_page
+    }
+}
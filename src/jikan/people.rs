@@ -0,0 +1,10 @@
+use std::error::Error;
+use super::{models::*, run_get};
+
+/// Takes a person ID, and gets the person, their voice acting roles, and their staff positions from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `Person`
+pub async fn get_person(id: u32) -> Result<Person, Box<dyn Error>> {
+    let path = format!("/people/{id}/full");
+    run_get(&path).await
+}
@@ -0,0 +1,22 @@
+use std::error::Error;
+use url::form_urlencoded::Serializer;
+use super::{models::*, run_get, run_get_page};
+
+/// Takes a producer ID, and gets the producer, studio or licensor from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `Producer`
+pub async fn get_producer(id: u32) -> Result<Producer, Box<dyn Error>> {
+    let path = format!("/producers/{id}/full");
+    run_get(&path).await
+}
+
+/// Takes a name to search for (or `""` for every magazine) and a page number (starting at 1), and gets that page
+/// of magazines from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanPage` of `Magazine`
+pub async fn get_magazines(name: &str, page: u32) -> Result<JikanPage<Magazine>, Box<dyn Error>> {
+    let mut query = Serializer::new(String::new());
+    if !name.is_empty() { query.append_pair("q", name); }
+    query.append_pair("page", &page.to_string());
+    run_get_page(&format!("/magazines?{}", query.finish())).await
+}
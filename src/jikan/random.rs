@@ -0,0 +1,16 @@
+use std::error::Error;
+use super::{models::*, run_get};
+
+/// Gets a random anime from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanAnime`
+pub async fn get_random_anime() -> Result<JikanAnime, Box<dyn Error>> {
+    run_get("/random/anime").await
+}
+
+/// Gets a random manga from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanManga`
+pub async fn get_random_manga() -> Result<JikanManga, Box<dyn Error>> {
+    run_get("/random/manga").await
+}
@@ -0,0 +1,14 @@
+use std::error::Error;
+use super::{models::*, run_get_page};
+
+/// Takes a `ScheduleDay` (or `None` for every day) and a page number (starting at 1), and gets that page of
+/// the currently airing anime broadcast on that day from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanPage` of `JikanAnime`
+pub async fn get_schedules(day: Option<ScheduleDay>, page: u32) -> Result<JikanPage<JikanAnime>, Box<dyn Error>> {
+    let path = match day {
+        Some(day) => format!("/schedules?filter={}&page={page}", day.as_query()),
+        None => format!("/schedules?page={page}"),
+    };
+    run_get_page(&path).await
+}
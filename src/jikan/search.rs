@@ -0,0 +1,224 @@
+use std::error::Error;
+use url::form_urlencoded::Serializer;
+use crate::myanimelist::models::{Genres, Rating};
+use super::{models::*, run_get_page};
+
+/// Builds a search of Jikan's `/anime`, with the filters MyAnimeList's own search doesn't have.<br>
+/// Every filter is optional, and an empty query with no filters lists every anime.
+/// ### Example usage:
+/// ```
+/// use mal_query::jikan::{AnimeSearchBuilder, JikanPager, models::*};
+/// use mal_query::myanimelist::models::Genres;
+/// async fn anime_search_builder_example() {
+///     // TV anime from 2010 to 2015, scoring at least 8, without genre 12
+///     let hentai = Genres { id: 12, name: "Hentai".to_string() };
+///     let mut search = AnimeSearchBuilder::new("");
+///     search
+///         .kind(AnimeType::TV)
+///         .start_date("2010-01-01")
+///         .end_date("2015-12-31")
+///         .min_score(8.0)
+///         .genres_exclude(&[hentai])
+///         .order_by(AnimeOrderBy::Score)
+///         .sort(SortOrder::Descending);
+///     let first_page = search.run().await.unwrap();
+///
+///     // Or every page of results
+///     let all = JikanPager::new(|page| search.run_page(page)).collect_all().await.unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimeSearchBuilder {
+    params: Vec<(&'static str, String)>,
+}
+
+/// Builds a search of Jikan's `/manga`, with the filters MyAnimeList's own search doesn't have.<br>
+/// Every filter is optional, and an empty query with no filters lists every manga.
+/// ### Example usage:
+/// ```
+/// use mal_query::jikan::{MangaSearchBuilder, models::*};
+/// async fn manga_search_builder_example() {
+///     let finished_manhwa = MangaSearchBuilder::new("")
+///         .kind(MangaType::Manhwa)
+///         .status(MangaStatus::Complete)
+///         .order_by(MangaOrderBy::Members)
+///         .sort(SortOrder::Descending)
+///         .run()
+///         .await
+///         .unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MangaSearchBuilder {
+    params: Vec<(&'static str, String)>,
+}
+
+impl AnimeSearchBuilder {
+    /// Takes a name to search for, or `""` to only use filters
+    pub fn new(query: &str) -> Self {
+        let mut builder = AnimeSearchBuilder { params: Vec::new() };
+        if !query.is_empty() { builder.set("q", query.to_string()); }
+        builder
+    }
+    /// Only includes anime of this `AnimeType`
+    pub fn kind(&mut self, kind: AnimeType) -> &mut Self {
+        self.set("type", kind.as_query().to_string())
+    }
+    /// Only includes anime with this `AnimeStatus`
+    pub fn status(&mut self, status: AnimeStatus) -> &mut Self {
+        self.set("status", status.as_query().to_string())
+    }
+    /// Only includes anime with this age `Rating`
+    pub fn rating(&mut self, rating: Rating) -> &mut Self {
+        let s: &str = match rating {
+            Rating::G => "g",
+            Rating::PG => "pg",
+            Rating::PG13 => "pg13",
+            Rating::R => "r17",
+            Rating::RPlus => "r",
+            Rating::RX => "rx",
+        };
+        self.set("rating", s.to_string())
+    }
+    /// Only includes anime made by one of these producers or studios, by their MyAnimeList IDs
+    pub fn producers(&mut self, ids: &[u32]) -> &mut Self {
+        self.set("producers", join_ids(ids))
+    }
+    /// Orders the results by this field, instead of by relevance
+    pub fn order_by(&mut self, order_by: AnimeOrderBy) -> &mut Self {
+        self.set("order_by", order_by.as_query().to_string())
+    }
+    /// Calls the Jikan API to get the first page of anime matching the search<br>
+    /// Async function, function must be called with `.await`<br>
+    /// Returns a `Result<>`, with a success containing a `JikanPage` of `JikanAnime`
+    pub async fn run(&self) -> Result<JikanPage<JikanAnime>, Box<dyn Error>> {
+        self.run_page(1).await
+    }
+    /// Calls the Jikan API to get a page (starting at 1) of anime matching the search.
+    /// Can be passed to a `JikanPager`.<br>
+    /// Async function, function must be called with `.await`<br>
+    /// Returns a `Result<>`, with a success containing a `JikanPage` of `JikanAnime`
+    pub async fn run_page(&self, page: u32) -> Result<JikanPage<JikanAnime>, Box<dyn Error>> {
+        run_get_page(&self.path("/anime", page)).await
+    }
+}
+
+impl MangaSearchBuilder {
+    /// Takes a name to search for, or `""` to only use filters
+    pub fn new(query: &str) -> Self {
+        let mut builder = MangaSearchBuilder { params: Vec::new() };
+        if !query.is_empty() { builder.set("q", query.to_string()); }
+        builder
+    }
+    /// Only includes manga of this `MangaType`
+    pub fn kind(&mut self, kind: MangaType) -> &mut Self {
+        self.set("type", kind.as_query().to_string())
+    }
+    /// Only includes manga with this `MangaStatus`
+    pub fn status(&mut self, status: MangaStatus) -> &mut Self {
+        self.set("status", status.as_query().to_string())
+    }
+    /// Only includes manga serialized in one of these magazines, by their MyAnimeList IDs
+    pub fn magazines(&mut self, ids: &[u32]) -> &mut Self {
+        self.set("magazines", join_ids(ids))
+    }
+    /// Orders the results by this field, instead of by relevance
+    pub fn order_by(&mut self, order_by: MangaOrderBy) -> &mut Self {
+        self.set("order_by", order_by.as_query().to_string())
+    }
+    /// Calls the Jikan API to get the first page of manga matching the search<br>
+    /// Async function, function must be called with `.await`<br>
+    /// Returns a `Result<>`, with a success containing a `JikanPage` of `JikanManga`
+    pub async fn run(&self) -> Result<JikanPage<JikanManga>, Box<dyn Error>> {
+        self.run_page(1).await
+    }
+    /// Calls the Jikan API to get a page (starting at 1) of manga matching the search.
+    /// Can be passed to a `JikanPager`.<br>
+    /// Async function, function must be called with `.await`<br>
+    /// Returns a `Result<>`, with a success containing a `JikanPage` of `JikanManga`
+    pub async fn run_page(&self, page: u32) -> Result<JikanPage<JikanManga>, Box<dyn Error>> {
+        run_get_page(&self.path("/manga", page)).await
+    }
+}
+
+fn join_ids(ids: &[u32]) -> String {
+    ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(",")
+}
+
+fn join_genre_ids(genres: &[Genres]) -> String {
+    join_ids(&genres.iter().map(|genre| genre.id).collect::<Vec<_>>())
+}
+
+// The filters both searches share
+macro_rules! impl_search_filters {
+    ($($struct:ident),*) => {
+        $(
+            impl $struct {
+                /// Only includes entries with exactly this score
+                pub fn score(&mut self, score: f32) -> &mut Self {
+                    self.set("score", score.to_string())
+                }
+                /// Only includes entries scoring at least this
+                pub fn min_score(&mut self, score: f32) -> &mut Self {
+                    self.set("min_score", score.to_string())
+                }
+                /// Only includes entries scoring at most this
+                pub fn max_score(&mut self, score: f32) -> &mut Self {
+                    self.set("max_score", score.to_string())
+                }
+                /// Leaves out adult entries when `true`
+                pub fn sfw(&mut self, sfw: bool) -> &mut Self {
+                    self.set("sfw", sfw.to_string())
+                }
+                /// Only includes entries with all of these genres, such as the `genres` of a `MalAnimeData`.<br>
+                /// MyAnimeList and Jikan share genre IDs, so only each `id` is sent.
+                pub fn genres(&mut self, genres: &[Genres]) -> &mut Self {
+                    self.set("genres", join_genre_ids(genres))
+                }
+                /// Leaves out entries with any of these genres
+                pub fn genres_exclude(&mut self, genres: &[Genres]) -> &mut Self {
+                    self.set("genres_exclude", join_genre_ids(genres))
+                }
+                /// Sorts the results in this direction, used with `.order_by()`
+                pub fn sort(&mut self, sort: SortOrder) -> &mut Self {
+                    self.set("sort", sort.as_query().to_string())
+                }
+                /// Only includes entries whose title starts with this letter
+                pub fn letter(&mut self, letter: char) -> &mut Self {
+                    self.set("letter", letter.to_string())
+                }
+                /// Only includes entries starting on or after this date, as `YYYY`, `YYYY-MM` or `YYYY-MM-DD`
+                pub fn start_date(&mut self, date: &str) -> &mut Self {
+                    self.set("start_date", date.to_string())
+                }
+                /// Only includes entries ending on or before this date, as `YYYY`, `YYYY-MM` or `YYYY-MM-DD`
+                pub fn end_date(&mut self, date: &str) -> &mut Self {
+                    self.set("end_date", date.to_string())
+                }
+                /// Sets how many results are on each page. Jikan allows up to 25, the default
+                pub fn limit(&mut self, limit: u32) -> &mut Self {
+                    self.set("limit", limit.to_string())
+                }
+
+                // The path and query string of the search, for a page
+                fn path(&self, endpoint: &str, page: u32) -> String {
+                    let mut query = Serializer::new(String::new());
+                    for (key, value) in &self.params {
+                        query.append_pair(key, value);
+                    }
+                    query.append_pair("page", &page.to_string());
+                    format!("{endpoint}?{}", query.finish())
+                }
+
+                // Setting a filter again replaces it
+                fn set(&mut self, key: &'static str, value: String) -> &mut Self {
+                    self.params.retain(|(k, _)| *k != key);
+                    self.params.push((key, value));
+                    self
+                }
+            }
+        )*
+    };
+}
+
+impl_search_filters!(AnimeSearchBuilder, MangaSearchBuilder);
@@ -0,0 +1,42 @@
+use std::error::Error;
+use crate::myanimelist::models::Season;
+use super::{models::*, run_get, run_get_page};
+
+/// Takes a page number (starting at 1), and gets that page of the anime airing this season from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanPage` of `JikanAnime`
+pub async fn get_season_now(page: u32) -> Result<JikanPage<JikanAnime>, Box<dyn Error>> {
+    let path = format!("/seasons/now?page={page}");
+    run_get_page(&path).await
+}
+
+/// Takes a page number (starting at 1), and gets that page of the announced anime that haven't aired yet
+/// from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanPage` of `JikanAnime`
+pub async fn get_season_upcoming(page: u32) -> Result<JikanPage<JikanAnime>, Box<dyn Error>> {
+    let path = format!("/seasons/upcoming?page={page}");
+    run_get_page(&path).await
+}
+
+/// Takes a year, a `Season` and a page number (starting at 1), and gets that page of the season's anime
+/// from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanPage` of `JikanAnime`
+pub async fn get_season(year: u32, season: Season, page: u32) -> Result<JikanPage<JikanAnime>, Box<dyn Error>> {
+    let s: &str = match season {
+        Season::Winter => "winter",
+        Season::Spring => "spring",
+        Season::Summer => "summer",
+        Season::Fall => "fall"
+    };
+    let path = format!("/seasons/{year}/{s}?page={page}");
+    run_get_page(&path).await
+}
+
+/// Gets every year and season that MyAnimeList has anime for, from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a Vector of `SeasonYear`, newest first
+pub async fn get_seasons_list() -> Result<Vec<SeasonYear>, Box<dyn Error>> {
+    run_get("/seasons").await
+}
@@ -0,0 +1,56 @@
+use std::error::Error;
+use super::{models::*, run_get_page};
+
+/// Takes an optional `AnimeType` and `TopAnimeFilter`, and a page number (starting at 1), and gets that page
+/// of the top anime from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanPage` of `JikanAnime`
+pub async fn get_top_anime(
+    kind: Option<AnimeType>,
+    filter: Option<TopAnimeFilter>,
+    page: u32,
+) -> Result<JikanPage<JikanAnime>, Box<dyn Error>> {
+    let mut path = format!("/top/anime?page={page}");
+    if let Some(kind) = kind {
+        path.push_str(&format!("&type={}", kind.as_query()));
+    }
+    if let Some(filter) = filter {
+        path.push_str(&format!("&filter={}", filter.as_query()));
+    }
+    run_get_page(&path).await
+}
+
+/// Takes an optional `MangaType` and `TopMangaFilter`, and a page number (starting at 1), and gets that page
+/// of the top manga from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanPage` of `JikanManga`
+pub async fn get_top_manga(
+    kind: Option<MangaType>,
+    filter: Option<TopMangaFilter>,
+    page: u32,
+) -> Result<JikanPage<JikanManga>, Box<dyn Error>> {
+    let mut path = format!("/top/manga?page={page}");
+    if let Some(kind) = kind {
+        path.push_str(&format!("&type={}", kind.as_query()));
+    }
+    if let Some(filter) = filter {
+        path.push_str(&format!("&filter={}", filter.as_query()));
+    }
+    run_get_page(&path).await
+}
+
+/// Takes a page number (starting at 1), and gets that page of the most favorited characters from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanPage` of `Character`
+pub async fn get_top_characters(page: u32) -> Result<JikanPage<Character>, Box<dyn Error>> {
+    let path = format!("/top/characters?page={page}");
+    run_get_page(&path).await
+}
+
+/// Takes a page number (starting at 1), and gets that page of the most favorited people from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanPage` of `Person`
+pub async fn get_top_people(page: u32) -> Result<JikanPage<Person>, Box<dyn Error>> {
+    let path = format!("/top/people?page={page}");
+    run_get_page(&path).await
+}
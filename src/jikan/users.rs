@@ -0,0 +1,72 @@
+use std::error::Error;
+use super::{models::*, run_get, run_get_page};
+
+/// Takes a username, and gets the user's profile and list statistics from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `UserProfile`
+pub async fn get_user(username: &str) -> Result<UserProfile, Box<dyn Error>> {
+    let path = format!("/users/{username}/full");
+    run_get(&path).await
+}
+
+/// Takes a username, and gets the statistics of the user's anime and manga lists from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `UserStatistics`
+pub async fn get_user_statistics(username: &str) -> Result<UserStatistics, Box<dyn Error>> {
+    let path = format!("/users/{username}/statistics");
+    run_get(&path).await
+}
+
+/// Takes a username, and gets the user's favorite anime, manga, characters and people from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `UserFavorites`
+pub async fn get_user_favorites(username: &str) -> Result<UserFavorites, Box<dyn Error>> {
+    let path = format!("/users/{username}/favorites");
+    run_get(&path).await
+}
+
+/// Takes a username and a `HistoryType` (or `None` for both), and gets the user's recent list updates
+/// from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a Vector of `HistoryEntry`, newest first
+pub async fn get_user_history(username: &str, kind: Option<HistoryType>) -> Result<Vec<HistoryEntry>, Box<dyn Error>> {
+    let path = match kind {
+        Some(kind) => format!("/users/{username}/history?type={}", kind.as_query()),
+        None => format!("/users/{username}/history"),
+    };
+    run_get(&path).await
+}
+
+/// Takes a username and a page number (starting at 1), and gets that page of the user's friends from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanPage` of `Friend`
+pub async fn get_user_friends(username: &str, page: u32) -> Result<JikanPage<Friend>, Box<dyn Error>> {
+    let path = format!("/users/{username}/friends?page={page}");
+    run_get_page(&path).await
+}
+
+/// Takes a username and a page number (starting at 1), and gets that page of the user's reviews from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanPage` of `UserReview`
+pub async fn get_user_reviews(username: &str, page: u32) -> Result<JikanPage<UserReview>, Box<dyn Error>> {
+    let path = format!("/users/{username}/reviews?page={page}");
+    run_get_page(&path).await
+}
+
+/// Takes a username and a page number (starting at 1), and gets that page of the user's recommendations
+/// from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanPage` of `UserRecommendation`
+pub async fn get_user_recommendations(username: &str, page: u32) -> Result<JikanPage<UserRecommendation>, Box<dyn Error>> {
+    let path = format!("/users/{username}/recommendations?page={page}");
+    run_get_page(&path).await
+}
+
+/// Takes a username and a page number (starting at 1), and gets that page of the clubs the user is in
+/// from the Jikan API<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `JikanPage` of `UserClub`
+pub async fn get_user_clubs(username: &str, page: u32) -> Result<JikanPage<UserClub>, Box<dyn Error>> {
+    let path = format!("/users/{username}/clubs?page={page}");
+    run_get_page(&path).await
+}
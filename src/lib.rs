@@ -83,12 +83,15 @@
 // TODO: delete manga
 
 // -- Jikan --
-// TODO: get_anime
-// TODO: get_anime_characters
-// TODO: get_anime_staff
-// etc. https://docs.api.jikan.moe/
+// TODO: more endpoints, https://docs.api.jikan.moe/
 
 pub mod myanimelist;
+pub mod provider;
+pub mod convert;
+pub mod config;
+mod rate_limit;
 
 #[cfg(feature = "jikan")]
 pub mod jikan;
+#[cfg(feature = "tui")]
+pub mod tui;
@@ -1,96 +1,500 @@
-#[allow(unused_imports)]
+use std::{error::Error, fmt, process::ExitCode};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use serde_json::json;
 use mal_query::myanimelist::{
     self,
-    builders::*, 
+    builders::*,
+    client::MalClient,
     models::*,
-    retrieval::*, 
-    user::*
+    retrieval,
+    user::{self, UpdateAnime},
 };
 
+// Exit codes. Clap also exits with 2 for usage errors
+const EXIT_ERROR: u8 = 1;
+const EXIT_USAGE: u8 = 2;
+const EXIT_NOT_LOGGED_IN: u8 = 3;
+const EXIT_NOT_FOUND: u8 = 4;
+
+/// A command-line client for MyAnimeList.
+///
+/// Exits with 0 on success, 1 when a request fails, 2 for invalid arguments,
+/// 3 when not logged in, and 4 when MyAnimeList has no such entry.
+#[derive(Parser)]
+#[command(name = "mal", version)]
+struct Cli {
+    /// Prints results as JSON instead of tables
+    #[arg(long, global = true)]
+    json: bool,
+    /// Sends requests to another MyAnimeList API URL, such as a proxy
+    #[arg(long, global = true)]
+    api_url: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Logs in with MyAnimeList in the browser, saving the token to token.txt
+    Login,
+    /// Logs out, deleting the saved token
+    Logout,
+    /// Shows the logged in user
+    Whoami,
+    /// Searches anime or manga by name
+    Search {
+        #[command(subcommand)]
+        kind: SearchKind,
+    },
+    /// Shows an anime or manga, by ID or MyAnimeList URL
+    Show {
+        target: String,
+        /// Treats an ID as a manga ID
+        #[arg(long)]
+        manga: bool,
+    },
+    /// Lists the anime of a season
+    Season {
+        year: u32,
+        #[arg(value_enum)]
+        season: SeasonArg,
+    },
+    /// Lists the top ranked anime
+    Ranking {
+        #[arg(long = "type", value_enum, default_value_t = RankingArg::All)]
+        ranking_type: RankingArg,
+        #[arg(long, default_value_t = 10)]
+        limit: u32,
+    },
+    /// Lists a user's anime list. `@me` is the logged in user
+    List {
+        user: String,
+        #[arg(long, value_enum)]
+        status: Option<StatusArg>,
+        #[arg(long, value_enum)]
+        sort: Option<SortArg>,
+        /// Only lists this many entries, instead of the whole list
+        #[arg(long)]
+        limit: Option<u32>,
+    },
+    /// Updates an anime on the logged in user's list
+    Update {
+        id: u32,
+        #[arg(long, value_parser = clap::value_parser!(u32).range(0..=10))]
+        score: Option<u32>,
+        /// The number of episodes watched
+        #[arg(long)]
+        episodes: Option<u32>,
+        #[arg(long, value_enum)]
+        status: Option<StatusArg>,
+        /// Replaces the entry's tags, separated by commas
+        #[arg(long, value_delimiter = ',')]
+        tags: Option<Vec<String>>,
+    },
+    /// Deletes an anime from the logged in user's list
+    Delete {
+        id: u32,
+    },
+    /// Browses and edits the logged in user's anime list in the terminal
+    #[cfg(feature = "tui")]
+    Tui,
+}
+
+#[derive(Subcommand)]
+enum SearchKind {
+    /// Searches anime by name
+    Anime {
+        #[arg(required = true)]
+        query: Vec<String>,
+        #[arg(long, default_value_t = 10)]
+        limit: u32,
+    },
+    /// Searches manga by name
+    Manga {
+        #[arg(required = true)]
+        query: Vec<String>,
+        #[arg(long, default_value_t = 10)]
+        limit: u32,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SeasonArg {
+    Winter,
+    Spring,
+    Summer,
+    Fall,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum RankingArg {
+    All,
+    Airing,
+    Upcoming,
+    Tv,
+    Ova,
+    Movie,
+    Special,
+    #[value(name = "bypopularity")]
+    ByPopularity,
+    Favorite,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum StatusArg {
+    #[value(name = "watching")]
+    Watching,
+    #[value(name = "completed")]
+    Completed,
+    #[value(name = "on_hold")]
+    OnHold,
+    #[value(name = "dropped")]
+    Dropped,
+    #[value(name = "plan_to_watch")]
+    PlanToWatch,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SortArg {
+    #[value(name = "score")]
+    ListScore,
+    #[value(name = "updated")]
+    ListUpdatedAt,
+    #[value(name = "title")]
+    AnimeTitle,
+    #[value(name = "start_date")]
+    AnimeStartDate,
+}
+
+impl From<SeasonArg> for Season {
+    fn from(season: SeasonArg) -> Self {
+        match season {
+            SeasonArg::Winter => Season::Winter,
+            SeasonArg::Spring => Season::Spring,
+            SeasonArg::Summer => Season::Summer,
+            SeasonArg::Fall => Season::Fall,
+        }
+    }
+}
+
+impl From<RankingArg> for RankingType {
+    fn from(ranking: RankingArg) -> Self {
+        match ranking {
+            RankingArg::All => RankingType::All,
+            RankingArg::Airing => RankingType::Airing,
+            RankingArg::Upcoming => RankingType::Upcoming,
+            RankingArg::Tv => RankingType::TV,
+            RankingArg::Ova => RankingType::OVA,
+            RankingArg::Movie => RankingType::Movie,
+            RankingArg::Special => RankingType::Special,
+            RankingArg::ByPopularity => RankingType::ByPopularity,
+            RankingArg::Favorite => RankingType::Favorite,
+        }
+    }
+}
+
+impl From<StatusArg> for Status {
+    fn from(status: StatusArg) -> Self {
+        match status {
+            StatusArg::Watching => Status::Watching,
+            StatusArg::Completed => Status::Completed,
+            StatusArg::OnHold => Status::OnHold,
+            StatusArg::Dropped => Status::Dropped,
+            StatusArg::PlanToWatch => Status::PlanToWatch,
+        }
+    }
+}
+
+impl From<SortArg> for Sort {
+    fn from(sort: SortArg) -> Self {
+        match sort {
+            SortArg::ListScore => Sort::ListScore,
+            SortArg::ListUpdatedAt => Sort::ListUpdatedAt,
+            SortArg::AnimeTitle => Sort::AnimeTitle,
+            SortArg::AnimeStartDate => Sort::AnimeStartDate,
+        }
+    }
+}
+
+// An invalid combination of arguments that clap can't catch itself
+#[derive(Debug)]
+struct UsageError(String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for UsageError {}
+
+// The library's errors are messages, so they're matched by their text
+fn exit_code(e: &(dyn Error + 'static)) -> u8 {
+    let message = e.to_string();
+    if e.is::<UsageError>() {
+        EXIT_USAGE
+    } else if message.contains("not logged in") || message.contains("status 401") {
+        EXIT_NOT_LOGGED_IN
+    } else if message.contains("status 404") {
+        EXIT_NOT_FOUND
+    } else {
+        EXIT_ERROR
+    }
+}
 
 #[tokio::main]
-#[allow(unused_variables)]
-async fn main() {
-    // match myanimelist::login().await {
-    //     Ok(()) => println!("Login Successful"),
-    //     Err(e) => eprintln!("Error: {e}"),
-    // }
-    let test: MalAnimeSearch = search_anime("One Piece", 1).await.unwrap();
-    println!("{test:?}");
-    // let test2: Vec<MalAnimeData> = get_season(2023, Season::Winter).await.unwrap();
-    // println!("{test2:?}");
-    // let test3: MalAnimeData = get_anime(13659).await.unwrap();
-    // println!("{:?}", test3.my_list_status);
-    // println!("{:?}", test3.recommendations);
-    // println!("{:?}", test3.main_picture.large);
-    // println!("{:?}", test3.synopsis);
-    // let test4: MalAnimeData = get_anime_from_url("https://myanimelist.net/anime/8769/Ore_no_Imouto_ga_Konnani_Kawaii_Wake_ga_Nai").await.unwrap();
-    // println!("{}", test4.title);
-    // let test5: Vec<MalAnimeData> = get_anime_ranking(RankingType::Airing, 10).await.unwrap();
-    // println!("{test5:?}");
-    // let test6: MalAnimeData = Builder::new(33)
-    //     .add_id()
-    //     .add_num_episodes()
-    //     .run()
-    //     .await
-    //     .unwrap();
-    // println!("{test6:?}");
-    // let test7: UpdateAnime = UpdateAnime::new(12291).await.unwrap();
-    // let search = search_anime("Gakkougurashi", 5).await.unwrap();
-    // let akame = search.get(0).unwrap();
-    // let akame_detailed = get_anime(akame.id).await.unwrap();
-    // println!("{:?}", &akame_detailed.my_list_status);
-    // let test2 = UpdateAnime::from_malanimedata(&akame_detailed)
-    //     .update_score(10)
-    //     .expect("Score is wrong")
-    //     .update()
-    //     .await;
-        
-    // match test2 {
-    //     Ok(data) => println!("success! {data:?}"),
-    //     Err(e) => eprintln!("{e}"),
-    // }
-    // let test8 = SearchBuilder::new("jujutsu", 3)
-    //     .add_id()
-    //     .add_num_episodes()
-    //     .run()
-    //     .await
-    //     .unwrap()
-    //     .to_titles();
-    // println!("{test8:?}");
-
-    // TODO: add field=list_score or something
-    // let test9 = UserListBuilder::new("naginis_api")
-    //     .sort(Sort::ListScore)
-    //     .limit(10)
-    //     .include_list_status()
-    //     .run()
-    //     .await
-    //     .unwrap();
-    // println!("{:?}", test9);
-
-    // let test10 = get_user_animelist("Naginipython", 10).await.unwrap();
-    // println!("{:?}", test10.get(0).unwrap().list_status);
-    
-    // let test11 = search_anime("azumanga", 2).await.unwrap();
-    // let search = test11.get(0).unwrap();
-    // let azumanga = Builder::new(search.id)
-    //     .add_my_list_status()
-    //     .run()
-    //     .await
-    //     .unwrap();
-    // println!("{azumanga:?}");
-
-    // let entry = test9.data.iter().find(|anime| anime.list_status.as_ref().unwrap().tags != Some([].to_vec())).unwrap();
-    // if let Err(e) = UpdateAnime::from_malanimedata(entry)
-    //     .update_tags(["test1", "test2", "test3"].to_vec())
-    //     .update_comments("This is a test")
-    //     .update_start_date(2024, 1, 4)
-    //     .update_finish_date(2024, 1, 3)
-    //     .update()
-    //     .await {
-    //         eprintln!("{e}");
-    //     }
-    // println!("{entry:?}");
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    if let Some(api_url) = &cli.api_url {
+        myanimelist::set_client(MalClient::new().api_url(api_url));
+    }
+    match run(cli.command, cli.json).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            ExitCode::from(exit_code(e.as_ref()))
+        }
+    }
+}
+
+async fn run(command: Command, json: bool) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Login => {
+            myanimelist::login().await?;
+            message(json, json!({ "logged_in": true }), "Logged in")
+        }
+        Command::Logout => {
+            myanimelist::logout()?;
+            message(json, json!({ "logged_in": false }), "Logged out")
+        }
+        Command::Whoami => {
+            let me = user::get_my_user().await?;
+            if json { return print_json(&me) }
+            println!("{} (ID {})", me.name, me.id);
+            Ok(())
+        }
+        Command::Search { kind: SearchKind::Anime { query, limit } } => {
+            let search = SearchBuilder::new(&query.join(" "), limit)
+                .add_media_type()
+                .add_num_episodes()
+                .add_mean()
+                .run()
+                .await?;
+            if json { return print_json(&search.data) }
+            print_anime_table(&search.data);
+            Ok(())
+        }
+        Command::Search { kind: SearchKind::Manga { query, limit } } => {
+            let search = retrieval::search_manga(&query.join(" "), limit).await?;
+            if json { return print_json(&search.data) }
+            let rows = search.data.iter().map(|m| vec![m.id.to_string(), m.title.clone()]).collect();
+            print_table(&["ID", "Title"], rows);
+            Ok(())
+        }
+        Command::Show { target, manga } => {
+            let is_url = target.parse::<u32>().is_err();
+            if manga || (is_url && target.contains("/manga/")) {
+                let data = match is_url {
+                    true => retrieval::get_manga_from_url(&target).await?,
+                    false => retrieval::get_manga(target.parse()?).await?,
+                };
+                if json { return print_json(&data) }
+                print_manga(&data);
+            } else {
+                let data = match is_url {
+                    true => retrieval::get_anime_from_url(&target).await?,
+                    false => retrieval::get_anime(target.parse()?).await?,
+                };
+                if json { return print_json(&data) }
+                print_anime(&data);
+            }
+            Ok(())
+        }
+        Command::Season { year, season } => {
+            let search = SeasonalBuilder::new(year, season.into())
+                .add_media_type()
+                .add_num_episodes()
+                .add_mean()
+                .run()
+                .await?;
+            if json { return print_json(&search.data) }
+            print_anime_table(&search.data);
+            Ok(())
+        }
+        Command::Ranking { ranking_type, limit } => {
+            let search = retrieval::get_anime_rankings(ranking_type.into(), limit).await?;
+            if json { return print_json(&search.data) }
+            let rows = search.data
+                .iter()
+                .map(|a| vec![or_dash(a.rank), a.id.to_string(), a.title.clone()])
+                .collect();
+            print_table(&["Rank", "ID", "Title"], rows);
+            Ok(())
+        }
+        Command::List { user, status, sort, limit } => {
+            let mut builder = UserListBuilder::new(&user);
+            builder.include_list_status().add_num_episodes();
+            if let Some(status) = status { builder.status(status.into()); }
+            if let Some(sort) = sort { builder.sort(sort.into()); }
+            let list = match limit {
+                Some(limit) => builder.limit(limit).run().await?,
+                None => builder.run_all().await?,
+            };
+            if json { return print_json(&list.data) }
+            let rows = list.data
+                .iter()
+                .map(|a| {
+                    let status = a.list_status.as_ref();
+                    vec![
+                        a.id.to_string(),
+                        a.title.clone(),
+                        status.map(|s| name(&s.status)).unwrap_or_else(|| "-".to_string()),
+                        status.map(|s| s.score.to_string()).unwrap_or_else(|| "-".to_string()),
+                        format!(
+                            "{}/{}",
+                            status.map(|s| s.num_episodes_watched).unwrap_or(0),
+                            or_dash(a.num_episodes.filter(|&n| n > 0))
+                        ),
+                    ]
+                })
+                .collect();
+            print_table(&["ID", "Title", "Status", "Score", "Progress"], rows);
+            Ok(())
+        }
+        Command::Update { id, score, episodes, status, tags } => {
+            if score.is_none() && episodes.is_none() && status.is_none() && tags.is_none() {
+                return Err(UsageError("Nothing to update, give at least one of --score, --episodes, --status or --tags".to_string()))?
+            }
+            let mut update = UpdateAnime::new(id);
+            if let Some(score) = score { update.update_score(score)?; }
+            if let Some(episodes) = episodes { update.update_num_watched_episodes(episodes); }
+            if let Some(status) = status { update.update_status(status.into()); }
+            if let Some(tags) = &tags { update.update_tags(tags.iter().map(String::as_str).collect()); }
+            let result = update.update().await?;
+            if json { return print_json(&result) }
+            println!(
+                "Updated {id}: {}, score {}, {} episodes watched",
+                name(&result.status), result.score, result.num_episodes_watched
+            );
+            Ok(())
+        }
+        Command::Delete { id } => {
+            user::delete_anime(id).await?;
+            message(json, json!({ "deleted": id }), &format!("Deleted {id} from your list"))
+        }
+        #[cfg(feature = "tui")]
+        Command::Tui => mal_query::tui::run().await,
+    }
+}
+
+// -------- Output --------
+
+fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<(), Box<dyn Error>> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn message(json: bool, value: serde_json::Value, text: &str) -> Result<(), Box<dyn Error>> {
+    match json {
+        true => print_json(&value),
+        false => {
+            println!("{text}");
+            Ok(())
+        }
+    }
+}
+
+// The name MyAnimeList uses for an enum value, such as `plan_to_watch`
+fn name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        _ => "-".to_string(),
+    }
+}
+
+fn or_dash<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string())
+}
+
+// Left aligned columns, padded to the widest cell
+fn print_table(headers: &[&str], rows: Vec<Vec<String>>) {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        println!("{}", padded.join("  ").trim_end());
+    };
+    line(headers.to_vec());
+    for row in &rows {
+        line(row.iter().map(String::as_str).collect());
+    }
+}
+
+fn print_anime_table(anime: &[MalAnimeData]) {
+    let rows = anime
+        .iter()
+        .map(|a| vec![
+            a.id.to_string(),
+            a.title.clone(),
+            a.media_type.as_ref().map(name).unwrap_or_else(|| "-".to_string()),
+            or_dash(a.num_episodes.filter(|&n| n > 0)),
+            or_dash(a.mean),
+        ])
+        .collect();
+    print_table(&["ID", "Title", "Type", "Episodes", "Score"], rows);
+}
+
+fn print_anime(a: &MalAnimeData) {
+    println!("{} (ID {})", a.title, a.id);
+    if let Some(titles) = a.alternative_titles.as_ref().filter(|t| !t.en.is_empty()) {
+        println!("English:    {}", titles.en);
+    }
+    println!("Type:       {}", a.media_type.as_ref().map(name).unwrap_or_else(|| "-".to_string()));
+    println!("Episodes:   {}", or_dash(a.num_episodes.filter(|&n| n > 0)));
+    println!("Status:     {}", a.status.as_ref().map(name).unwrap_or_else(|| "-".to_string()));
+    println!("Aired:      {} to {}", or_dash(a.start_date.as_ref()), or_dash(a.end_date.as_ref()));
+    println!("Score:      {}", or_dash(a.mean));
+    println!("Rank:       {}", or_dash(a.rank));
+    println!("Popularity: {}", or_dash(a.popularity));
+    if let Some(genres) = &a.genres {
+        println!("Genres:     {}", genres.iter().map(|g| g.name.as_str()).collect::<Vec<_>>().join(", "));
+    }
+    if let Some(studios) = &a.studios {
+        println!("Studios:    {}", studios.iter().map(|s| s.name.as_str()).collect::<Vec<_>>().join(", "));
+    }
+    if let Some(status) = &a.list_status {
+        println!("Your list:  {}, score {}, {} episodes watched", name(&status.status), status.score, status.num_episodes_watched);
+    }
+    if let Some(synopsis) = &a.synopsis {
+        println!("\n{synopsis}");
+    }
+}
 
+fn print_manga(m: &MalMangaData) {
+    println!("{} (ID {})", m.title, m.id);
+    if let Some(titles) = m.alternative_titles.as_ref().filter(|t| !t.en.is_empty()) {
+        println!("English:    {}", titles.en);
+    }
+    println!("Type:       {}", m.media_type.as_ref().map(name).unwrap_or_else(|| "-".to_string()));
+    println!("Volumes:    {}", or_dash(m.num_volumes.filter(|&n| n > 0)));
+    println!("Chapters:   {}", or_dash(m.num_chapters.filter(|&n| n > 0)));
+    println!("Status:     {}", m.status.as_ref().map(name).unwrap_or_else(|| "-".to_string()));
+    println!("Published:  {} to {}", or_dash(m.start_date.as_ref()), or_dash(m.end_date.as_ref()));
+    println!("Score:      {}", or_dash(m.mean));
+    println!("Rank:       {}", or_dash(m.rank));
+    if let Some(genres) = &m.genres {
+        println!("Genres:     {}", genres.iter().map(|g| g.name.as_str()).collect::<Vec<_>>().join(", "));
+    }
+    if let Some(synopsis) = &m.synopsis {
+        println!("\n{synopsis}");
+    }
 }
@@ -1,6 +1,6 @@
 use std::error::Error;
 
-use super::{*, models::{MalAnimeData, Season, Status, Sort}};
+use super::{*, models::{MalAnimeData, MalMangaData, ReadingStatus, Season, Status, Sort}};
 
 pub struct Builder {
     url: String,
@@ -9,7 +9,7 @@ impl Builder {
     /// Takes an anime ID, and initializes a single entry retriever for the corresponding anime
     pub fn new(id: u32) -> Self {
         Builder {
-            url: format!("https://api.myanimelist.net/v2/anime/{id}?fields=").to_string(),
+            url: format!("/anime/{id}?fields="),
         }
     }
     /// Calls the MyAnimeList API to recieve anime created by the builder, based on the ID 
@@ -41,7 +41,7 @@ impl Builder {
     /// }
     /// ```
     pub async fn run(&self) -> Result<MalAnimeData, Box<dyn Error>> {
-        run_get(&self.url).await
+        run_get(&api_url(&self.url)).await
     }
 }
 
@@ -52,7 +52,7 @@ impl SearchBuilder {
     /// Takes an anime name and limiter, and initializes a search entry retriever for the corresponding anime
     pub fn new(name: &str, limit: u32) -> Self {
         SearchBuilder {
-            url: format!("https://api.myanimelist.net/v2/anime?q={name}&limit={limit}&fields=").to_string(),
+            url: format!("/anime?q={name}&limit={limit}&fields="),
         }
     }
     /// Calls the MyAnimeList API to recieve anime created by the builder, based on the name, a limiter, 
@@ -85,7 +85,7 @@ impl SearchBuilder {
     /// }
     /// ```
     pub async fn run(&self) -> Result<MalAnimeSearch, Box<dyn Error>> {
-        run_search(&self.url).await
+        run_search(&api_url(&self.url)).await
     }
 }
 
@@ -102,7 +102,7 @@ impl SeasonalBuilder {
             Season::Fall => "fall"
         };
         SeasonalBuilder {
-            url: format!("https://api.myanimelist.net/v2/anime/season/{year}/{s}?fields=").to_string(),
+            url: format!("/anime/season/{year}/{s}?fields="),
         }
     }
     /// Calls the MyAnimeList API to recieve anime created by the builder, based on the year, the season, 
@@ -127,18 +127,24 @@ impl SeasonalBuilder {
     /// }
     /// ```
     pub async fn run(&self) -> Result<MalAnimeSearch, Box<dyn Error>> {
-        run_search(&self.url).await
+        run_search(&api_url(&self.url)).await
     }
 }
 
+// The most entries MyAnimeList returns from a user's list at once
+const USER_LIST_PAGE: u32 = 1000;
+
 pub struct UserListBuilder {
     url: String,
+    fields: String,
 }
 impl UserListBuilder {
-    /// Takes a MyAnimeList username, and initializes a user's animelist retriever
+    /// Takes a MyAnimeList username, and initializes a user's animelist retriever.<br>
+    /// `"@me"` can be used for the logged in user's list.
     pub fn new(username: &str) -> Self {
         UserListBuilder {
-            url: format!("https://api.myanimelist.net/v2/users/{username}/animelist?")
+            url: format!("/users/{username}/animelist?"),
+            fields: String::new(),
         }
     }
     /// A filter added to UserListBuilder that will tell the `run()` to filter by the user's listed status
@@ -178,7 +184,7 @@ impl UserListBuilder {
     }
     /// A field added to UserListBuilder that will tell the `run()` to add the user's list details
     pub fn include_list_status(&mut self) -> &mut Self {
-        self.url.push_str("fields=list_status{{is_rewatching,num_times_rewatched,rewatch_value,priority,tags,comments,start_date,end_date}}&");
+        self.fields.push_str("list_status{is_rewatching,num_times_rewatched,rewatch_value,priority,tags,comments,start_date,finish_date},");
         self
     }
     /// Calls the MyAnimeList API to recieve a user's animelist created by the builder, based on the username, 
@@ -203,9 +209,159 @@ impl UserListBuilder {
     /// }
     /// ```
     pub async fn run(&self) -> Result<MalAnimeSearch, Box<dyn Error>> {
-        run_search(&self.url).await
+        run_search(&self.full_url()).await
+    }
+    /// Calls the MyAnimeList API like `run()`, but follows every page of the user's list to receive all of it.<br>
+    /// The page size and offset are managed by this method, so `.limit()` and `.offset()` should not be used with it.
+    pub async fn run_all(&self) -> Result<MalAnimeSearch, Box<dyn Error>> {
+        let mut result: Vec<MalAnimeData> = Vec::new();
+        self.for_each_page(|page| {
+            result.extend(page.data);
+            Ok(())
+        }).await?;
+        Ok(MalAnimeSearch::new(result))
+    }
+    /// Calls the MyAnimeList API like `run_all()`, but hands each page to `f` as it is received, instead of keeping the
+    /// whole list. Used to stream big lists, such as into `export::CsvWriter`.<br>
+    /// An error from `f` stops the paging, and is returned.
+    /// ### Example usage:
+    /// ```
+    /// use mal_query::myanimelist::{builders::UserListBuilder, export::JsonLinesWriter};
+    /// async fn for_each_page_example() {
+    ///     let mut writer = JsonLinesWriter::new(std::fs::File::create("animelist.jsonl").unwrap());
+    ///     UserListBuilder::new("naginis_api")
+    ///         .include_list_status()
+    ///         .for_each_page(|page| writer.write_all(&page.data))
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub async fn for_each_page<F>(&self, mut f: F) -> Result<(), Box<dyn Error>>
+    where F: FnMut(MalAnimeSearch) -> Result<(), Box<dyn Error>> {
+        let mut offset = 0;
+        loop {
+            let page = run_search(&format!("{}limit={USER_LIST_PAGE}&offset={offset}&", self.full_url())).await?;
+            let count = page.data.len();
+            offset += count;
+            f(page)?;
+            if count < USER_LIST_PAGE as usize { break }
+        }
+        Ok(())
+    }
+    // The filters, and the fields added with `include_list_status()` and `AddFields`
+    fn full_url(&self) -> String {
+        match self.fields.is_empty() {
+            true => api_url(&self.url),
+            false => api_url(&format!("{}fields={}&", self.url, self.fields)),
+        }
+    }
+}
+
+pub struct UserMangaListBuilder {
+    url: String,
+    fields: String,
+}
+impl UserMangaListBuilder {
+    /// Takes a MyAnimeList username, and initializes a user's mangalist retriever.<br>
+    /// `"@me"` can be used for the logged in user's list.
+    pub fn new(username: &str) -> Self {
+        UserMangaListBuilder {
+            url: format!("/users/{username}/mangalist?"),
+            fields: String::new(),
+        }
+    }
+    /// A filter added to UserMangaListBuilder that will tell the `run()` to filter by the user's listed status
+    pub fn status(&mut self, status: ReadingStatus) -> &mut Self {
+        let s: &str = match status {
+            ReadingStatus::Completed => "completed",
+            ReadingStatus::Dropped => "dropped",
+            ReadingStatus::OnHold => "on_hold",
+            ReadingStatus::PlanToRead => "plan_to_read",
+            ReadingStatus::Reading => "reading",
+        };
+        self.url.push_str(&format!("status={s}&"));
+        self
+    }
+    /// A filter added to UserMangaListBuilder that will tell the `run()` to sort the User's list.
+    /// The anime sorts sort by the manga's equivalent
+    pub fn sort(&mut self, sort: Sort) -> &mut Self {
+        let s: &str = match sort {
+            Sort::AnimeId => "manga_id",
+            Sort::AnimeStartDate => "manga_start_date",
+            Sort::AnimeTitle => "manga_title",
+            Sort::ListScore => "list_score",
+            Sort::ListUpdatedAt => "list_updated_at"
+        };
+        self.url.push_str(&format!("sort={s}&"));
+        self
+    }
+    /// A filter added to UserMangaListBuilder that will tell the `run()` to limit the entries in the User's list
+    pub fn limit(&mut self, limit: u32) -> &mut Self {
+        self.url.push_str(&format!("limit={limit}&"));
+        self
+    }
+    /// A filter added to UserMangaListBuilder that will tell the `run()` to offset the starting point of the user's list
+    pub fn offset(&mut self, offset: u32) -> &mut Self {
+        self.url.push_str(&format!("offset={offset}&"));
+        self
+    }
+    /// A field added to UserMangaListBuilder that will tell the `run()` to add the user's list details
+    pub fn include_list_status(&mut self) -> &mut Self {
+        self.fields.push_str("list_status{is_rereading,num_times_reread,reread_value,priority,tags,comments,start_date,finish_date},");
+        self
+    }
+    /// A field added to UserMangaListBuilder that will tell the `run()` to add each manga's media type
+    pub fn add_media_type(&mut self) -> &mut Self {
+        self.fields.push_str("media_type,");
+        self
+    }
+    /// A field added to UserMangaListBuilder that will tell the `run()` to add each manga's number of volumes
+    pub fn add_num_volumes(&mut self) -> &mut Self {
+        self.fields.push_str("num_volumes,");
+        self
+    }
+    /// A field added to UserMangaListBuilder that will tell the `run()` to add each manga's number of chapters
+    pub fn add_num_chapters(&mut self) -> &mut Self {
+        self.fields.push_str("num_chapters,");
+        self
+    }
+    /// Calls the MyAnimeList API to recieve a user's mangalist created by the builder, the same as
+    /// `UserListBuilder::run()`.<br>
+    /// Async function, function must be called with `.await`<br>
+    /// Returns a `Result<>`, with a success containing a `MalMangaSearch`
+    pub async fn run(&self) -> Result<MalMangaSearch, Box<dyn Error>> {
+        run_search_manga(&self.full_url()).await
+    }
+    /// Calls the MyAnimeList API like `run()`, but follows every page of the user's list to receive all of it.<br>
+    /// The page size and offset are managed by this method, so `.limit()` and `.offset()` should not be used with it.
+    pub async fn run_all(&self) -> Result<MalMangaSearch, Box<dyn Error>> {
+        let mut result: Vec<MalMangaData> = Vec::new();
+        self.for_each_page(|page| {
+            result.extend(page.data);
+            Ok(())
+        }).await?;
+        Ok(MalMangaSearch::new(result))
+    }
+    /// Calls the MyAnimeList API like `run_all()`, but hands each page to `f` as it is received, the same as
+    /// `UserListBuilder::for_each_page()`
+    pub async fn for_each_page<F>(&self, mut f: F) -> Result<(), Box<dyn Error>>
+    where F: FnMut(MalMangaSearch) -> Result<(), Box<dyn Error>> {
+        let mut offset = 0;
+        loop {
+            let page = run_search_manga(&format!("{}limit={USER_LIST_PAGE}&offset={offset}&", self.full_url())).await?;
+            let count = page.data.len();
+            offset += count;
+            f(page)?;
+            if count < USER_LIST_PAGE as usize { break }
+        }
+        Ok(())
+    }
+    fn full_url(&self) -> String {
+        match self.fields.is_empty() {
+            true => api_url(&self.url),
+            false => api_url(&format!("{}fields={}&", self.url, self.fields)),
+        }
     }
-    // TODO: feature: add Builder addon, to include more data
 }
 
 pub trait AddFields {
@@ -245,168 +401,169 @@ pub trait AddFields {
 
 // ChatGPT taught me that I can do this, a macro to add the methods to each struct.
 // Figured I'd rather have it one once than like, this times 3
+// The field given is where each builder keeps its list of fields
 macro_rules! impl_filters_for_builders {
-    ($($struct:ident),*) => {
+    ($fields:ident: $($struct:ident),*) => {
         $(
             impl AddFields for $struct {
                 /// Adds the option of an Id to be given to the final result
                 fn add_id(&mut self) -> &mut Self {
-                    self.url.push_str("id,");
+                    self.$fields.push_str("id,");
                     self
                 }
                 /// Adds the option of a title to be given to the final result
                 fn add_title(&mut self) -> &mut Self {
-                    self.url.push_str("title,");
+                    self.$fields.push_str("title,");
                     self
                 }
                 /// Adds the option of a Main Picture to be given to the final result
                 fn add_main_picture(&mut self) -> &mut Self {
-                    self.url.push_str("main_picture,");
+                    self.$fields.push_str("main_picture,");
                     self
                 }
                 /// Adds the option of Alternate Titles to be given to the final result
                 fn add_alt_titles(&mut self) -> &mut Self {
-                    self.url.push_str("alternative_titles,");
+                    self.$fields.push_str("alternative_titles,");
                     self
                 }
                 /// Adds the option of a Start Date to be given to the final result
                 fn add_start_date(&mut self) -> &mut Self {
-                    self.url.push_str("start_date,");
+                    self.$fields.push_str("start_date,");
                     self
                 }
                 /// Adds the option of an End Date to be given to the final result
                 fn add_end_date(&mut self) -> &mut Self {
-                    self.url.push_str("end_date,");
+                    self.$fields.push_str("end_date,");
                     self
                 }
                 /// Adds the option of a Synopsis to be given to the final result
                 fn add_synopsis(&mut self) -> &mut Self {
-                    self.url.push_str("synopsis,");
+                    self.$fields.push_str("synopsis,");
                     self
                 }
                 /// Adds the option of a Mean to be given to the final result
                 fn add_mean(&mut self) -> &mut Self {
-                    self.url.push_str("mean,");
+                    self.$fields.push_str("mean,");
                     self
                 }
                 /// Adds the option of a Rank to be given to the final result
                 fn add_rank(&mut self) -> &mut Self {
-                    self.url.push_str("rank,");
+                    self.$fields.push_str("rank,");
                     self
                 }
                 /// Adds the option of Popularity to be given to the final result
                 fn add_popularity(&mut self) -> &mut Self {
-                    self.url.push_str("popularity,");
+                    self.$fields.push_str("popularity,");
                     self
                 }
                 /// Adds the option of Num. List Users to be given to the final result
                 fn add_num_list_users(&mut self) -> &mut Self {
-                    self.url.push_str("num_list_users,");
+                    self.$fields.push_str("num_list_users,");
                     self
                 }
                 /// Adds the option of Num. Scoring Users to be given to the final result
                 fn add_num_scoring_users(&mut self) -> &mut Self {
-                    self.url.push_str("num_scoring_users,");
+                    self.$fields.push_str("num_scoring_users,");
                     self
                 }
                 /// Adds the option of NSFW rating to be given to the final result
                 fn add_nsfw(&mut self) -> &mut Self {
-                    self.url.push_str("nsfw,");
+                    self.$fields.push_str("nsfw,");
                     self
                 }
                 /// Adds the option of a Created Date to be given to the final result
                 fn add_created_at(&mut self) -> &mut Self {
-                    self.url.push_str("created_at,");
+                    self.$fields.push_str("created_at,");
                     self
                 }
                 /// Adds the option of an Updated Date to be given to the final result
                 fn add_updated_at(&mut self) -> &mut Self {
-                    self.url.push_str("updated_at,");
+                    self.$fields.push_str("updated_at,");
                     self
                 }
                 /// Adds the option of a Media Type to be given to the final result
                 fn add_media_type(&mut self) -> &mut Self {
-                    self.url.push_str("media_type,");
+                    self.$fields.push_str("media_type,");
                     self
                 }
                 /// Adds the option of the Airing Status to be given to the final result
                 fn add_status(&mut self) -> &mut Self {
-                    self.url.push_str("status,");
+                    self.$fields.push_str("status,");
                     self
                 }
                 /// Adds the option of Genres to be given to the final result
                 fn add_genres(&mut self) -> &mut Self {
-                    self.url.push_str("genres,");
+                    self.$fields.push_str("genres,");
                     self
                 }
                 /// Adds the option of your List Status to be given to the final result
                 fn add_my_list_status(&mut self) -> &mut Self {
-                    self.url.push_str("my_list_status,");
+                    self.$fields.push_str("my_list_status,");
                     self
                 }
                 /// Adds the option of Start Season data to be given to the final result
                 fn add_start_season(&mut self) -> &mut Self {
-                    self.url.push_str("start_season,");
+                    self.$fields.push_str("start_season,");
                     self
                 }
                 /// Adds the option of an Episode Count to be given to the final result
                 fn add_num_episodes(&mut self) -> &mut Self {
-                    self.url.push_str("num_episodes,");
+                    self.$fields.push_str("num_episodes,");
                     self
                 }
                 /// Adds the option of Broadcast data to be given to the final result
                 fn add_broadcast(&mut self) -> &mut Self {
-                    self.url.push_str("broadcast,");
+                    self.$fields.push_str("broadcast,");
                     self
                 }
                 /// Adds the option of Source data to be given to the final result
                 fn add_source(&mut self) -> &mut Self {
-                    self.url.push_str("source,");
+                    self.$fields.push_str("source,");
                     self
                 }
                 /// Adds the option of an Average Episode Duration to be given to the final result
                 fn add_average_episode_duration(&mut self) -> &mut Self {
-                    self.url.push_str("average_episode_duration,");
+                    self.$fields.push_str("average_episode_duration,");
                     self
                 }
                 /// Adds the option of a Rating type to be given to the final result
                 fn add_rating(&mut self) -> &mut Self {
-                    self.url.push_str("rating,");
+                    self.$fields.push_str("rating,");
                     self
                 }
                 /// Adds the option of MyAnimeList's Pictures to be given to the final result
                 fn add_pictures(&mut self) -> &mut Self {
-                    self.url.push_str("pictures,");
+                    self.$fields.push_str("pictures,");
                     self
                 }
                 /// Adds the option of the show's Background to be given to the final result
                 fn add_background(&mut self) -> &mut Self {
-                    self.url.push_str("background,");
+                    self.$fields.push_str("background,");
                     self
                 }
                 /// Adds the option of Related Anime to be given to the final result
                 fn add_related_anime(&mut self) -> &mut Self {
-                    self.url.push_str("related_anime,");
+                    self.$fields.push_str("related_anime,");
                     self
                 }
                 /// Adds the option of Related Manga to be given to the final result
                 fn add_related_manga(&mut self) -> &mut Self {
-                    self.url.push_str("related_manga,");
+                    self.$fields.push_str("related_manga,");
                     self
                 }
                 /// Adds the option of Recommendations to be given to the final result
                 fn add_recommendations(&mut self) -> &mut Self {
-                    self.url.push_str("recommendations,");
+                    self.$fields.push_str("recommendations,");
                     self
                 }
                 /// Adds the option of Studio data to be given to the final result
                 fn add_studios(&mut self) -> &mut Self {
-                    self.url.push_str("studios,");
+                    self.$fields.push_str("studios,");
                     self
                 }
                 /// Adds the option of MyAnimeList Statistics to be given to the final result
                 fn add_statistics(&mut self) -> &mut Self {
-                    self.url.push_str("statistics,");
+                    self.$fields.push_str("statistics,");
                     self
                 }
             }
@@ -414,4 +571,5 @@ macro_rules! impl_filters_for_builders {
     };
 }
 
-impl_filters_for_builders!(Builder, SearchBuilder, SeasonalBuilder);
\ No newline at end of file
+impl_filters_for_builders!(url: Builder, SearchBuilder, SeasonalBuilder);
+impl_filters_for_builders!(fields: UserListBuilder);
\ No newline at end of file
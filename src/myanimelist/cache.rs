@@ -0,0 +1,323 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// The kinds of MyAnimeList requests, which each keep their responses for their own time in a `ResponseCache`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    /// One anime's details, such as from `get_anime`
+    Anime,
+    /// One manga's details, such as from `get_manga`
+    Manga,
+    /// Searches by name, such as from `search_anime`
+    Search,
+    /// Seasonal anime, such as from `get_season`
+    Season,
+    /// Rankings, such as from `get_anime_rankings`
+    Ranking,
+    /// A user's anime or manga list, such as from `get_user_animelist`
+    UserList,
+    /// A user's profile, such as from `get_my_user`
+    User,
+    /// Anything else
+    Other,
+}
+
+impl Endpoint {
+    /// Finds the kind of a MyAnimeList API URL, from its path
+    pub fn from_url(url: &str) -> Endpoint {
+        let Ok(parsed) = Url::parse(url) else { return Endpoint::Other };
+        let segments: Vec<&str> = parsed.path_segments().map(|s| s.filter(|s| !s.is_empty()).collect()).unwrap_or_default();
+        // Anything before the resource, such as `/v2`, is skipped
+        let start = segments.iter().position(|s| matches!(*s, "anime" | "manga" | "users"));
+        match start.map(|i| &segments[i..]).unwrap_or_default() {
+            ["anime" | "manga"] => Endpoint::Search,
+            ["anime", "season", ..] => Endpoint::Season,
+            ["anime" | "manga", "ranking"] => Endpoint::Ranking,
+            ["anime", id] if id.parse::<u32>().is_ok() => Endpoint::Anime,
+            ["manga", id] if id.parse::<u32>().is_ok() => Endpoint::Manga,
+            ["users", _, "animelist" | "mangalist"] => Endpoint::UserList,
+            ["users", _] => Endpoint::User,
+            _ => Endpoint::Other,
+        }
+    }
+}
+
+/// How a `ResponseCache` has answered requests
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CacheStats {
+    /// Requests answered from the cache, without calling MyAnimeList
+    pub hits: u64,
+    /// Requests sent to MyAnimeList, because the cache didn't have them, or had them without an `ETag`
+    pub misses: u64,
+    /// Requests for expired responses that MyAnimeList confirmed were unchanged, from their `ETag`
+    pub revalidated: u64,
+}
+
+/// Keeps MyAnimeList's responses, so requests for the same URL don't call MyAnimeList again until they expire.<br>
+/// Responses are kept in memory, up to a number of responses, dropping the least recently used first,
+/// and can also be saved to a directory, to be kept between runs.<br>
+/// An expired response that came with an `ETag` is revalidated with `If-None-Match`,
+/// and is used again if MyAnimeList answers that it is unchanged.<br>
+/// Responses are kept apart for each logged in user. Updates and deletes made through `UpdateAnime`, `UpdateManga`
+/// and `delete_anime` remove the responses they change.<br>
+/// Clones share the same responses and stats, so a clone given to `MalClient::cache` can be kept to read the stats.
+/// ### Example usage:
+/// ```
+/// use std::time::Duration;
+/// use mal_query::myanimelist::{self, cache::{Endpoint, ResponseCache}, client::MalClient, retrieval::get_anime};
+/// async fn cache_example() {
+///     let cache = ResponseCache::new(500)
+///         .ttl(Endpoint::Season, Duration::from_secs(60 * 60 * 24))
+///         .disk("mal_cache")
+///         .clone();
+///     myanimelist::set_client(MalClient::new().cache(&cache));
+///     let first = get_anime(21).await.unwrap();
+///     let second = get_anime(21).await.unwrap();
+///     assert_eq!(cache.stats().hits, 1);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    capacity: usize,
+    ttls: HashMap<Endpoint, Duration>,
+    dir: Option<PathBuf>,
+    state: Arc<Mutex<CacheState>>,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<String, CachedResponse>,
+    // Counts up on every use, so the entry with the lowest `used` is the least recently used
+    clock: u64,
+    stats: CacheStats,
+}
+
+/// A response kept by a `ResponseCache`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedResponse {
+    pub(crate) url: String,
+    pub(crate) body: String,
+    pub(crate) etag: Option<String>,
+    // Milliseconds since the Unix epoch, so saved responses expire between runs
+    expires: u64,
+    #[serde(skip)]
+    used: u64,
+}
+
+impl CachedResponse {
+    pub(crate) fn new(url: &str, body: String, etag: Option<String>) -> Self {
+        CachedResponse { url: url.to_string(), body, etag, expires: 0, used: 0 }
+    }
+    pub(crate) fn is_fresh(&self) -> bool {
+        self.expires > now_millis()
+    }
+}
+
+/// What a `ResponseCache` has for a request
+pub(crate) enum Lookup {
+    /// A response that hasn't expired
+    Fresh(CachedResponse),
+    /// An expired response with an `ETag`, to revalidate
+    Stale(CachedResponse),
+    Missing,
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        ResponseCache::new(1000)
+    }
+}
+
+impl ResponseCache {
+    /// Creates a cache keeping up to `capacity` responses in memory, and nothing on disk.<br>
+    /// Responses are kept for 24 hours for `Anime` and `Manga`, 1 hour for `Search`, `Season` and `Ranking`,
+    /// and 5 minutes for `UserList`, `User` and `Other`.
+    /// Any response that asked for the user's `my_list_status` is kept for the `UserList` time, as it changes with the list.
+    pub fn new(capacity: usize) -> Self {
+        let hour = Duration::from_secs(60 * 60);
+        let five_minutes = Duration::from_secs(5 * 60);
+        ResponseCache {
+            capacity,
+            ttls: HashMap::from([
+                (Endpoint::Anime, hour * 24),
+                (Endpoint::Manga, hour * 24),
+                (Endpoint::Search, hour),
+                (Endpoint::Season, hour),
+                (Endpoint::Ranking, hour),
+                (Endpoint::UserList, five_minutes),
+                (Endpoint::User, five_minutes),
+                (Endpoint::Other, five_minutes),
+            ]),
+            dir: None,
+            state: Arc::new(Mutex::new(CacheState::default())),
+        }
+    }
+    /// Changes how long responses of an endpoint are kept. A zero duration has them revalidated every time,
+    /// or not reused when they have no `ETag`
+    pub fn ttl(&mut self, endpoint: Endpoint, ttl: Duration) -> &mut Self {
+        self.ttls.insert(endpoint, ttl);
+        self
+    }
+    /// Also saves responses as files in a directory, which is created if needed. The directory isn't limited by
+    /// the capacity, expired files are replaced when their URL is requested again.<br>
+    /// Files that can't be read or written are skipped, without failing the request.
+    pub fn disk(&mut self, dir: impl Into<PathBuf>) -> &mut Self {
+        self.dir = Some(dir.into());
+        self
+    }
+    /// Returns how the cache has answered requests so far
+    pub fn stats(&self) -> CacheStats {
+        self.state.lock().unwrap().stats
+    }
+    /// Removes every response, in memory and on disk. The stats are kept
+    pub fn clear(&self) {
+        self.state.lock().unwrap().entries.clear();
+        self.remove_files(|_| true);
+    }
+    /// Removes the responses an update to a user's anime entry changes: the anime's details, users' lists,
+    /// and any anime response that asked for `my_list_status`, such as a search or a season
+    pub fn invalidate_anime(&self, id: u32) {
+        self.invalidate(|url| {
+            let endpoint = Endpoint::from_url(url);
+            matches!(endpoint, Endpoint::UserList | Endpoint::User)
+                || (endpoint == Endpoint::Anime && path_id(url) == Some(id))
+                || asks_for_list_status(url, "anime")
+        });
+    }
+    /// Removes the responses an update to a user's manga entry changes: the manga's details, users' lists,
+    /// and any manga response that asked for `my_list_status`
+    pub fn invalidate_manga(&self, id: u32) {
+        self.invalidate(|url| {
+            let endpoint = Endpoint::from_url(url);
+            matches!(endpoint, Endpoint::UserList | Endpoint::User)
+                || (endpoint == Endpoint::Manga && path_id(url) == Some(id))
+                || asks_for_list_status(url, "manga")
+        });
+    }
+
+    fn invalidate(&self, matches: impl Fn(&str) -> bool) {
+        self.state.lock().unwrap().entries.retain(|_, entry| !matches(&entry.url));
+        self.remove_files(|entry| matches(&entry.url));
+    }
+
+    // Finds the response for a key, from memory, then from disk
+    pub(crate) fn lookup(&self, key: &str) -> Lookup {
+        let mut state = self.state.lock().unwrap();
+        state.clock += 1;
+        let clock = state.clock;
+        let found = match state.entries.get_mut(key) {
+            Some(entry) => {
+                entry.used = clock;
+                Some(entry.clone())
+            },
+            None => self.read_file(key),
+        };
+        match found {
+            Some(entry) if entry.is_fresh() => {
+                state.stats.hits += 1;
+                if !state.entries.contains_key(key) { self.insert(&mut state, key, entry.clone()) }
+                Lookup::Fresh(entry)
+            },
+            Some(entry) if entry.etag.is_some() => Lookup::Stale(entry),
+            _ => {
+                state.stats.misses += 1;
+                Lookup::Missing
+            },
+        }
+    }
+    // MyAnimeList confirmed a stale response is unchanged, so it is kept for another TTL
+    pub(crate) fn revalidated(&self, key: &str, mut entry: CachedResponse) -> CachedResponse {
+        let mut state = self.state.lock().unwrap();
+        state.stats.revalidated += 1;
+        entry.expires = now_millis() + self.ttl_of(&entry.url);
+        self.write_file(key, &entry);
+        self.insert(&mut state, key, entry.clone());
+        entry
+    }
+    // A stale response MyAnimeList didn't confirm, which is sent again in full
+    pub(crate) fn missed(&self) {
+        self.state.lock().unwrap().stats.misses += 1;
+    }
+    pub(crate) fn store(&self, key: &str, url: &str, body: String, etag: Option<String>) {
+        let entry = CachedResponse { expires: now_millis() + self.ttl_of(url), ..CachedResponse::new(url, body, etag) };
+        let mut state = self.state.lock().unwrap();
+        self.write_file(key, &entry);
+        self.insert(&mut state, key, entry);
+    }
+
+    fn insert(&self, state: &mut CacheState, key: &str, mut entry: CachedResponse) {
+        state.clock += 1;
+        entry.used = state.clock;
+        state.entries.insert(key.to_string(), entry);
+        while state.entries.len() > self.capacity {
+            let oldest = state.entries.iter().min_by_key(|(_, e)| e.used).map(|(k, _)| k.clone());
+            match oldest {
+                Some(oldest) => state.entries.remove(&oldest),
+                None => break,
+            };
+        }
+    }
+    fn ttl_of(&self, url: &str) -> u64 {
+        let endpoint = match asks_for_list_status(url, "anime") || asks_for_list_status(url, "manga") {
+            true => Endpoint::UserList,
+            false => Endpoint::from_url(url),
+        };
+        self.ttls.get(&endpoint).map(|ttl| ttl.as_millis() as u64).unwrap_or_default()
+    }
+    fn file(&self, key: &str) -> Option<PathBuf> {
+        Some(self.dir.as_ref()?.join(format!("{:016x}.json", fnv1a(key))))
+    }
+    fn read_file(&self, key: &str) -> Option<CachedResponse> {
+        let text = fs::read_to_string(self.file(key)?).ok()?;
+        serde_json::from_str(&text).ok()
+    }
+    fn write_file(&self, key: &str, entry: &CachedResponse) {
+        let (Some(dir), Some(file)) = (&self.dir, self.file(key)) else { return };
+        let _ = fs::create_dir_all(dir);
+        if let Ok(text) = serde_json::to_string(entry) { let _ = fs::write(file, text); }
+    }
+    fn remove_files(&self, matches: impl Fn(&CachedResponse) -> bool) {
+        let Some(files) = self.dir.as_ref().and_then(|dir| fs::read_dir(dir).ok()) else { return };
+        for file in files.flatten() {
+            let path = file.path();
+            if path.extension().is_none_or(|ext| ext != "json") { continue }
+            let entry: Option<CachedResponse> = fs::read_to_string(&path).ok().and_then(|t| serde_json::from_str(&t).ok());
+            if entry.is_some_and(|e| matches(&e)) { let _ = fs::remove_file(path); }
+        }
+    }
+}
+
+// The id after `anime/` or `manga/` in a URL
+fn path_id(url: &str) -> Option<u32> {
+    let parsed = Url::parse(url).ok()?;
+    let mut segments = parsed.path_segments()?.skip_while(|s| !matches!(*s, "anime" | "manga"));
+    segments.nth(1)?.parse().ok()
+}
+
+// Whether a URL for an anime or manga resource asks for the user's `my_list_status`, which an update changes
+fn asks_for_list_status(url: &str, resource: &str) -> bool {
+    let Ok(parsed) = Url::parse(url) else { return false };
+    parsed.path_segments().is_some_and(|mut segments| segments.any(|s| s == resource))
+        && parsed.query_pairs().any(|(key, value)| key == "fields" && value.contains("my_list_status"))
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or_default()
+}
+
+// Responses are kept apart for each user, by a hash of how the request is authorized, so tokens aren't saved
+pub(crate) fn cache_key(url: &str, authorization: &str) -> String {
+    format!("{:016x} {url}", fnv1a(authorization))
+}
+
+// FNV-1a, for names that are saved to disk, and so must hash the same with every Rust release, unlike `DefaultHasher`
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
@@ -0,0 +1,135 @@
+use std::error::Error;
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use super::{builders::{AddFields, UserListBuilder}, models::*};
+
+// Used when MyAnimeList doesn't have an episode length for the anime
+const DEFAULT_EPISODE_MINUTES: u32 = 24;
+
+/// Reads a user's currently watching list, and creates an iCalendar (RFC 5545) feed of the anime that are airing,
+/// which can be imported or subscribed to in calendar apps.<br>
+/// The user searched must not be private, unless it is the logged in user (`"@me"`).<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing the `.ics` text
+/// ### Example usage:
+/// ```
+/// use mal_query::myanimelist::calendar::watching_calendar;
+/// async fn calendar_example() {
+///     let ics = watching_calendar("naginis_api").await.unwrap();
+///     std::fs::write("airing.ics", ics).unwrap();
+/// }
+/// ```
+pub async fn watching_calendar(username: &str) -> Result<String, Box<dyn Error>> {
+    let list = UserListBuilder::new(username)
+        .status(Status::Watching)
+        .add_status()
+        .add_broadcast()
+        .add_num_episodes()
+        .add_start_date()
+        .add_end_date()
+        .add_average_episode_duration()
+        .run_all()
+        .await?;
+    Ok(to_icalendar(&list.data, Utc::now()))
+}
+
+/// Creates iCalendar (RFC 5545) text, with one weekly event for each anime that is currently airing and has
+/// `broadcast` data. Other anime are left out.<br>
+/// Each event starts at the first broadcast on or after the anime's `start_date`, and repeats until its `end_date`,
+/// or for `num_episodes` weeks when there is no end date yet.<br>
+/// `dtstamp` is when the calendar was created, which every event is stamped with.
+pub fn to_icalendar(anime: &[MalAnimeData], dtstamp: DateTime<Utc>) -> String {
+    let mut lines: Vec<String> = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//mal-query//Airing Schedule//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        "X-WR-CALNAME:Airing Schedule".to_string(),
+    ];
+    for show in anime {
+        if let Some(event) = event_lines(show, dtstamp) {
+            lines.extend(event);
+        }
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold(line) + "\r\n").collect()
+}
+
+// The VEVENT of one anime, if it is airing and its broadcast slot is known
+fn event_lines(anime: &MalAnimeData, dtstamp: DateTime<Utc>) -> Option<Vec<String>> {
+    if anime.status != Some(AiringStatus::CurrentlyAiring) { return None }
+    let broadcast = anime.broadcast.as_ref()?;
+
+    // The first broadcast on or after the start date, or the next one from now if the start date isn't a full date
+    let first = match anime.start_date.as_deref().and_then(parse_date) {
+        Some(start) => broadcast.next_after(jst_start_of_day(start)? - Duration::seconds(1))?,
+        None => broadcast.next_after(dtstamp)?,
+    };
+
+    let mut rule = "RRULE:FREQ=WEEKLY".to_string();
+    match (anime.end_date.as_deref().and_then(parse_date), anime.num_episodes) {
+        (Some(end), _) => {
+            let until = jst_start_of_day(end)? + Duration::days(1) - Duration::seconds(1);
+            rule.push_str(&format!(";UNTIL={}", format_utc(until)));
+        },
+        (None, Some(episodes)) if episodes > 0 => rule.push_str(&format!(";COUNT={episodes}")),
+        _ => {},
+    }
+
+    let minutes = match anime.average_episode_duration {
+        Some(seconds) if seconds >= 60 => (seconds + 30) / 60,
+        _ => DEFAULT_EPISODE_MINUTES,
+    };
+
+    Some(vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:mal-anime-{}@mal-query", anime.id),
+        format!("DTSTAMP:{}", format_utc(dtstamp)),
+        format!("DTSTART:{}", format_utc(first)),
+        format!("DURATION:PT{minutes}M"),
+        rule,
+        format!("SUMMARY:{}", escape_text(&anime.title)),
+        format!("URL:https://myanimelist.net/anime/{}", anime.id),
+        "END:VEVENT".to_string(),
+    ])
+}
+
+// MyAnimeList dates can be only a year or a year and month, which can't be scheduled
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+fn jst_start_of_day(date: NaiveDate) -> Option<DateTime<Utc>> {
+    let start = jst().from_local_datetime(&date.and_time(NaiveTime::MIN)).single()?;
+    Some(start.with_timezone(&Utc))
+}
+
+fn format_utc(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+// Escapes a TEXT value, RFC 5545 section 3.3.11
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+// Lines longer than 75 octets are folded onto lines starting with a space, RFC 5545 section 3.1.
+// Lines are only split between characters, so multi-byte titles stay valid UTF-8
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded
+}
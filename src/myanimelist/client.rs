@@ -0,0 +1,320 @@
+use std::{
+    collections::hash_map::RandomState,
+    error::Error,
+    hash::{BuildHasher, Hasher},
+    sync::Arc,
+    time::Duration,
+};
+use chrono::{DateTime, Utc};
+use reqwest::{header::{ETAG, IF_NONE_MATCH, RETRY_AFTER}, Method, RequestBuilder, Response, StatusCode};
+use crate::{config::ClientConfig, rate_limit::RateLimiter};
+use super::{cache::{cache_key, CachedResponse, Lookup, ResponseCache}, transport::Transport, CLIENT_ID, TOKEN};
+
+/// The client every MyAnimeList request is sent through, reads and updates alike.<br>
+/// The default client sends requests to `https://api.myanimelist.net/v2`, retries requests MyAnimeList rejects under load
+/// with `RetryPolicy::new()`, and isn't rate limited. Another, such as one with a `ResponseCache`, can be used for every `myanimelist`
+/// function with `myanimelist::set_client`.
+/// ### Example usage:
+/// ```
+/// use std::time::Duration;
+/// use mal_query::myanimelist::{self, cache::ResponseCache, client::{MalClient, RetryPolicy}};
+/// fn client_example() {
+///     myanimelist::set_client(MalClient::new()
+///         .cache(&ResponseCache::new(500))
+///         .retry(RetryPolicy::new().max_attempts(5))
+///         .rate_limit(&[(1, Duration::from_secs(1))])
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MalClient {
+    // Builds the requests, and sends them when no other transport is given
+    http: reqwest::Client,
+    transport: Option<Arc<dyn Transport>>,
+    api_url: String,
+    oauth_url: String,
+    cache: Option<ResponseCache>,
+    retry: RetryPolicy,
+    limiter: Arc<RateLimiter>,
+}
+
+impl Default for MalClient {
+    fn default() -> Self {
+        MalClient::new()
+    }
+}
+
+impl MalClient {
+    /// Creates a client without a cache or rate limits, which retries with `RetryPolicy::new()`
+    pub fn new() -> Self {
+        MalClient {
+            http: reqwest::Client::new(),
+            transport: None,
+            api_url: "https://api.myanimelist.net/v2".to_string(),
+            oauth_url: "https://myanimelist.net/v1/oauth2".to_string(),
+            cache: None,
+            retry: RetryPolicy::new(),
+            limiter: Arc::new(RateLimiter::new(&[])),
+        }
+    }
+    /// Changes the URL MyAnimeList API requests are sent to, such as a caching proxy or a stub server.
+    /// Defaults to `https://api.myanimelist.net/v2`
+    pub fn api_url(&mut self, api_url: &str) -> &mut Self {
+        self.api_url = api_url.trim_end_matches('/').to_string();
+        self
+    }
+    /// Changes the URL `login()` authorizes the user and requests their token from. Defaults to `https://myanimelist.net/v1/oauth2`
+    pub fn oauth_url(&mut self, oauth_url: &str) -> &mut Self {
+        self.oauth_url = oauth_url.trim_end_matches('/').to_string();
+        self
+    }
+    /// Returns the URL MyAnimeList API requests are sent to
+    pub fn get_api_url(&self) -> &str {
+        &self.api_url
+    }
+    /// Returns the URL `login()` uses for OAuth
+    pub fn get_oauth_url(&self) -> &str {
+        &self.oauth_url
+    }
+    /// Replaces what requests are sent with, such as a `MockTransport` for tests. Clones of the transport are used
+    pub fn transport<T: Transport + Clone + 'static>(&mut self, transport: &T) -> &mut Self {
+        self.transport = Some(Arc::new(transport.clone()));
+        self
+    }
+    /// Sends requests with a client built from the config, with its proxy, timeouts, user agent and certificates.
+    /// `login()` uses it too
+    pub fn config(&mut self, config: &ClientConfig) -> Result<&mut Self, Box<dyn Error>> {
+        self.http = config.build()?;
+        Ok(self)
+    }
+    /// Keeps responses in a cache. Clones of the cache share its responses, so one can be kept to read its stats
+    pub fn cache(&mut self, cache: &ResponseCache) -> &mut Self {
+        self.cache = Some(cache.clone());
+        self
+    }
+    /// Returns the client's cache, if it has one
+    pub fn get_cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_ref()
+    }
+    /// Replaces how failed requests are retried
+    pub fn retry(&mut self, policy: &RetryPolicy) -> &mut Self {
+        self.retry = policy.clone();
+        self
+    }
+    /// Returns how the client retries failed requests
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry
+    }
+    /// Limits requests, as a list of `(requests, window)`, such as `&[(1, Duration::from_secs(1))]`.
+    /// Every retry waits for the limits too. An empty list removes the limits
+    pub fn rate_limit(&mut self, limits: &[(u32, Duration)]) -> &mut Self {
+        self.limiter = Arc::new(RateLimiter::new(limits));
+        self
+    }
+    /// Gets a MyAnimeList API URL, as the logged in user when there is one.<br>
+    /// With a cache, the response is taken from it when it can be, and kept in it when successful.<br>
+    /// Async function, function must be called with `.await`<br>
+    /// Returns a `Result<>`, with a success containing the response, which might not be successful
+    pub async fn get(&self, url: &str) -> Result<Response, Box<dyn Error>> {
+        let (header_key, header_value) = auth_header();
+        let Some(cache) = &self.cache else {
+            return self.send(|http| http.get(url).header(header_key, &header_value)).await
+        };
+
+        let key = cache_key(url, &header_value);
+        let stale = match cache.lookup(&key) {
+            Lookup::Fresh(entry) => return cached_response(&entry),
+            Lookup::Stale(entry) => Some(entry),
+            Lookup::Missing => None,
+        };
+        let etag = stale.as_ref().and_then(|e| e.etag.clone());
+        let res = self.send(|http| {
+            let request = http.get(url).header(header_key, &header_value);
+            match &etag {
+                Some(etag) => request.header(IF_NONE_MATCH, etag),
+                None => request,
+            }
+        }).await?;
+
+        if let (Some(entry), StatusCode::NOT_MODIFIED) = (stale.clone(), res.status()) {
+            return cached_response(&cache.revalidated(&key, entry));
+        }
+        if stale.is_some() { cache.missed() }
+        if res.status() != StatusCode::OK { return Ok(res) }
+
+        let etag = res.headers().get(ETAG).and_then(|v| v.to_str().ok()).map(String::from);
+        let body = res.text().await?;
+        cache.store(&key, url, body.clone(), etag.clone());
+        cached_response(&CachedResponse::new(url, body, etag))
+    }
+
+    // As `get`, without the cache, for reads of the user's entry just before it is written
+    pub(crate) async fn get_uncached(&self, url: &str) -> Result<Response, Box<dyn Error>> {
+        let (header_key, header_value) = auth_header();
+        self.send(|http| http.get(url).header(header_key, &header_value)).await
+    }
+
+    // The HTTP client, for requests that aren't to the API, such as `login()`'s
+    pub(crate) fn http(&self) -> &reqwest::Client {
+        &self.http
+    }
+
+    // Sends a request built by `request`, waiting for the rate limits and retrying with the client's policy.
+    // The last response is returned when retries run out, so callers read its status as before
+    pub(crate) async fn send(&self, request: impl Fn(&reqwest::Client) -> RequestBuilder) -> Result<Response, Box<dyn Error>> {
+        self.send_with(&self.retry, request).await.map_err(|e| e as Box<dyn Error>)
+    }
+
+    // As `send`, with another policy, and errors that can be sent between threads, for updates sent from spawned tasks
+    pub(crate) async fn send_with(
+        &self,
+        policy: &RetryPolicy,
+        request: impl Fn(&reqwest::Client) -> RequestBuilder,
+    ) -> Result<Response, Box<dyn Error + Send + Sync>> {
+        let mut attempt = 1;
+        loop {
+            self.limiter.acquire().await;
+            let request = request(&self.http).build()?;
+            let write = request.method() != Method::GET;
+            let sent = match &self.transport {
+                Some(transport) => transport.execute(request).await,
+                None => Transport::execute(&self.http, request).await,
+            };
+            let retry_after = match sent {
+                Ok(res) if !policy.retries_status(res.status(), write) => return Ok(res),
+                Ok(res) if attempt >= policy.max_attempts => return Ok(res),
+                Ok(res) => retry_after(&res),
+                Err(e) if !is_connection_error(&*e) || attempt >= policy.max_attempts => return Err(e),
+                Err(_) => None,
+            };
+            let delay = match retry_after {
+                Some(retry_after) => retry_after.min(policy.max_backoff),
+                None => policy.delay(attempt),
+            };
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// How a `MalClient` retries requests that fail from MyAnimeList's load, or from the connection.<br>
+/// The wait before each retry doubles from the first backoff, up to the longest backoff, and is shortened by up to
+/// half at random with jitter, so clients that failed together don't retry together.
+/// A `Retry-After` header from MyAnimeList is waited for instead, when it sends one, up to the longest backoff.
+/// ### Example usage:
+/// ```
+/// use std::time::Duration;
+/// use mal_query::myanimelist::client::RetryPolicy;
+/// let policy = RetryPolicy::new()
+///     .max_attempts(5)
+///     .backoff(Duration::from_secs(1), Duration::from_secs(60))
+///     .retry_on(&[429, 503])
+///     .clone();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retry_on: Vec<u16>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new()
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy of 3 attempts, waiting 500ms then 1s with jitter, retrying on 429, 500, 502, 503 and 504
+    /// responses and connection errors
+    pub fn new() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            retry_on: vec![429, 500, 502, 503, 504],
+        }
+    }
+    /// Creates a policy that never retries
+    pub fn none() -> Self {
+        RetryPolicy { max_attempts: 1, ..RetryPolicy::new() }
+    }
+    /// Sets how many times a request is sent at most, counting the first. Defaults to 3
+    pub fn max_attempts(&mut self, max_attempts: u32) -> &mut Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+    /// Sets the wait before the first retry, and the longest wait it can double to, which also caps `Retry-After`.
+    /// Defaults to 500ms and 30s
+    pub fn backoff(&mut self, backoff: Duration, max_backoff: Duration) -> &mut Self {
+        self.backoff = backoff;
+        self.max_backoff = max_backoff.max(backoff);
+        self
+    }
+    /// Sets whether waits are shortened at random. Defaults to true
+    pub fn jitter(&mut self, jitter: bool) -> &mut Self {
+        self.jitter = jitter;
+        self
+    }
+    /// Replaces the response statuses that are retried.<br>
+    /// MyAnimeList sometimes answers 403 when it is under load, which can be added, but is only retried for reads:
+    /// for an update or delete, it is an expired or revoked token
+    pub fn retry_on(&mut self, statuses: &[u16]) -> &mut Self {
+        self.retry_on = statuses.to_vec();
+        self
+    }
+    /// Returns how many times a request is sent at most
+    pub fn get_max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+    /// Returns the wait after a request's `attempt`th failure, starting at 1, when there is no `Retry-After`
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let doubled = self.backoff.saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+        let delay = doubled.min(self.max_backoff);
+        match self.jitter {
+            true => delay / 2 + delay.mul_f64(random_fraction() / 2.0),
+            false => delay,
+        }
+    }
+    fn retries_status(&self, status: StatusCode, write: bool) -> bool {
+        self.retry_on.contains(&status.as_u16()) && !(write && status == StatusCode::FORBIDDEN)
+    }
+}
+
+// The header a request is authorized with: the user's token when logged in, or the client ID
+fn auth_header() -> (&'static str, String) {
+    let token = TOKEN.lock().unwrap().clone();
+    match token.is_empty() {
+        true => ("X-MAL-CLIENT-ID", CLIENT_ID.clone()),
+        false => ("Authorization", format!("Bearer {token}")),
+    }
+}
+
+pub(crate) fn is_connection_error(error: &(dyn Error + Send + Sync + 'static)) -> bool {
+    error.downcast_ref::<reqwest::Error>().is_some_and(|e| e.is_connect() || e.is_timeout())
+}
+
+// Retry-After is either seconds, or an HTTP date
+fn retry_after(res: &Response) -> Option<Duration> {
+    let value = res.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() { return Some(Duration::from_secs(seconds)) }
+    let date = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    Some((date - Utc::now()).to_std().unwrap_or_default())
+}
+
+// A number from 0 to 1, from the random keys std hashes with, so no random crate is needed for jitter
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+// Rebuilds a response from the cache, for the functions that read responses
+fn cached_response(entry: &CachedResponse) -> Result<Response, Box<dyn Error>> {
+    let mut builder = http::Response::builder().status(StatusCode::OK);
+    if let Some(etag) = &entry.etag { builder = builder.header(ETAG, etag) }
+    Ok(Response::from(builder.body(entry.body.clone())?))
+}
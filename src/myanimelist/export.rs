@@ -0,0 +1,181 @@
+use std::{error::Error, io::Write};
+use serde::Serialize;
+use serde_json::Value;
+use super::models::*;
+
+/// The columns `MalAnimeSearch::write_csv` uses when none are given
+pub const ANIME_CSV_COLUMNS: &[&str] = &[
+    "id", "title", "alternative_titles.en", "alternative_titles.ja", "media_type", "status", "num_episodes",
+    "start_date", "end_date", "mean", "rank", "popularity", "genres", "studios",
+    "list_status.status", "list_status.score", "list_status.num_episodes_watched", "list_status.updated_at",
+];
+
+/// The columns `MalMangaSearch::write_csv` uses when none are given
+pub const MANGA_CSV_COLUMNS: &[&str] = &[
+    "id", "title", "alternative_titles.en", "alternative_titles.ja", "media_type", "status", "num_volumes",
+    "num_chapters", "start_date", "end_date", "mean", "rank", "popularity", "genres",
+    "list_status.status", "list_status.score", "list_status.num_volumes_read", "list_status.num_chapters_read",
+    "list_status.updated_at",
+];
+
+/// Writes anime or manga as CSV rows, one at a time, so lists can be written as they are received.<br>
+/// Columns are named by the fields of `MalAnimeData` or `MalMangaData`, with a `.` for the fields of nested values,
+/// such as `alternative_titles.en`, `main_picture.large` or `list_status.score`. Lists of names, like `genres`,
+/// `studios`, `alternative_titles.synonyms` and `list_status.tags`, are joined with `; `.
+/// Fields the entry doesn't have are left empty.
+/// ### Example usage:
+/// ```
+/// use mal_query::myanimelist::{builders::UserListBuilder, export::CsvWriter};
+/// async fn csv_writer_example() {
+///     let file = std::fs::File::create("animelist.csv").unwrap();
+///     let mut csv = CsvWriter::new(file, &["id", "title", "genres", "list_status.score"]);
+///     UserListBuilder::new("naginis_api")
+///         .include_list_status()
+///         .for_each_page(|page| csv.write_all(&page.data))
+///         .await
+///         .unwrap();
+/// }
+/// ```
+pub struct CsvWriter<W: Write> {
+    writer: W,
+    columns: Vec<String>,
+    wrote_header: bool,
+}
+
+impl<W: Write> CsvWriter<W> {
+    /// Takes where to write, and the columns of each row. The header row is written before the first row
+    pub fn new(writer: W, columns: &[&str]) -> Self {
+        CsvWriter {
+            writer,
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            wrote_header: false,
+        }
+    }
+    /// Writes one row
+    pub fn write<T: Serialize>(&mut self, entry: &T) -> Result<(), Box<dyn Error>> {
+        if !self.wrote_header { self.write_header()? }
+        let value = serde_json::to_value(entry)?;
+        let row: Vec<String> = self.columns.iter().map(|c| csv_field(&column_text(&value, c))).collect();
+        self.writer.write_all((row.join(",") + "\r\n").as_bytes())?;
+        Ok(())
+    }
+    /// Writes a row for each entry
+    pub fn write_all<T: Serialize>(&mut self, entries: &[T]) -> Result<(), Box<dyn Error>> {
+        entries.iter().try_for_each(|entry| self.write(entry))
+    }
+    /// Writes the header row if no rows were written, and returns the writer
+    pub fn finish(mut self) -> Result<W, Box<dyn Error>> {
+        if !self.wrote_header { self.write_header()? }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+    fn write_header(&mut self) -> Result<(), Box<dyn Error>> {
+        let header: Vec<String> = self.columns.iter().map(|c| csv_field(c)).collect();
+        self.writer.write_all((header.join(",") + "\r\n").as_bytes())?;
+        self.wrote_header = true;
+        Ok(())
+    }
+}
+
+/// Writes anime or manga as JSON Lines, one JSON object per line, with every field the entry has.<br>
+/// Like `CsvWriter`, entries are written one at a time.
+pub struct JsonLinesWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> JsonLinesWriter<W> {
+    pub fn new(writer: W) -> Self {
+        JsonLinesWriter { writer }
+    }
+    /// Writes one entry as a line
+    pub fn write<T: Serialize>(&mut self, entry: &T) -> Result<(), Box<dyn Error>> {
+        serde_json::to_writer(&mut self.writer, entry)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+    /// Writes a line for each entry
+    pub fn write_all<T: Serialize>(&mut self, entries: &[T]) -> Result<(), Box<dyn Error>> {
+        entries.iter().try_for_each(|entry| self.write(entry))
+    }
+    /// Returns the writer, after flushing it
+    pub fn finish(mut self) -> Result<W, Box<dyn Error>> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl MalAnimeSearch {
+    /// Writes every anime as CSV, with `ANIME_CSV_COLUMNS` when `columns` is `None`. See `CsvWriter` for the columns
+    pub fn write_csv<W: Write>(&self, writer: W, columns: Option<&[&str]>) -> Result<(), Box<dyn Error>> {
+        let mut csv = CsvWriter::new(writer, columns.unwrap_or(ANIME_CSV_COLUMNS));
+        csv.write_all(&self.data)?;
+        csv.finish()?;
+        Ok(())
+    }
+    /// Writes every anime as JSON Lines
+    pub fn write_json_lines<W: Write>(&self, writer: W) -> Result<(), Box<dyn Error>> {
+        let mut lines = JsonLinesWriter::new(writer);
+        lines.write_all(&self.data)?;
+        lines.finish()?;
+        Ok(())
+    }
+}
+
+impl MalMangaSearch {
+    /// Writes every manga as CSV, with `MANGA_CSV_COLUMNS` when `columns` is `None`. See `CsvWriter` for the columns
+    pub fn write_csv<W: Write>(&self, writer: W, columns: Option<&[&str]>) -> Result<(), Box<dyn Error>> {
+        let mut csv = CsvWriter::new(writer, columns.unwrap_or(MANGA_CSV_COLUMNS));
+        csv.write_all(&self.data)?;
+        csv.finish()?;
+        Ok(())
+    }
+    /// Writes every manga as JSON Lines
+    pub fn write_json_lines<W: Write>(&self, writer: W) -> Result<(), Box<dyn Error>> {
+        let mut lines = JsonLinesWriter::new(writer);
+        lines.write_all(&self.data)?;
+        lines.finish()?;
+        Ok(())
+    }
+}
+
+// The text of a column, following the `.`s into nested values
+fn column_text(value: &Value, column: &str) -> String {
+    let found = column.split('.').try_fold(value, |v, key| v.get(key));
+    match found {
+        Some(v) => value_text(v),
+        None => String::new(),
+    }
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => {
+            // Lists of names, like genres and studios, are joined. Anything else is kept as JSON
+            let names: Option<Vec<String>> = items
+                .iter()
+                .map(|item| match item {
+                    Value::String(s) => Some(s.clone()),
+                    Value::Object(o) => o.get("name")?.as_str().map(String::from),
+                    _ => None,
+                })
+                .collect();
+            names.map(|names| names.join("; ")).unwrap_or_else(|| value.to_string())
+        },
+        // The models' scores are f32, which serde_json widens, so 9.1 would be written as 9.100000381469727
+        Value::Number(n) => match n.as_f64() {
+            Some(f) if n.is_f64() && (f as f32) as f64 == f => (f as f32).to_string(),
+            _ => n.to_string(),
+        },
+        other => other.to_string(),
+    }
+}
+
+// Fields with commas, quotes or line breaks are quoted, with quotes doubled, RFC 4180
+fn csv_field(text: &str) -> String {
+    match text.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", text.replace('"', "\"\"")),
+        false => text.to_string(),
+    }
+}
@@ -0,0 +1,220 @@
+use std::{collections::HashMap, error::Error};
+use super::{
+    builders::{UserListBuilder, UserMangaListBuilder},
+    models::*,
+    user::{reading_status_param, status_param, FieldChange, UpdateAnime, UpdateManga},
+    xml::{parse_xml, XmlList},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EntryKind {
+    Anime,
+    Manga,
+}
+
+/// What an import does with one entry
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportAction {
+    /// The entry isn't on the user's list, and will be added
+    Add,
+    /// The entry is on the user's list, with fields that will change
+    Update,
+    /// The entry is already on the user's list as it is in the file
+    Unchanged,
+}
+
+/// One entry of an `ImportPlan`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedEntry {
+    pub kind: EntryKind,
+    pub id: u32,
+    pub title: String,
+    pub action: ImportAction,
+    /// The fields that will be sent, with their value on the user's list now. Empty when `Unchanged`
+    pub changes: Vec<FieldChange>,
+}
+
+/// The changes importing a MyAnimeList XML export makes to the user's lists, from `plan_import` or `reconcile`.<br>
+/// Nothing is changed until `apply()` is called, so the plan can be shown to the user first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportPlan {
+    pub entries: Vec<PlannedEntry>,
+}
+
+impl ImportPlan {
+    /// The entries that will be added to the user's lists
+    pub fn adds(&self) -> Vec<&PlannedEntry> {
+        self.with_action(ImportAction::Add)
+    }
+    /// The entries already on the user's lists that will change
+    pub fn updates(&self) -> Vec<&PlannedEntry> {
+        self.with_action(ImportAction::Update)
+    }
+    /// The entries already on the user's lists as they are in the file, which won't be sent
+    pub fn unchanged(&self) -> Vec<&PlannedEntry> {
+        self.with_action(ImportAction::Unchanged)
+    }
+    fn with_action(&self, action: ImportAction) -> Vec<&PlannedEntry> {
+        self.entries.iter().filter(|e| e.action == action).collect()
+    }
+    /// Calls the MyAnimeList API to add and update every entry of the plan, with `UpdateAnime` and `UpdateManga`.
+    /// Only the fields that change are sent, and unchanged entries are skipped.<br>
+    /// The user must first be logged in, for the app to have received a Token from MyAnimList.<br>
+    /// A failing entry does not stop the others, each entry's result is in the returned `ImportReport`.<br>
+    /// Async function, function must be called with `.await`<br>
+    /// Returns a `Result<>`, with a success containing an `ImportReport`
+    pub async fn apply(&self) -> Result<ImportReport, Box<dyn Error>> {
+        let mut report = ImportReport::default();
+        for entry in &self.entries {
+            if entry.action == ImportAction::Unchanged { continue }
+            let params: HashMap<String, String> = entry.changes
+                .iter()
+                .map(|change| (change.field.clone(), change.new.clone()))
+                .collect();
+            let result = match entry.kind {
+                EntryKind::Anime => UpdateAnime::from_params(entry.id, params).update().await.map(|_| ()),
+                EntryKind::Manga => UpdateManga::from_params(entry.id, params).update().await.map(|_| ()),
+            };
+            report.results.push((entry.kind, entry.id, result.map_err(|e| e.to_string())));
+        }
+        Ok(report)
+    }
+}
+
+/// The result of each entry sent by `ImportPlan::apply()`, in the plan's order
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub results: Vec<(EntryKind, u32, Result<(), String>)>,
+}
+
+impl ImportReport {
+    /// Returns the entries that were added or updated
+    pub fn successes(&self) -> Vec<(EntryKind, u32)> {
+        self.results.iter().filter(|(_, _, r)| r.is_ok()).map(|(kind, id, _)| (*kind, *id)).collect()
+    }
+    /// Returns the entries that failed, with the error
+    pub fn failures(&self) -> Vec<(EntryKind, u32, &String)> {
+        self.results.iter().filter_map(|(kind, id, r)| Some((*kind, *id, r.as_ref().err()?))).collect()
+    }
+    /// Returns true if every entry was sent
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|(_, _, r)| r.is_ok())
+    }
+}
+
+/// Reads a MyAnimeList XML export, and plans importing it into the logged in user's lists.<br>
+/// The user's whole anime list is fetched when the file has anime, and their manga list when it has manga.<br>
+/// User MUST be loggin in with the `login` function, and have a token generated for them for this to be used.<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing an `ImportPlan`
+/// ### Example usage:
+/// ```
+/// use mal_query::myanimelist::import::plan_import;
+/// async fn import_example() {
+///     let xml = std::fs::read_to_string("animelist.xml").unwrap();
+///     let plan = plan_import(&xml).await.unwrap();
+///     println!("{} to add, {} to update", plan.adds().len(), plan.updates().len());
+///     let report = plan.apply().await.unwrap();
+///     assert!(report.is_success());
+/// }
+/// ```
+pub async fn plan_import(xml: &str) -> Result<ImportPlan, Box<dyn Error>> {
+    let list = parse_xml(xml)?;
+    let anime = match list.anime.is_empty() {
+        true => Vec::new(),
+        false => UserListBuilder::new("@me").include_list_status().run_all().await?.data,
+    };
+    let manga = match list.manga.is_empty() {
+        true => Vec::new(),
+        false => UserMangaListBuilder::new("@me").include_list_status().run_all().await?.data,
+    };
+    Ok(reconcile(&list, &anime, &manga))
+}
+
+/// Compares the entries of an export against the user's current lists, which should have their `list_status`.
+/// Entries on the user's lists that aren't in the file are left alone.
+pub fn reconcile(list: &XmlList, anime: &[MalAnimeData], manga: &[MalMangaData]) -> ImportPlan {
+    let current_anime: HashMap<u32, &ListStatus> = anime
+        .iter()
+        .filter_map(|a| Some((a.id, a.list_status.as_ref()?)))
+        .collect();
+    let current_manga: HashMap<u32, &MangaListStatus> = manga
+        .iter()
+        .filter_map(|m| Some((m.id, m.list_status.as_ref()?)))
+        .collect();
+
+    let anime_entries = list.anime.iter().map(|a| {
+        let current = current_anime.get(&a.id).map(|s| anime_params(s));
+        planned(EntryKind::Anime, a.id, &a.title, anime_params(&a.list_status), current)
+    });
+    let manga_entries = list.manga.iter().map(|m| {
+        let current = current_manga.get(&m.id).map(|s| manga_params(s));
+        planned(EntryKind::Manga, m.id, &m.title, manga_params(&m.list_status), current)
+    });
+    ImportPlan { entries: anime_entries.chain(manga_entries).collect() }
+}
+
+fn planned(
+    kind: EntryKind,
+    id: u32,
+    title: &str,
+    new: Vec<(&'static str, String)>,
+    current: Option<Vec<(&'static str, String)>>,
+) -> PlannedEntry {
+    let current: Option<HashMap<&str, String>> = current.map(|c| c.into_iter().collect());
+    let mut changes: Vec<FieldChange> = new
+        .into_iter()
+        .filter_map(|(field, new)| {
+            let now = current.as_ref().and_then(|c| c.get(field).cloned());
+            match now.as_deref() == Some(new.as_str()) {
+                true => None,
+                false => Some(FieldChange { field: field.to_string(), current: now, new }),
+            }
+        })
+        .collect();
+    changes.sort_by(|a, b| a.field.cmp(&b.field));
+    let action = match (&current, changes.is_empty()) {
+        (None, _) => ImportAction::Add,
+        (Some(_), false) => ImportAction::Update,
+        (Some(_), true) => ImportAction::Unchanged,
+    };
+    PlannedEntry { kind, id, title: title.to_string(), action, changes }
+}
+
+// An entry as the params `UpdateAnime` sends. Fields MyAnimeList leaves out when unset are sent as their default,
+// so a missing value and the default compare as equal. Dates are only sent when the entry has them
+pub(crate) fn anime_params(status: &ListStatus) -> Vec<(&'static str, String)> {
+    let mut params = vec![
+        ("status", status_param(&status.status).to_string()),
+        ("score", status.score.to_string()),
+        ("num_watched_episodes", status.num_episodes_watched.to_string()),
+        ("is_rewatching", status.is_rewatching.to_string()),
+        ("priority", status.priority.unwrap_or(0).to_string()),
+        ("num_times_rewatched", status.num_times_rewatched.unwrap_or(0).to_string()),
+        ("rewatch_value", status.rewatch_value.unwrap_or(0).to_string()),
+        ("tags", status.tags.as_deref().unwrap_or_default().join(",")),
+        ("comments", status.comments.clone().unwrap_or_default()),
+    ];
+    if let Some(date) = &status.start_date { params.push(("start_date", date.clone())) }
+    if let Some(date) = &status.finish_date { params.push(("finish_date", date.clone())) }
+    params
+}
+
+// The same as `anime_params`, as the params `UpdateManga` sends
+fn manga_params(status: &MangaListStatus) -> Vec<(&'static str, String)> {
+    let mut params = vec![
+        ("status", reading_status_param(&status.status).to_string()),
+        ("score", status.score.to_string()),
+        ("num_volumes_read", status.num_volumes_read.to_string()),
+        ("num_chapters_read", status.num_chapters_read.to_string()),
+        ("is_rereading", status.is_rereading.to_string()),
+        ("priority", status.priority.unwrap_or(0).to_string()),
+        ("num_times_reread", status.num_times_reread.unwrap_or(0).to_string()),
+        ("reread_value", status.reread_value.unwrap_or(0).to_string()),
+        ("tags", status.tags.as_deref().unwrap_or_default().join(",")),
+        ("comments", status.comments.clone().unwrap_or_default()),
+    ];
+    if let Some(date) = &status.start_date { params.push(("start_date", date.clone())) }
+    if let Some(date) = &status.finish_date { params.push(("finish_date", date.clone())) }
+    params
+}
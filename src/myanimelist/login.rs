@@ -1,9 +1,9 @@
-use std::{error::Error, fs::File, io::Write};
+use std::{error::Error, fs::{self, File}, io::{ErrorKind, Write}};
 use reqwest::Body;
 use serde_json::Value;
 use url::Url;
 use tiny_http::Server;
-use super::{CLIENT_ID, TOKEN};
+use super::{client, CLIENT_ID, TOKEN};
 
 /// This function will create a User's authentication token to use more MyAnimeList features, such as updating your lists entries, or viewing your list statistics.<br>
 /// Calling this function will send the user to a browser to accept the OAuth2.0 authentication, and than use the callback to generate a user's token.<br>
@@ -23,7 +23,9 @@ pub async fn login() -> Result<(), Box<dyn Error>> {
     // Creates and sends the url
     let code_verify = pkce::code_verifier(50);
     let code_challenge = pkce::code_challenge(&code_verify);
-    let auth_url: String = format!("https://myanimelist.net/v1/oauth2/authorize?response_type=code&client_id={}&code_challenge={code_challenge}", CLIENT_ID.clone());
+    let mal = client();
+    let oauth_url = mal.get_oauth_url();
+    let auth_url: String = format!("{oauth_url}/authorize?response_type=code&client_id={}&code_challenge={code_challenge}", CLIENT_ID.clone());
     
     // Opens a localhost server
     let server = Server::http("127.0.0.1:8080").expect("Failed to create server");
@@ -44,8 +46,7 @@ pub async fn login() -> Result<(), Box<dyn Error>> {
     }
 
     // Requests the access_token
-    let client = reqwest::Client::new();
-    let res = client.post("https://myanimelist.net/v1/oauth2/token")
+    let res = mal.http().post(format!("{oauth_url}/token"))
         .header("content-type", "application/x-www-form-urlencoded")
         .body(Body::from(format!(
             "client_id={}&code={}&code_verifier={}&grant_type=authorization_code",
@@ -70,4 +71,27 @@ pub async fn login() -> Result<(), Box<dyn Error>> {
         },
         None => Err(format!("{json:?}"))?,
     }
-}
\ No newline at end of file
+}
+
+/// This function logs the user out, forgetting their authentication token, and deleting the `token.txt` that `login()` saved.<br>
+/// It succeeds if the user wasn't logged in.<br>
+/// ### Example usage:
+/// ```
+/// use mal_query::myanimelist::login::logout;
+/// fn logout_example() {
+///     logout().unwrap();
+/// }
+/// ```
+pub fn logout() -> Result<(), Box<dyn Error>> {
+    TOKEN.lock().unwrap().clear();
+    match fs::remove_file("token.txt") {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(e)?,
+        _ => Ok(()),
+    }
+}
+
+/// Logs in with a token the app already has, such as one kept somewhere other than `token.txt`, instead of `login()`.<br>
+/// The token is not saved to `token.txt`.
+pub fn set_token(token: &str) {
+    *TOKEN.lock().unwrap() = token.to_string();
+}
@@ -1,42 +1,63 @@
-use std::{sync::Mutex, fs, error::Error};
+use std::{sync::{Mutex, RwLock}, fs, error::Error};
 use lazy_static::lazy_static;
 use reqwest::Response;
 use serde_json::Value;
-use self::models::*;
+use self::{client::MalClient, models::*};
 
-pub use login::login;
+pub use login::{login, logout, set_token};
 
 pub mod retrieval;
 pub mod login;
 pub mod builders;
 pub mod models;
 pub mod user;
+pub mod calendar;
+pub mod xml;
+pub mod import;
+pub mod export;
+pub mod sync;
+pub mod client;
+pub mod cache;
+pub mod transport;
 
 lazy_static! {
     // Couldn't find effective way to hide this
     static ref CLIENT_ID: String = String::from("f7e5c56ef3561bb0a290a13d35b02c0b");
     static ref TOKEN: Mutex<String> = Mutex::new(fs::read_to_string("token.txt").unwrap_or_default());
+    static ref CLIENT: RwLock<MalClient> = RwLock::new(MalClient::new());
+}
+
+/// Replaces the client every `myanimelist` function gets its data through, such as one with a `ResponseCache`
+pub fn set_client(client: &MalClient) {
+    *CLIENT.write().unwrap() = client.clone();
+}
+
+// The client is cloned out, so the lock isn't held while requests wait
+fn client() -> MalClient {
+    CLIENT.read().unwrap().clone()
+}
+
+// Removes the responses an update to the user's anime entry changes, when the client has a cache
+fn invalidate_anime(id: u32) {
+    if let Some(cache) = client().get_cache() { cache.invalidate_anime(id) }
+}
+
+fn invalidate_manga(id: u32) {
+    if let Some(cache) = client().get_cache() { cache.invalidate_manga(id) }
+}
+
+// A MyAnimeList API URL for a path such as `/anime/21`, on the client's API URL
+pub(crate) fn api_url(path: &str) -> String {
+    format!("{}{path}", client().get_api_url())
 }
 
 async fn client_call(url: &str) -> Result<Response, Box<dyn Error>> {
-    let token = TOKEN.lock().unwrap();
-    let header_key: &str;
-    let header_value: String;
-    if token.is_empty() {
-        header_key = "X-MAL-CLIENT-ID";
-        header_value = CLIENT_ID.clone();
-    } else {
-        header_key = "Authorization";
-        header_value = format!("Bearer {}", *token);
-    }
+    client().get(url).await
+}
 
-    let client = reqwest::Client::new();
-    let res = client
-        .get(url)
-        .header(header_key, header_value)
-        .send()
-        .await?;
-    Ok(res)
+// As `client_call`, skipping the cache, for state that is about to be written
+async fn client_call_uncached(url: &str) -> Result<Response, Box<dyn Error>> {
+    client().get_uncached(url).await
 }
 
 // To get one anime
@@ -54,7 +75,7 @@ async fn run_get(url: &str) -> Result<MalAnimeData, Box<dyn Error>> {
 }
 
 // To get Vec of anime (search)
-async fn run_search(url: &str) -> Result<MalAnimeSearch, Box<dyn Error>> {
+pub(crate) async fn run_search(url: &str) -> Result<MalAnimeSearch, Box<dyn Error>> {
     let res = client_call(url).await?;
 
     if res.status().is_success() {
@@ -121,9 +142,9 @@ async fn run_search_manga(url: &str) -> Result<MalMangaSearch, Box<dyn Error>> {
                 if let Some(r) = v.get("ranking") {
                     to_push.rank = Some(r["rank"].as_u64().unwrap() as u32);
                 }
-                // get_user_animelist has slightly different results
+                // get_user_mangalist has slightly different results
                 if let Some(s) = v.get("list_status") {
-                    let status = serde_json::from_value::<ListStatus>(s.clone()).unwrap();
+                    let status = serde_json::from_value::<MangaListStatus>(s.clone()).unwrap();
                     to_push.list_status = Some(status);
                 }
                 result.push(to_push);
@@ -1,8 +1,9 @@
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
 
 // -------- enums --------
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
 pub enum RankingType {
     All,
     Airing,
@@ -16,7 +17,7 @@ pub enum RankingType {
     None,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
 pub enum Season {
     #[serde(rename = "winter")]
     Winter,
@@ -28,7 +29,7 @@ pub enum Season {
     Fall
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub enum Nsfw {
     #[serde(rename = "white")]
     White,
@@ -38,7 +39,7 @@ pub enum Nsfw {
     Black,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
 pub enum AnimeMediaType {
     #[serde(rename = "unknown")]
     Unknown,
@@ -56,7 +57,7 @@ pub enum AnimeMediaType {
     Music,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
 pub enum AiringStatus {
     #[serde(rename = "finished_airing")]
     FinishedAiring,
@@ -66,7 +67,7 @@ pub enum AiringStatus {
     NotYetAired,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
 pub enum Status {
     #[serde(rename = "watching")]
     Watching,
@@ -80,7 +81,7 @@ pub enum Status {
     PlanToWatch,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub enum Source {
     #[serde(rename = "other")]
     Other,
@@ -114,7 +115,7 @@ pub enum Source {
     Music,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub enum Rating {
     #[serde(rename = "g")]
     G,
@@ -130,7 +131,43 @@ pub enum Rating {
     RX,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub enum Weekday {
+    #[serde(rename = "monday")]
+    Monday,
+    #[serde(rename = "tuesday")]
+    Tuesday,
+    #[serde(rename = "wednesday")]
+    Wednesday,
+    #[serde(rename = "thursday")]
+    Thursday,
+    #[serde(rename = "friday")]
+    Friday,
+    #[serde(rename = "saturday")]
+    Saturday,
+    #[serde(rename = "sunday")]
+    Sunday,
+    #[serde(rename = "other")]
+    Other,
+}
+
+impl Weekday {
+    /// Converts to a `chrono::Weekday`, or `None` for `Weekday::Other`
+    pub fn to_chrono(&self) -> Option<chrono::Weekday> {
+        match self {
+            Weekday::Monday => Some(chrono::Weekday::Mon),
+            Weekday::Tuesday => Some(chrono::Weekday::Tue),
+            Weekday::Wednesday => Some(chrono::Weekday::Wed),
+            Weekday::Thursday => Some(chrono::Weekday::Thu),
+            Weekday::Friday => Some(chrono::Weekday::Fri),
+            Weekday::Saturday => Some(chrono::Weekday::Sat),
+            Weekday::Sunday => Some(chrono::Weekday::Sun),
+            Weekday::Other => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub enum Sort {
     ListScore,
     ListUpdatedAt,
@@ -141,7 +178,7 @@ pub enum Sort {
 
 // -------- MalAnimeData --------
 
-#[derive(Debug, Deserialize, Default, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Default, PartialEq)]
 pub struct MalAnimeData {
     pub id: u32,
     pub title: String,
@@ -177,20 +214,20 @@ pub struct MalAnimeData {
     pub statistics: Option<Statistics>,
 }
 
-#[derive(Debug, Deserialize, Default, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Default, PartialEq)]
 pub struct Picture {
     pub large: String,
     pub medium: String,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub struct AlternativeTitles {
     pub synonyms: Vec<String>,
     pub en: String,
     pub ja: String,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub struct Genres {
     pub id: u32,
     pub name: String,
@@ -212,42 +249,74 @@ pub struct ListStatus {
     pub updated_at: String,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub struct StartSeason {
     pub year: u32,
     pub season: Season,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+/// When an anime airs each week, in Japan time (JST)
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub struct Broadcast {
-    pub day_of_the_week: String, // enum?
-    pub start_time: String,
+    pub day_of_the_week: Weekday,
+    #[serde(default, deserialize_with = "deserialize_start_time")]
+    pub start_time: Option<NaiveTime>,
+}
+
+impl Broadcast {
+    /// Gets the first broadcast after `after`, in UTC.<br>
+    /// Returns `None` if the weekday is `Weekday::Other`, or there is no start time.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let day = self.day_of_the_week.to_chrono()?;
+        let time = self.start_time?;
+
+        let date = after.with_timezone(&jst()).date_naive();
+        let days_ahead = (7 + day.num_days_from_monday() - date.weekday().num_days_from_monday()) % 7;
+        let mut next = jst()
+            .from_local_datetime(&(date + Duration::days(days_ahead as i64)).and_time(time))
+            .single()?;
+        if next <= after {
+            next += Duration::days(7);
+        }
+        Some(next.with_timezone(&Utc))
+    }
+}
+
+/// Japan Standard Time, which MyAnimeList's broadcast times are in. Japan has no daylight saving time.
+pub fn jst() -> FixedOffset {
+    FixedOffset::east_opt(9 * 3600).unwrap()
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+// MyAnimeList sends the time as "HH:MM". Anything else is treated as an unknown time, rather than failing the whole anime
+fn deserialize_start_time<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<NaiveTime>, D::Error> {
+    let time: Option<String> = Option::deserialize(deserializer)?;
+    Ok(time.and_then(|t| NaiveTime::parse_from_str(&t, "%H:%M").ok()))
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub struct Studios {
     pub id: u32,
     pub name: String,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub struct RelatedAnime {
     pub node: MalAnimeData,
     pub relation_type: String,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub struct Recommended {
     pub node: MalAnimeData,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub struct Statistics {
     pub num_list_users: u32,
     pub status: StatisticsStatus,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub struct StatisticsStatus {
     pub watching: String,
     pub completed: String,
@@ -260,7 +329,24 @@ pub struct StatisticsStatus {
 
 // TODO: create getters for option layered structs
 
-#[derive(Debug)]
+impl MalAnimeData {
+    /// For an anime that is currently airing, gets its next broadcast in UTC, from the broadcast's Japan time slot.<br>
+    /// Returns `None` if the anime isn't currently airing, or the `broadcast` field wasn't received.
+    pub fn next_broadcast(&self) -> Option<DateTime<Utc>> {
+        self.next_broadcast_after(Utc::now())
+    }
+    /// The same as `next_broadcast()`, converted into the timezone given, such as `chrono::Local`
+    pub fn next_broadcast_in<Tz: TimeZone>(&self, tz: &Tz) -> Option<DateTime<Tz>> {
+        self.next_broadcast().map(|next| next.with_timezone(tz))
+    }
+    /// The same as `next_broadcast()`, but the next broadcast after a given instant instead of now
+    pub fn next_broadcast_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if self.status != Some(AiringStatus::CurrentlyAiring) { return None }
+        self.broadcast.as_ref()?.next_after(after)
+    }
+}
+
+#[derive(Debug, Serialize)]
 pub struct MalAnimeSearch {
     pub data: Vec<MalAnimeData>,
 }
@@ -284,7 +370,7 @@ impl MalAnimeSearch {
 
 // -------- Manga Enums ---------
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub enum MangaRankingType {
     All,
     Manga,
@@ -299,7 +385,7 @@ pub enum MangaRankingType {
     None,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub enum MangaMediaType {
     #[serde(rename = "unknown")]
     Unknown,
@@ -321,7 +407,7 @@ pub enum MangaMediaType {
     OEL,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub enum PublishingStatus {
     #[serde(rename = "finished")]
     Finished,
@@ -331,9 +417,23 @@ pub enum PublishingStatus {
     NotYetPublished,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub enum ReadingStatus {
+    #[serde(rename = "reading")]
+    Reading,
+    #[serde(rename = "completed")]
+    Completed,
+    #[serde(rename = "on_hold")]
+    OnHold,
+    #[serde(rename = "dropped")]
+    Dropped,
+    #[serde(rename = "plan_to_read")]
+    PlanToRead,
+}
+
 // -------- MalMangaData --------
 
-#[derive(Debug, Deserialize, Default, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Default, PartialEq)]
 pub struct MalMangaData {
     pub id: u32,
     pub title: String,
@@ -353,7 +453,7 @@ pub struct MalMangaData {
     pub updated_at: Option<String>,
     pub media_type: Option<MangaMediaType>,
     pub status: Option<PublishingStatus>,
-    pub list_status: Option<ListStatus>,
+    pub list_status: Option<MangaListStatus>,
     pub num_volumes: Option<u32>,
     pub num_chapters: Option<u32>,
     // pub authors: Option<Vec<Authors>>,
@@ -367,7 +467,25 @@ pub struct MalMangaData {
 
 // -------- Structs --------
 
-#[derive(Debug, Deserialize, PartialEq)]
+/// The user's entry for a manga on their list, the manga version of `ListStatus`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct MangaListStatus {
+    pub status: ReadingStatus,
+    pub score: u32,
+    pub num_volumes_read: u32,
+    pub num_chapters_read: u32,
+    pub is_rereading: bool,
+    pub start_date: Option<String>,
+    pub finish_date: Option<String>,
+    pub priority: Option<u32>,
+    pub num_times_reread: Option<u32>,
+    pub reread_value: Option<u32>,
+    pub tags: Option<Vec<String>>,
+    pub comments: Option<String>,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub struct RelatedManga {
     pub node: MalMangaData,
     pub relation_type: String,
@@ -381,7 +499,7 @@ pub struct RelatedManga {
 
 // -------- Methods --------
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct MalMangaSearch {
     pub data: Vec<MalMangaData>,
 }
@@ -401,4 +519,20 @@ impl MalMangaSearch {
     pub fn get(&self, index: usize) -> Option<&MalMangaData> {
         self.data.get(index)
     }
-}
\ No newline at end of file
+}
+
+// -------- MalUser --------
+
+/// The logged in user, from `user::get_my_user`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct MalUser {
+    pub id: u32,
+    pub name: String,
+    pub picture: Option<String>,
+    pub gender: Option<String>,
+    pub birthday: Option<String>,
+    pub location: Option<String>,
+    pub joined_at: Option<String>,
+    pub time_zone: Option<String>,
+    pub is_supporter: Option<bool>,
+}
@@ -8,7 +8,7 @@ use super::*;
 /// Async function, function must be called with `.await`<br>
 /// Returns a `Result<>`, with a success containing a `MalAnimeSearch`
 pub async fn search_anime(name: &str, limit: u32) -> Result<MalAnimeSearch, Box<dyn Error>> {
-    let base_url = format!("https://api.myanimelist.net/v2/anime?q={name}&limit={limit}");
+    let base_url = api_url(&format!("/anime?q={name}&limit={limit}"));
     run_search(&base_url).await
 }
 
@@ -22,7 +22,7 @@ pub async fn get_season(year: u32, season: Season) -> Result<MalAnimeSearch, Box
         Season::Summer => "summer",
         Season::Fall => "fall"
     };
-    let base_url = format!("https://api.myanimelist.net/v2/anime/season/{year}/{s}?limit=500");
+    let base_url = api_url(&format!("/anime/season/{year}/{s}?limit=500"));
     run_search(&base_url).await
 }
 
@@ -30,10 +30,9 @@ pub async fn get_season(year: u32, season: Season) -> Result<MalAnimeSearch, Box
 /// Async function, function must be called with `.await`<br>
 /// Returns a `Result<>`, with a success containing a `MalAnimeSearch`
 pub async fn get_anime(id: u32) -> Result<MalAnimeData, Box<dyn Error>> {
-    let base_url = format!(
-        "https://api.myanimelist.net/v2/anime/{id}?fields=id,title,main_picture,alternative_titles,start_date,end_date,synopsis,mean,rank,
+    let base_url = api_url(&format!("/anime/{id}?fields=id,title,main_picture,alternative_titles,start_date,end_date,synopsis,mean,rank,
         popularity,num_list_users,num_scoring_users,nsfw,created_at,updated_at,media_type,status,genres,my_list_status,num_episodes,
-        start_season,broadcast,source,average_episode_duration,rating,pictures,background,related_anime,related_manga,recommendations,studios,statistics");
+        start_season,broadcast,source,average_episode_duration,rating,pictures,background,related_anime,related_manga,recommendations,studios,statistics"));
     run_get(&base_url).await
 }
 
@@ -67,7 +66,7 @@ pub async fn get_anime_rankings(ranking_type: RankingType, limit: u32) -> Result
         RankingType::Favorite => "favorite",
         RankingType::None => ""
     };
-    let base_url = format!("https://api.myanimelist.net/v2/anime/ranking?ranking_type={r_type}&limit={limit}");
+    let base_url = api_url(&format!("/anime/ranking?ranking_type={r_type}&limit={limit}"));
     run_search(&base_url).await
 }
 
@@ -75,7 +74,7 @@ pub async fn get_anime_rankings(ranking_type: RankingType, limit: u32) -> Result
 /// Async function, function must be called with `.await`<br>
 /// Returns a `Result<>`, with a success containing a `MalAnimeSearch`
 pub async fn get_user_animelist(username: &str, limit: u32) -> Result<MalAnimeSearch, Box<dyn Error>> {
-    let base_url = format!("https://api.myanimelist.net/v2/users/{username}/animelist?fields=list_status{{is_rewatching,num_times_rewatched,rewatch_value,priority,tags,comments,start_date,end_date}}&limit={limit}");
+    let base_url = api_url(&format!("/users/{username}/animelist?fields=list_status{{is_rewatching,num_times_rewatched,rewatch_value,priority,tags,comments,start_date,end_date}}&limit={limit}"));
     run_search(&base_url).await
 }
 
@@ -85,7 +84,7 @@ pub async fn get_user_animelist(username: &str, limit: u32) -> Result<MalAnimeSe
 /// Async function, function must be called with `.await`<br>
 /// Returns a `Result<>`, with a success containing a `MalMangaSearch`
 pub async fn search_manga(name: &str, limit: u32) -> Result<MalMangaSearch, Box<dyn Error>> {
-    let base_url = format!("https://api.myanimelist.net/v2/manga?q={name}&limit={limit}");
+    let base_url = api_url(&format!("/manga?q={name}&limit={limit}"));
     run_search_manga(&base_url).await
 }
 
@@ -93,10 +92,9 @@ pub async fn search_manga(name: &str, limit: u32) -> Result<MalMangaSearch, Box<
 /// Async function, function must be called with `.await`<br>
 /// Returns a `Result<>`, with a success containing a `MalMangaData`
 pub async fn get_manga(id: u32) -> Result<MalMangaData, Box<dyn Error>> {
-    let base_url = format!(
-        "https://api.myanimelist.net/v2/manga/{id}?fields=id,title,main_picture,alternative_titles,start_date,end_date,synopsis,mean,rank,
+    let base_url = api_url(&format!("/manga/{id}?fields=id,title,main_picture,alternative_titles,start_date,end_date,synopsis,mean,rank,
         popularity,num_list_users,num_scoring_users,nsfw,created_at,updated_at,media_type,status,genres,my_list_status,,num_volumes,
-        num_chapters,authors{{first_name,last_name}},pictures,background,related_anime,related_manga,recommendations,serialization{{name}}");
+        num_chapters,authors{{first_name,last_name}},pictures,background,related_anime,related_manga,recommendations,serialization{{name}}"));
     run_get_manga(&base_url).await
 }
 
@@ -131,7 +129,7 @@ pub async fn get_manga_rankings(ranking_type: MangaRankingType, limit: u32) -> R
         MangaRankingType::Favorite => "favorite",
         MangaRankingType::None => ""
     };
-    let base_url = format!("https://api.myanimelist.net/v2/manga/ranking?ranking_type={r_type}&limit={limit}");
+    let base_url = api_url(&format!("/manga/ranking?ranking_type={r_type}&limit={limit}"));
     run_search_manga(&base_url).await
 }
 
@@ -139,6 +137,6 @@ pub async fn get_manga_rankings(ranking_type: MangaRankingType, limit: u32) -> R
 /// Async function, function must be called with `.await`<br>
 /// Returns a `Result<>`, with a success containing a `MalAnimeSearch`
 pub async fn get_user_mangalist(username: &str, limit: u32) -> Result<MalMangaSearch, Box<dyn Error>> {
-    let base_url = format!("https://api.myanimelist.net/v2/users/{username}/mangalist?fields=list_status{{is_rewatching,num_times_rewatched,rewatch_value,priority,tags,comments,start_date,end_date}}&limit={limit}");
+    let base_url = api_url(&format!("/users/{username}/mangalist?fields=list_status{{is_rereading,num_times_reread,reread_value,priority,tags,comments,start_date,finish_date}}&limit={limit}"));
     run_search_manga(&base_url).await
 }
\ No newline at end of file
@@ -0,0 +1,411 @@
+use std::{collections::{BTreeMap, BTreeSet, HashMap}, error::Error, fs, io::ErrorKind, path::{Path, PathBuf}};
+use serde::{Deserialize, Serialize};
+use super::{
+    builders::{AddFields, UserListBuilder},
+    import::anime_params,
+    models::*,
+    user::{delete_anime, UpdateAnime},
+    xml::{anime_list_xml, parse_xml},
+};
+
+/// A list that can be synchronized with `sync`, such as the logged in user's `MalAccount` or a `FileSource`
+#[allow(async_fn_in_trait)]
+pub trait ListSource {
+    /// Reads every entry of the list. Entries without a `list_status` are not on the list, and are ignored
+    async fn load(&mut self) -> Result<Vec<MalAnimeData>, Box<dyn Error>>;
+    /// Adds the anime to the list, or changes its entry
+    async fn set(&mut self, id: u32, title: &str, status: &ListStatus) -> Result<(), Box<dyn Error>>;
+    /// Removes the anime from the list
+    async fn delete(&mut self, id: u32) -> Result<(), Box<dyn Error>>;
+    /// Called once every change is made, to save them. Does nothing by default
+    async fn finish(&mut self) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+}
+
+/// The logged in user's anime list on MyAnimeList. Entries are changed with `UpdateAnime` and `delete_anime`.<br>
+/// User MUST be loggin in with the `login` function, and have a token generated for them for this to be used.
+#[derive(Debug, Default)]
+pub struct MalAccount;
+
+impl ListSource for MalAccount {
+    async fn load(&mut self) -> Result<Vec<MalAnimeData>, Box<dyn Error>> {
+        let list = UserListBuilder::new("@me")
+            .include_list_status()
+            .add_num_episodes()
+            .run_all()
+            .await?;
+        Ok(list.data)
+    }
+    async fn set(&mut self, id: u32, _title: &str, status: &ListStatus) -> Result<(), Box<dyn Error>> {
+        let params: HashMap<String, String> = anime_params(status)
+            .into_iter()
+            .map(|(field, value)| (field.to_string(), value))
+            .collect();
+        UpdateAnime::from_params(id, params).update().await?;
+        Ok(())
+    }
+    async fn delete(&mut self, id: u32) -> Result<(), Box<dyn Error>> {
+        delete_anime(id).await
+    }
+}
+
+/// An anime list saved in a file, either a MyAnimeList XML export (when the path ends with `.xml`)
+/// or a JSON array of `MalAnimeData`.<br>
+/// A file that doesn't exist is read as an empty list, and is created by `finish`.
+#[derive(Debug)]
+pub struct FileSource {
+    path: PathBuf,
+    username: String,
+    entries: Vec<MalAnimeData>,
+}
+
+impl FileSource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileSource {
+            path: path.into(),
+            username: String::new(),
+            entries: Vec::new(),
+        }
+    }
+    fn is_xml(&self) -> bool {
+        self.path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("xml"))
+    }
+}
+
+impl ListSource for FileSource {
+    async fn load(&mut self) -> Result<Vec<MalAnimeData>, Box<dyn Error>> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => Err(e)?,
+        };
+        self.entries = match (self.is_xml(), text.trim().is_empty()) {
+            (_, true) => Vec::new(),
+            (true, false) => {
+                let list = parse_xml(&text)?;
+                self.username = list.username;
+                list.anime
+                    .into_iter()
+                    .map(|a| MalAnimeData {
+                        id: a.id,
+                        title: a.title,
+                        num_episodes: (a.num_episodes > 0).then_some(a.num_episodes),
+                        list_status: Some(a.list_status),
+                        ..Default::default()
+                    })
+                    .collect()
+            },
+            (false, false) => serde_json::from_str(&text)?,
+        };
+        // The list is kept as it was read, so the file can be written back with every field it had
+        Ok(self.entries.iter().map(copy_entry).collect())
+    }
+    async fn set(&mut self, id: u32, title: &str, status: &ListStatus) -> Result<(), Box<dyn Error>> {
+        match self.entries.iter_mut().find(|a| a.id == id) {
+            Some(entry) => entry.list_status = Some(status.clone()),
+            None => self.entries.push(MalAnimeData {
+                id,
+                title: title.to_string(),
+                list_status: Some(status.clone()),
+                ..Default::default()
+            }),
+        }
+        Ok(())
+    }
+    async fn delete(&mut self, id: u32) -> Result<(), Box<dyn Error>> {
+        self.entries.retain(|a| a.id != id);
+        Ok(())
+    }
+    async fn finish(&mut self) -> Result<(), Box<dyn Error>> {
+        let text = match self.is_xml() {
+            true => anime_list_xml(&self.entries, &self.username),
+            false => serde_json::to_string_pretty(&self.entries)?,
+        };
+        fs::write(&self.path, text)?;
+        Ok(())
+    }
+}
+
+// The fields a sync uses, as `MalAnimeData` isn't `Clone`
+fn copy_entry(entry: &MalAnimeData) -> MalAnimeData {
+    MalAnimeData {
+        id: entry.id,
+        title: entry.title.clone(),
+        num_episodes: entry.num_episodes,
+        list_status: entry.list_status.clone(),
+        ..Default::default()
+    }
+}
+
+/// Both lists as they were after the last sync, which `plan` compares each list to, to know which side changed.<br>
+/// Saved as JSON, by anime id.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncSnapshot {
+    pub entries: BTreeMap<u32, ListStatus>,
+}
+
+impl SyncSnapshot {
+    /// Reads a snapshot saved by `save`. A file that doesn't exist is an empty snapshot, as before the first sync
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(serde_json::from_str(&text)?),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(SyncSnapshot::default()),
+            Err(e) => Err(e)?,
+        }
+    }
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// One of the two lists given to `sync` or `plan`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Side {
+    A,
+    B,
+}
+
+/// How `plan` settles an entry both lists changed differently since the last sync
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictPolicy {
+    /// The entry with the newest `updated_at` is kept. When one side removed the entry, the side that changed it wins
+    NewestWins,
+    /// List A's entry is kept
+    PreferA,
+    /// List B's entry is kept
+    PreferB,
+    /// Nothing is changed, the conflict is in `SyncPlan::conflicts` to be settled with `SyncPlan::resolve`
+    Manual,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncAction {
+    /// Adds the entry, or changes it to this status
+    Set(ListStatus),
+    Delete,
+}
+
+/// A change `SyncPlan::apply` makes to one list
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncChange {
+    pub id: u32,
+    pub title: String,
+    /// The list that is changed
+    pub target: Side,
+    pub action: SyncAction,
+}
+
+/// An entry both lists changed differently since the last sync. `None` when the list removed it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncConflict {
+    pub id: u32,
+    pub title: String,
+    pub a: Option<ListStatus>,
+    pub b: Option<ListStatus>,
+    /// The entry at the last sync, `None` when it was added since
+    pub base: Option<ListStatus>,
+}
+
+/// The changes that bring two lists together, from `plan`.<br>
+/// Nothing is changed until `apply()` is called, so the plan can be shown to the user first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncPlan {
+    pub changes: Vec<SyncChange>,
+    pub conflicts: Vec<SyncConflict>,
+    // The last sync, and both lists once every change is made
+    base: SyncSnapshot,
+    merged: SyncSnapshot,
+}
+
+impl SyncPlan {
+    /// Settles a conflict by keeping one side's entry, adding the change to the other side to `changes`.<br>
+    /// Returns `false` when there is no conflict for the anime.
+    pub fn resolve(&mut self, id: u32, keep: Side) -> bool {
+        let Some(index) = self.conflicts.iter().position(|c| c.id == id) else { return false };
+        let conflict = self.conflicts.remove(index);
+        let (kept, target) = match keep {
+            Side::A => (conflict.a, Side::B),
+            Side::B => (conflict.b, Side::A),
+        };
+        self.changes.push(change(id, &conflict.title, target, kept.as_ref()));
+        match kept {
+            Some(status) => self.merged.entries.insert(id, status),
+            None => self.merged.entries.remove(&id),
+        };
+        true
+    }
+    /// Makes every change to both lists, in order. A failing change does not stop the others, each change's result
+    /// is in the returned `SyncReport`, whose snapshot keeps the failed entries as they were at the last sync,
+    /// so they are tried again next time.<br>
+    /// `finish` is not called on the lists.<br>
+    /// Async function, function must be called with `.await`<br>
+    /// Returns a `Result<>`, with a success containing a `SyncReport`
+    pub async fn apply(
+        &self,
+        a: &mut impl ListSource,
+        b: &mut impl ListSource,
+    ) -> Result<SyncReport, Box<dyn Error>> {
+        let mut snapshot = self.merged.clone();
+        let mut results = Vec::new();
+        for change in &self.changes {
+            let result = match (change.target, &change.action) {
+                (Side::A, SyncAction::Set(status)) => a.set(change.id, &change.title, status).await,
+                (Side::A, SyncAction::Delete) => a.delete(change.id).await,
+                (Side::B, SyncAction::Set(status)) => b.set(change.id, &change.title, status).await,
+                (Side::B, SyncAction::Delete) => b.delete(change.id).await,
+            };
+            if result.is_err() {
+                match self.base.entries.get(&change.id) {
+                    Some(status) => snapshot.entries.insert(change.id, status.clone()),
+                    None => snapshot.entries.remove(&change.id),
+                };
+            }
+            results.push((change.target, change.id, result.map_err(|e| e.to_string())));
+        }
+        Ok(SyncReport { results, conflicts: self.conflicts.clone(), snapshot })
+    }
+}
+
+/// The result of each change made by `SyncPlan::apply()`, in the plan's order
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub results: Vec<(Side, u32, Result<(), String>)>,
+    /// The conflicts left for the user, with `ConflictPolicy::Manual`
+    pub conflicts: Vec<SyncConflict>,
+    /// The snapshot to compare the lists to next time
+    pub snapshot: SyncSnapshot,
+}
+
+impl SyncReport {
+    /// Returns the changes that were made
+    pub fn successes(&self) -> Vec<(Side, u32)> {
+        self.results.iter().filter(|(_, _, r)| r.is_ok()).map(|(side, id, _)| (*side, *id)).collect()
+    }
+    /// Returns the changes that failed, with the error
+    pub fn failures(&self) -> Vec<(Side, u32, &String)> {
+        self.results.iter().filter_map(|(side, id, r)| Some((*side, *id, r.as_ref().err()?))).collect()
+    }
+    /// Returns true if every change was made
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|(_, _, r)| r.is_ok())
+    }
+}
+
+/// Reads both lists and the last sync's snapshot, makes the changes that bring the lists together, and saves
+/// both lists and the new snapshot. The first sync, without a snapshot, only adds entries, and settles entries
+/// on both lists by the policy.<br>
+/// With `ConflictPolicy::Manual`, the conflicts are in the report, and are left as they are. To settle them
+/// first, use `plan` and `SyncPlan::resolve`.<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `SyncReport`
+/// ### Example usage:
+/// ```
+/// use mal_query::myanimelist::sync::{sync, ConflictPolicy, FileSource, MalAccount};
+/// async fn sync_example() {
+///     let mut file = FileSource::new("animelist.xml");
+///     let report = sync(&mut MalAccount, &mut file, "sync.json", ConflictPolicy::NewestWins).await.unwrap();
+///     assert!(report.is_success());
+/// }
+/// ```
+pub async fn sync(
+    a: &mut impl ListSource,
+    b: &mut impl ListSource,
+    snapshot_path: impl AsRef<Path>,
+    policy: ConflictPolicy,
+) -> Result<SyncReport, Box<dyn Error>> {
+    let base = SyncSnapshot::load(&snapshot_path)?;
+    let a_list = a.load().await?;
+    let b_list = b.load().await?;
+    let report = plan(&a_list, &b_list, &base, policy).apply(a, b).await?;
+    a.finish().await?;
+    b.finish().await?;
+    report.snapshot.save(&snapshot_path)?;
+    Ok(report)
+}
+
+/// Compares both lists to the last sync, a three way diff. An entry only one side changed is copied to the other,
+/// and an entry both changed differently is a conflict, settled by the policy.<br>
+/// Entries are compared by the fields `UpdateAnime` sends, so `updated_at` alone is not a change.
+pub fn plan(a: &[MalAnimeData], b: &[MalAnimeData], base: &SyncSnapshot, policy: ConflictPolicy) -> SyncPlan {
+    let a_entries = listed(a);
+    let b_entries = listed(b);
+    let ids: BTreeSet<u32> = a_entries.keys().chain(b_entries.keys()).chain(base.entries.keys()).copied().collect();
+
+    let mut plan = SyncPlan { base: base.clone(), ..Default::default() };
+    for id in ids {
+        let a_entry = a_entries.get(&id);
+        let b_entry = b_entries.get(&id);
+        let a_status = a_entry.map(|(_, s)| *s);
+        let b_status = b_entry.map(|(_, s)| *s);
+        let base_status = base.entries.get(&id);
+        let title = a_entry.or(b_entry).map(|(t, _)| t.as_str()).unwrap_or_default();
+
+        let kept = if same(a_status, b_status) {
+            a_status
+        } else if same(a_status, base_status) {
+            plan.changes.push(change(id, title, Side::A, b_status));
+            b_status
+        } else if same(b_status, base_status) {
+            plan.changes.push(change(id, title, Side::B, a_status));
+            a_status
+        } else {
+            let winner = match policy {
+                ConflictPolicy::PreferA => Some(Side::A),
+                ConflictPolicy::PreferB => Some(Side::B),
+                ConflictPolicy::NewestWins => match (a_status, b_status) {
+                    (_, None) => Some(Side::A),
+                    (None, _) => Some(Side::B),
+                    (Some(a), Some(b)) if b.updated_at > a.updated_at => Some(Side::B),
+                    _ => Some(Side::A),
+                },
+                ConflictPolicy::Manual => None,
+            };
+            match winner {
+                Some(Side::A) => {
+                    plan.changes.push(change(id, title, Side::B, a_status));
+                    a_status
+                },
+                Some(Side::B) => {
+                    plan.changes.push(change(id, title, Side::A, b_status));
+                    b_status
+                },
+                None => {
+                    plan.conflicts.push(SyncConflict {
+                        id,
+                        title: title.to_string(),
+                        a: a_status.cloned(),
+                        b: b_status.cloned(),
+                        base: base_status.cloned(),
+                    });
+                    // Kept as it was, so it is still a conflict next time if it isn't resolved
+                    base_status
+                },
+            }
+        };
+        if let Some(status) = kept { plan.merged.entries.insert(id, status.clone()); }
+    }
+    plan
+}
+
+// The entries on the list, by id, with their title
+fn listed(list: &[MalAnimeData]) -> HashMap<u32, (&String, &ListStatus)> {
+    list.iter().filter_map(|a| Some((a.id, (&a.title, a.list_status.as_ref()?)))).collect()
+}
+
+fn same(a: Option<&ListStatus>, b: Option<&ListStatus>) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => anime_params(a) == anime_params(b),
+        _ => false,
+    }
+}
+
+fn change(id: u32, title: &str, target: Side, status: Option<&ListStatus>) -> SyncChange {
+    let action = match status {
+        Some(status) => SyncAction::Set(status.clone()),
+        None => SyncAction::Delete,
+    };
+    SyncChange { id, title: title.to_string(), target, action }
+}
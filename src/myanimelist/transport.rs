@@ -0,0 +1,170 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    error::Error,
+    fmt::Debug,
+    fs,
+    future::Future,
+    path::Path,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+use reqwest::{header::CONTENT_TYPE, Request, Response};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// The future a `Transport` returns, with the response or why the request couldn't be sent
+pub type TransportFuture = Pin<Box<dyn Future<Output = Result<Response, Box<dyn Error + Send + Sync>>> + Send>>;
+
+/// What a `MalClient` sends its requests with, after its cache, rate limits and retries.<br>
+/// `reqwest::Client` sends them to MyAnimeList, and `MockTransport` answers them in process, for tests.
+pub trait Transport: Debug + Send + Sync {
+    fn execute(&self, request: Request) -> TransportFuture;
+}
+
+impl Transport for reqwest::Client {
+    fn execute(&self, request: Request) -> TransportFuture {
+        let pending = reqwest::Client::execute(self, request);
+        Box::pin(async move { Ok(pending.await?) })
+    }
+}
+
+/// A request a `MockTransport` received
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockRequest {
+    pub method: String,
+    /// The URL's path, such as `/v2/anime/21`
+    pub path: String,
+    pub query: HashMap<String, String>,
+    /// The form sent by updates, such as `UpdateAnime`'s fields
+    pub form: HashMap<String, String>,
+}
+
+/// A `Transport` that answers requests from responses it was given, without a network, and keeps every request
+/// it received. Requests without a response get a `404`.<br>
+/// A response is given for a method and a path, which can have query parameters that the request must also have,
+/// such as `/v2/users/naginis_api/animelist?status=watching`. Routes are matched in the order they were added.
+/// When a route is given more than one response, they are answered in order, and the last one is kept answering.<br>
+/// Clones share the same responses and requests.
+/// ### Example usage:
+/// ```
+/// use mal_query::myanimelist::{self, client::MalClient, retrieval::get_anime, transport::MockTransport};
+/// async fn mock_example() {
+///     let mock = MockTransport::new();
+///     mock.respond("GET", "/v2/anime/21", 200, r#"{"id":21,"title":"One Piece","main_picture":{"medium":"","large":""}}"#);
+///     myanimelist::set_client(MalClient::new().transport(&mock));
+///     assert_eq!(get_anime(21).await.unwrap().title, "One Piece");
+///     assert_eq!(mock.requests()[0].path, "/v2/anime/21");
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockTransport {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    routes: Vec<MockRoute>,
+    requests: Vec<MockRequest>,
+}
+
+#[derive(Debug)]
+struct MockRoute {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    responses: VecDeque<(u16, String)>,
+}
+
+// One exchange of a recording, the body being the JSON MyAnimeList answered with
+#[derive(Deserialize)]
+struct Recorded {
+    method: String,
+    path: String,
+    #[serde(default = "ok")]
+    status: u16,
+    #[serde(default)]
+    body: Value,
+}
+
+fn ok() -> u16 {
+    200
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        MockTransport::default()
+    }
+    /// Reads a recording: a JSON array of `{ "method", "path", "status", "body" }` exchanges, with `status`
+    /// defaulting to 200, and `body` being the JSON response. Each exchange is added with `respond`
+    pub fn from_recording(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let mock = MockTransport::new();
+        let recorded: Vec<Recorded> = serde_json::from_str(&fs::read_to_string(path)?)?;
+        for exchange in recorded {
+            mock.respond(&exchange.method, &exchange.path, exchange.status, &exchange.body.to_string());
+        }
+        Ok(mock)
+    }
+    /// Adds a response for a method and path
+    pub fn respond(&self, method: &str, path: &str, status: u16, body: &str) -> &Self {
+        let (path, query) = match path.split_once('?') {
+            Some((path, query)) => (path, url::form_urlencoded::parse(query.as_bytes()).into_owned().collect()),
+            None => (path, Vec::new()),
+        };
+        let mut state = self.state.lock().unwrap();
+        let existing = state.routes.iter_mut().find(|r| r.method == method && r.path == path && r.query == query);
+        match existing {
+            Some(route) => route.responses.push_back((status, body.to_string())),
+            None => state.routes.push(MockRoute {
+                method: method.to_string(),
+                path: path.to_string(),
+                query,
+                responses: VecDeque::from([(status, body.to_string())]),
+            }),
+        }
+        self
+    }
+    /// Returns every request received so far, in order
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+    /// Returns the requests received for a method and path
+    pub fn requests_to(&self, method: &str, path: &str) -> Vec<MockRequest> {
+        self.requests().into_iter().filter(|r| r.method == method && r.path == path).collect()
+    }
+    fn answer(&self, request: &MockRequest) -> (u16, String) {
+        let mut state = self.state.lock().unwrap();
+        let route = state.routes.iter_mut().find(|r| {
+            r.method == request.method
+                && r.path == request.path
+                && r.query.iter().all(|(key, value)| request.query.get(key) == Some(value))
+        });
+        match route {
+            Some(route) if route.responses.len() > 1 => route.responses.pop_front().unwrap(),
+            Some(route) => route.responses[0].clone(),
+            None => (404, r#"{"error":"not_found","message":""}"#.to_string()),
+        }
+    }
+}
+
+impl Transport for MockTransport {
+    fn execute(&self, request: Request) -> TransportFuture {
+        let received = MockRequest {
+            method: request.method().to_string(),
+            path: request.url().path().to_string(),
+            query: request.url().query_pairs().into_owned().collect(),
+            form: request.body()
+                .and_then(|body| body.as_bytes())
+                .map(|bytes| url::form_urlencoded::parse(bytes).into_owned().collect())
+                .unwrap_or_default(),
+        };
+        let (status, body) = self.answer(&received);
+        self.state.lock().unwrap().requests.push(received);
+        Box::pin(async move {
+            let res = http::Response::builder()
+                .status(status)
+                .header(CONTENT_TYPE, "application/json")
+                .body(body)?;
+            Ok(Response::from(res))
+        })
+    }
+}
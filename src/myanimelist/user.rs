@@ -1,11 +1,34 @@
-use std::{error::Error, collections::HashMap};
+use std::{error::Error, collections::HashMap, sync::Arc};
+use chrono::Local;
+use reqwest::{Response, StatusCode};
 use serde::Deserialize;
-use super::{models::*, TOKEN};
+use tokio::{sync::Semaphore, task::JoinSet};
+use super::{models::*, builders::UserListBuilder, client::RetryPolicy, api_url, client, client_call, client_call_uncached, invalidate_anime, invalidate_manga, TOKEN};
 
-#[derive(Debug, Deserialize)]
+// Tag changes that depend on the user's current tags, resolved when the update is sent
+#[derive(Debug, Deserialize, Clone)]
+enum TagOp {
+    Add(Vec<String>),
+    Remove(Vec<String>),
+    Rename(String, String),
+}
+
+// Progress changes that depend on the entry's episode count, resolved when the update is sent
+#[derive(Debug, Deserialize, Clone)]
+enum ProgressOp {
+    IncrementEpisode,
+    MarkCompleted,
+    StartRewatch,
+}
+
+#[derive(Debug, Deserialize, Clone)]
 pub struct UpdateAnime {
     id: u32,
     params: HashMap<String, String>,
+    #[serde(default)]
+    tag_ops: Vec<TagOp>,
+    #[serde(default)]
+    progress_ops: Vec<ProgressOp>,
 }
 impl UpdateAnime {
     /// Takes an anime id, and initializes data for an update
@@ -13,6 +36,17 @@ impl UpdateAnime {
         UpdateAnime {
             id,
             params: HashMap::new(),
+            tag_ops: Vec::new(),
+            progress_ops: Vec::new(),
+        }
+    }
+    // Used when the params are already in MyAnimeList's form, such as from an import
+    pub(crate) fn from_params(id: u32, params: HashMap<String, String>) -> Self {
+        UpdateAnime {
+            id,
+            params,
+            tag_ops: Vec::new(),
+            progress_ops: Vec::new(),
         }
     }
     /// Takes an existing MalAnimeData variable, and initializes data for an update
@@ -20,6 +54,8 @@ impl UpdateAnime {
         UpdateAnime {
             id: mal_data.id,
             params: HashMap::new(),
+            tag_ops: Vec::new(),
+            progress_ops: Vec::new(),
         }
     }
     /// Calls the MyAnimeList API to update the user's anime entry based on the fields the other methods implement.<br>
@@ -38,36 +74,54 @@ impl UpdateAnime {
     /// }
     /// ```
     pub async fn update(&mut self) -> Result<ListStatus, Box<dyn Error>> {
-        let url = format!("https://api.myanimelist.net/v2/anime/{}/my_list_status", self.id);
-        let token = TOKEN.lock()?;
+        let token = TOKEN.lock()?.clone();
         if token.is_empty() { return Err("User is not logged in")? }
 
-        let client = reqwest::Client::new();
-        let res = client
-            .put(url)
-            .header("Authorization", format!("Bearer {}", *token))
-            .form(&self.params)
-            .send()
-            .await?;
+        let params = self.resolve_params().await?;
+        send_update(self.id, &params, &token).await
+    }
+    /// Fetches the user's current entry, and returns every field this update would change, without updating anything.<br>
+    /// Fields that already have the new value are left out, so an empty `Vec` means the update would change nothing.<br>
+    /// The user must first be logged in, for the app to have received a Token from MyAnimList.
+    /// ### Example usage:
+    /// ```
+    /// use mal_query::myanimelist::user::UpdateAnime;
+    /// async fn preview_example() {
+    ///     let changes = UpdateAnime::new(33)
+    ///         .update_score(10)
+    ///         .expect("Score should be between 0-10")
+    ///         .preview()
+    ///         .await
+    ///         .unwrap(); // Assuming successful
+    ///     for change in changes {
+    ///         println!("{}: {:?} -> {}", change.field, change.current, change.new);
+    ///     }
+    /// }
+    /// ```
+    pub async fn preview(&self) -> Result<Vec<FieldChange>, Box<dyn Error>> {
+        let token = TOKEN.lock()?.clone();
+        if token.is_empty() { return Err("User is not logged in")? }
 
-        if res.status().is_success() {
-            let data = res.text().await?;
-            let result: ListStatus = serde_json::from_str(&data)?;
-            Ok(result)
-        } else {
-            Err(format!("Request failed with status {:?}", res.status()))?
-        }
+        let current = current_entry(self.id).await?;
+        let params = self.resolve_with(Some(&current))?;
+        Ok(diff_params(&params, current.my_list_status.as_ref()))
+    }
+    /// Calls the MyAnimeList API to update the user's anime entry like `update()`, but only if a field would change.<br>
+    /// This avoids bumping the entry's `updated_at` on MyAnimeList when nothing is different.<br>
+    /// Returns `None` when the update was skipped, or the new `ListStatus` when it was sent.
+    pub async fn update_if_changed(&mut self) -> Result<Option<ListStatus>, Box<dyn Error>> {
+        let token = TOKEN.lock()?.clone();
+        if token.is_empty() { return Err("User is not logged in")? }
+
+        let current = current_entry(self.id).await?;
+        let params = self.resolve_with(Some(&current))?;
+        if diff_params(&params, current.my_list_status.as_ref()).is_empty() { return Ok(None) }
+
+        Ok(Some(send_update(self.id, &params, &token).await?))
     }
     /// Adds an update to the user's status to the Update
     pub fn update_status(&mut self, new_status: Status) -> &mut Self {
-        let s: &str = match new_status {
-            Status::Completed => "completed",
-            Status::Dropped => "dropped",
-            Status::OnHold => "on_hold",
-            Status::PlanToWatch => "plan_to_watch",
-            Status::Watching => "watching",
-        };
-        self.params.insert("status".to_string(), s.to_string());
+        self.params.insert("status".to_string(), status_param(&new_status).to_string());
         self
     }
     /// Adds an update to the user's is_rewatching to the Update
@@ -110,6 +164,48 @@ impl UpdateAnime {
         self.params.insert("tags".to_string(), new_tags.join(","));
         self
     }
+    /// Adds tags to the user's anime entry, keeping the tags it already has.<br>
+    /// The entry's current tags are fetched when `update()` is called.
+    pub fn add_tags(&mut self, tags: Vec<&str>) -> &mut Self {
+        self.tag_ops.push(TagOp::Add(tags.into_iter().map(String::from).collect()));
+        self
+    }
+    /// Removes tags from the user's anime entry, keeping the rest.<br>
+    /// The entry's current tags are fetched when `update()` is called.
+    pub fn remove_tags(&mut self, tags: Vec<&str>) -> &mut Self {
+        self.tag_ops.push(TagOp::Remove(tags.into_iter().map(String::from).collect()));
+        self
+    }
+    /// Renames one tag of the user's anime entry, if the entry has it.<br>
+    /// The entry's current tags are fetched when `update()` is called.
+    pub fn rename_tag(&mut self, old_tag: &str, new_tag: &str) -> &mut Self {
+        self.tag_ops.push(TagOp::Rename(old_tag.to_string(), new_tag.to_string()));
+        self
+    }
+    /// Adds one to the user's number of watched episodes.<br>
+    /// When the last episode is reached, the entry is marked completed with today as the finish date, the same as
+    /// `mark_completed()`. An entry that wasn't being watched is changed to `Status::Watching`.<br>
+    /// The entry's progress and episode count are fetched when `update()` is called, and the update fails if every
+    /// episode is already watched.
+    pub fn increment_episode(&mut self) -> &mut Self {
+        self.progress_ops.push(ProgressOp::IncrementEpisode);
+        self
+    }
+    /// Marks the user's anime entry as completed, with every episode watched and today as the finish date
+    /// (unless the entry already has one).<br>
+    /// If the entry was being rewatched, the rewatch is finished instead, and the number of times rewatched goes up by one.<br>
+    /// The entry's episode count is fetched when `update()` is called, and the update fails if it is not known yet.
+    pub fn mark_completed(&mut self) -> &mut Self {
+        self.progress_ops.push(ProgressOp::MarkCompleted);
+        self
+    }
+    /// Starts a rewatch of a completed anime, setting `is_rewatching` and the watched episodes back to 0.<br>
+    /// The number of times rewatched goes up when the rewatch is completed, with `increment_episode()` or `mark_completed()`.<br>
+    /// The entry is fetched when `update()` is called, and the update fails if it is not completed.
+    pub fn start_rewatch(&mut self) -> &mut Self {
+        self.progress_ops.push(ProgressOp::StartRewatch);
+        self
+    }
     /// Overrides the comment of the user's anime entry
     pub fn update_comments(&mut self, new_comments: &str) -> &mut Self {
         self.params.insert("comments".to_string(), new_comments.to_string());
@@ -133,22 +229,581 @@ impl UpdateAnime {
     }
 }
 
+impl UpdateAnime {
+    // The params that will be sent, after the tag changes are merged into the entry's current tags
+    async fn resolve_params(&self) -> Result<HashMap<String, String>, Box<dyn Error>> {
+        let current = self.fetch_if_needed().await?;
+        self.resolve_with(current.as_ref())
+    }
+    // The current entry is only needed for progress changes, or when tags are merged into the ones the user has now
+    async fn fetch_if_needed(&self) -> Result<Option<CurrentEntry>, Box<dyn Error>> {
+        let merges_tags = !self.tag_ops.is_empty() && !self.params.contains_key("tags");
+        match merges_tags || !self.progress_ops.is_empty() {
+            true => Ok(Some(current_entry(self.id).await?)),
+            false => Ok(None),
+        }
+    }
+    // Same as `resolve_params`, with the user's current entry already fetched
+    fn resolve_with(&self, current: Option<&CurrentEntry>) -> Result<HashMap<String, String>, Box<dyn Error>> {
+        let mut params = self.params.clone();
+        let status = current.and_then(|entry| entry.my_list_status.as_ref());
+
+        if !self.tag_ops.is_empty() {
+            // `update_tags` replaces the starting tags, otherwise it is what the user has now
+            let tags: Vec<String> = match params.get("tags") {
+                Some(tags) => tags.split(',').filter(|t| !t.is_empty()).map(String::from).collect(),
+                None => status.and_then(|status| status.tags.clone()).unwrap_or_default(),
+            };
+            params.insert("tags".to_string(), merge_tags(tags, &self.tag_ops).join(","));
+        }
+
+        if !self.progress_ops.is_empty() {
+            // MyAnimeList uses 0 episodes for anime that don't have a known count yet
+            let total = current.and_then(|entry| entry.num_episodes).unwrap_or(0);
+            let progress = Progress { status, total, today: Local::now().format("%Y-%m-%d").to_string() };
+            for op in &self.progress_ops {
+                progress.apply(op, &mut params)?;
+            }
+        }
+        Ok(params)
+    }
+}
+
+// The user's entry and the anime's episode count, which progress changes are checked against.
+// Values already set on the update (like `update_num_watched_episodes`) are used before the entry's values
+struct Progress<'a> {
+    status: Option<&'a ListStatus>,
+    total: u32,
+    today: String,
+}
+impl Progress<'_> {
+    fn apply(&self, op: &ProgressOp, params: &mut HashMap<String, String>) -> Result<(), Box<dyn Error>> {
+        let watched: u32 = match params.get("num_watched_episodes") {
+            Some(watched) => watched.parse()?,
+            None => self.status.map(|s| s.num_episodes_watched).unwrap_or(0),
+        };
+        if self.total > 0 && watched > self.total {
+            return Err(format!("Watched episodes ({watched}) is more than the anime's {} episodes", self.total))?
+        }
+        let rewatching = match params.get("is_rewatching") {
+            Some(rewatching) => rewatching == "true",
+            None => self.status.is_some_and(|s| s.is_rewatching),
+        };
+        let list_status: Option<String> = params
+            .get("status")
+            .cloned()
+            .or_else(|| self.status.map(|s| status_param(&s.status).to_string()));
+
+        match op {
+            ProgressOp::IncrementEpisode => {
+                if self.total > 0 && watched == self.total {
+                    return Err(format!("All {} episodes are already watched", self.total))?
+                }
+                params.insert("num_watched_episodes".to_string(), (watched + 1).to_string());
+                if watched + 1 == self.total {
+                    self.complete(rewatching, params);
+                } else if !rewatching && list_status.as_deref() != Some("watching") {
+                    params.insert("status".to_string(), "watching".to_string());
+                    if !params.contains_key("start_date") && self.status.and_then(|s| s.start_date.as_ref()).is_none() {
+                        params.insert("start_date".to_string(), self.today.clone());
+                    }
+                }
+            },
+            ProgressOp::MarkCompleted => {
+                if self.total == 0 { return Err("The anime's episode count is not known yet")? }
+                self.complete(rewatching, params);
+            },
+            ProgressOp::StartRewatch => {
+                if list_status.as_deref() != Some("completed") { return Err("Only completed anime can be rewatched")? }
+                if rewatching { return Err("The anime is already being rewatched")? }
+                params.insert("is_rewatching".to_string(), "true".to_string());
+                params.insert("num_watched_episodes".to_string(), "0".to_string());
+            },
+        }
+        Ok(())
+    }
+    // Completes the entry, or finishes the rewatch of an entry that was already completed
+    fn complete(&self, rewatching: bool, params: &mut HashMap<String, String>) {
+        params.insert("status".to_string(), "completed".to_string());
+        params.insert("num_watched_episodes".to_string(), self.total.to_string());
+        if rewatching {
+            let times = match params.get("num_times_rewatched") {
+                Some(times) => times.parse().unwrap_or(0),
+                None => self.status.and_then(|s| s.num_times_rewatched).unwrap_or(0),
+            };
+            params.insert("is_rewatching".to_string(), "false".to_string());
+            params.insert("num_times_rewatched".to_string(), (times + 1).to_string());
+        } else if !params.contains_key("finish_date") && self.status.and_then(|s| s.finish_date.as_ref()).is_none() {
+            params.insert("finish_date".to_string(), self.today.clone());
+        }
+    }
+}
+
+/// One field that an `UpdateAnime` would change on the user's entry, from `UpdateAnime::preview()`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    /// The MyAnimeList parameter name, such as `score` or `num_watched_episodes`
+    pub field: String,
+    /// The entry's value now, or `None` if the entry doesn't have one
+    pub current: Option<String>,
+    /// The value the update would send
+    pub new: String,
+}
+
+// Compares pending params against the user's entry, using the same text form the params are sent in
+fn diff_params(params: &HashMap<String, String>, current: Option<&ListStatus>) -> Vec<FieldChange> {
+    let mut changes: Vec<FieldChange> = params
+        .iter()
+        .filter_map(|(field, new)| {
+            let now = current.and_then(|status| current_param(status, field));
+            match now.as_deref() == Some(new.as_str()) {
+                true => None,
+                false => Some(FieldChange { field: field.clone(), current: now, new: new.clone() }),
+            }
+        })
+        .collect();
+    changes.sort_by(|a, b| a.field.cmp(&b.field));
+    changes
+}
+
+// The value of one MyAnimeList parameter in the user's entry
+fn current_param(status: &ListStatus, field: &str) -> Option<String> {
+    match field {
+        "status" => Some(status_param(&status.status).to_string()),
+        "is_rewatching" => Some(status.is_rewatching.to_string()),
+        "score" => Some(status.score.to_string()),
+        "num_watched_episodes" => Some(status.num_episodes_watched.to_string()),
+        "priority" => status.priority.map(|p| p.to_string()),
+        "num_times_rewatched" => status.num_times_rewatched.map(|n| n.to_string()),
+        "rewatch_value" => status.rewatch_value.map(|r| r.to_string()),
+        // MyAnimeList leaves out empty tags and comments
+        "tags" => Some(status.tags.as_ref().map(|tags| tags.join(",")).unwrap_or_default()),
+        "comments" => Some(status.comments.clone().unwrap_or_default()),
+        "start_date" => status.start_date.clone(),
+        "finish_date" => status.finish_date.clone(),
+        _ => None,
+    }
+}
+
+pub(crate) fn status_param(status: &Status) -> &'static str {
+    match status {
+        Status::Completed => "completed",
+        Status::Dropped => "dropped",
+        Status::OnHold => "on_hold",
+        Status::PlanToWatch => "plan_to_watch",
+        Status::Watching => "watching",
+    }
+}
+
+// The parts of an anime's details needed to compute changes against the user's entry
+#[derive(Debug, Deserialize)]
+struct CurrentEntry {
+    num_episodes: Option<u32>,
+    my_list_status: Option<ListStatus>,
+}
+
+// Gets the logged in user's current entry for an anime, never from the cache, as it may have been edited elsewhere
+async fn current_entry(id: u32) -> Result<CurrentEntry, Box<dyn Error>> {
+    let url = api_url(&format!("/anime/{id}?fields=num_episodes,my_list_status{{start_date,finish_date,priority,num_times_rewatched,rewatch_value,tags,comments}}"));
+    let res = client_call_uncached(&url).await?;
+
+    if res.status().is_success() {
+        let data: CurrentEntry = res.json().await?;
+        Ok(data)
+    } else {
+        Err(format!("Request failed with status {:?}", res.status()))?
+    }
+}
+
+// Applies tag changes in order, without creating duplicate tags
+fn merge_tags(mut tags: Vec<String>, ops: &[TagOp]) -> Vec<String> {
+    for op in ops {
+        match op {
+            TagOp::Add(new_tags) => {
+                for tag in new_tags {
+                    if !tags.contains(tag) { tags.push(tag.clone()) }
+                }
+            },
+            TagOp::Remove(old_tags) => tags.retain(|tag| !old_tags.contains(tag)),
+            TagOp::Rename(old_tag, new_tag) => {
+                if let Some(index) = tags.iter().position(|tag| tag == old_tag) {
+                    if tags.contains(new_tag) {
+                        tags.remove(index);
+                    } else {
+                        tags[index] = new_tag.clone();
+                    }
+                }
+            },
+        }
+    }
+    tags
+}
+
+/// Renames a tag on every entry of the logged in user's anime list that has it.<br>
+/// The whole list is read, and only the entries with the tag are updated, using a `BulkUpdate`.<br>
+/// User MUST be loggin in with the `login` function, and have a token generated for them for this to be used.
+pub async fn rename_tag_everywhere(old_tag: &str, new_tag: &str) -> Result<BulkReport, Box<dyn Error>> {
+    let list = UserListBuilder::new("@me")
+        .include_list_status()
+        .run_all()
+        .await?;
+
+    let entries = list.data
+        .iter()
+        .filter_map(|anime| {
+            let tags = anime.list_status.as_ref()?.tags.as_ref()?;
+            if !tags.iter().any(|tag| tag == old_tag) { return None }
+            let renamed = merge_tags(tags.clone(), &[TagOp::Rename(old_tag.to_string(), new_tag.to_string())]);
+            let mut entry = UpdateAnime::new(anime.id);
+            entry.update_tags(renamed.iter().map(String::as_str).collect());
+            Some(entry)
+        })
+        .collect();
+
+    BulkUpdate::from_entries(entries).run().await
+}
+
+/// Gets the profile of the logged in user, such as to check who is logged in.<br>
+/// User MUST be loggin in with the `login` function, and have a token generated for them for this to be used.<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing a `MalUser`
+pub async fn get_my_user() -> Result<MalUser, Box<dyn Error>> {
+    let token = TOKEN.lock()?.clone();
+    if token.is_empty() { return Err("User is not logged in")? }
+
+    let res = client_call(&api_url("/users/@me")).await?;
+    match res.status().is_success() {
+        true => Ok(res.json().await?),
+        false => Err(format!("Request failed with status {:?}", res.status()))?,
+    }
+}
+
 /// Deletes an anime of the corresponding ID from the User's MyAnimeList database.<br>
 /// User MUST be loggin in with the `login` function, and have a token generated for them for this to be used.
 pub async fn delete_anime(id: u32) -> Result<(), Box<dyn Error>> {
-    let url = format!("https://api.myanimelist.net/v2/anime/{id}/my_list_status");
-    let token = TOKEN.lock()?;
+    let url = api_url(&format!("/anime/{id}/my_list_status"));
+    let token = TOKEN.lock()?.clone();
     if token.is_empty() { return Err("User is not logged in")? }
 
-    let client = reqwest::Client::new();
-    let res = client
-        .delete(url)
-        .header("Authorization", format!("Bearer {}", *token))
-        .send()
+    let res = client()
+        .send(|http| http.delete(&url).header("Authorization", format!("Bearer {token}")))
         .await?;
+    invalidate_anime(id);
 
     match res.status().is_success() {
         true => Ok(()),
         false => Err(format!("Request failed with status {:?}", res.status()))?,
     }
-}
\ No newline at end of file
+}
+
+// Sends the PUT for one anime entry, retried with the policy, and drops the cached responses it changes.
+// Shared by `UpdateAnime` and `BulkUpdate`
+async fn put_list_status(id: u32, params: &HashMap<String, String>, token: &str, policy: &RetryPolicy) -> Result<Response, Box<dyn Error + Send + Sync>> {
+    let url = api_url(&format!("/anime/{id}/my_list_status"));
+    let res = client()
+        .send_with(policy, |http| http.put(&url).header("Authorization", format!("Bearer {token}")).form(params))
+        .await;
+    invalidate_anime(id);
+    res
+}
+
+// Sends the update, and reads the user's new list status
+async fn send_update(id: u32, params: &HashMap<String, String>, token: &str) -> Result<ListStatus, Box<dyn Error>> {
+    let res = put_list_status(id, params, token, client().retry_policy()).await.map_err(|e| e as Box<dyn Error>)?;
+
+    if res.status().is_success() {
+        let data = res.text().await?;
+        let result: ListStatus = serde_json::from_str(&data)?;
+        Ok(result)
+    } else {
+        Err(format!("Request failed with status {:?}", res.status()))?
+    }
+}
+
+// ---------- Manga ----------
+
+/// Updates the user's entry for a manga, the manga version of `UpdateAnime`
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdateManga {
+    id: u32,
+    params: HashMap<String, String>,
+}
+impl UpdateManga {
+    /// Takes a manga id, and initializes data for an update
+    pub fn new(id: u32) -> Self {
+        UpdateManga {
+            id,
+            params: HashMap::new(),
+        }
+    }
+    // Used when the params are already in MyAnimeList's form, such as from an import
+    pub(crate) fn from_params(id: u32, params: HashMap<String, String>) -> Self {
+        UpdateManga { id, params }
+    }
+    /// Calls the MyAnimeList API to update the user's manga entry based on the fields the other methods implement.<br>
+    /// The user must first be logged in, for the app to have received a Token from MyAnimList.<br>
+    /// Example usage:
+    /// ```
+    /// use mal_query::myanimelist::{user::UpdateManga, models::ReadingStatus};
+    /// async fn update_manga_example() {
+    ///     let status = UpdateManga::new(2)
+    ///         .update_status(ReadingStatus::Reading)
+    ///         .update_num_chapters_read(364)
+    ///         .update()
+    ///         .await
+    ///         .unwrap(); // Assuming successful
+    ///     assert_eq!(status.num_chapters_read, 364);
+    /// }
+    /// ```
+    pub async fn update(&mut self) -> Result<MangaListStatus, Box<dyn Error>> {
+        let token = TOKEN.lock()?.clone();
+        if token.is_empty() { return Err("User is not logged in")? }
+
+        let url = api_url(&format!("/manga/{}/my_list_status", self.id));
+        let res = client()
+            .send(|http| http.put(&url).header("Authorization", format!("Bearer {token}")).form(&self.params))
+            .await?;
+        invalidate_manga(self.id);
+
+        if res.status().is_success() {
+            let data = res.text().await?;
+            let result: MangaListStatus = serde_json::from_str(&data)?;
+            Ok(result)
+        } else {
+            Err(format!("Request failed with status {:?}", res.status()))?
+        }
+    }
+    /// Adds an update to the user's status to the Update
+    pub fn update_status(&mut self, new_status: ReadingStatus) -> &mut Self {
+        self.params.insert("status".to_string(), reading_status_param(&new_status).to_string());
+        self
+    }
+    /// Adds an update to the user's is_rereading to the Update
+    pub fn update_is_rereading(&mut self, new_is_rereading: bool) -> &mut Self {
+        self.params.insert("is_rereading".to_string(), new_is_rereading.to_string());
+        self
+    }
+    /// Adds an update to the user's score to the Update
+    pub fn update_score(&mut self, new_score: u32) -> Result<&mut Self, Box<dyn Error>> {
+        if new_score > 10 { return Err("Score has to be 0-10")? }
+        self.params.insert("score".to_string(), new_score.to_string());
+        Ok(self)
+    }
+    /// Adds an update to the user's number of volumes read to the Update
+    pub fn update_num_volumes_read(&mut self, new_num_volumes_read: u32) -> &mut Self {
+        self.params.insert("num_volumes_read".to_string(), new_num_volumes_read.to_string());
+        self
+    }
+    /// Adds an update to the user's number of chapters read to the Update
+    pub fn update_num_chapters_read(&mut self, new_num_chapters_read: u32) -> &mut Self {
+        self.params.insert("num_chapters_read".to_string(), new_num_chapters_read.to_string());
+        self
+    }
+    /// Adds an update to the user's personal priority to the Update
+    pub fn update_priority(&mut self, new_priority: u32) -> Result<&mut Self, Box<dyn Error>> {
+        if new_priority > 2 { return Err("Priority has to be 0-2")? }
+        self.params.insert("priority".to_string(), new_priority.to_string());
+        Ok(self)
+    }
+    /// Adds an update to the user's number of times reread to the Update
+    pub fn update_num_times_reread(&mut self, new_num_times_reread: u32) -> &mut Self {
+        self.params.insert("num_times_reread".to_string(), new_num_times_reread.to_string());
+        self
+    }
+    /// Adds an update to the user's reread value to the Update
+    pub fn update_reread_value(&mut self, new_reread_value: u32) -> Result<&mut Self, Box<dyn Error>> {
+        if new_reread_value > 5 { return Err("reread_value has to be 0-5")? }
+        self.params.insert("reread_value".to_string(), new_reread_value.to_string());
+        Ok(self)
+    }
+    /// Overrides the tags of the user's manga entry<br>
+    /// ### WARNING:<br> 
+    /// This will change all tags into ONLY what the params are
+    pub fn update_tags(&mut self, new_tags: Vec<&str>) -> &mut Self {
+        self.params.insert("tags".to_string(), new_tags.join(","));
+        self
+    }
+    /// Overrides the comment of the user's manga entry
+    pub fn update_comments(&mut self, new_comments: &str) -> &mut Self {
+        self.params.insert("comments".to_string(), new_comments.to_string());
+        self
+    }
+    /// Changes the Starting Date of the user's entry
+    pub fn update_start_date(&mut self, new_year: u32, new_month: u32, new_day: u32) -> &mut Self {
+        self.params.insert("start_date".to_string(), format!("{:04}-{:02}-{:02}", new_year, new_month, new_day));
+        self
+    }
+    /// Changes the Finish Date of the user's entry
+    pub fn update_finish_date(&mut self, new_year: u32, new_month: u32, new_day: u32) -> &mut Self {
+        self.params.insert("finish_date".to_string(), format!("{:04}-{:02}-{:02}", new_year, new_month, new_day));
+        self
+    }
+}
+
+pub(crate) fn reading_status_param(status: &ReadingStatus) -> &'static str {
+    match status {
+        ReadingStatus::Completed => "completed",
+        ReadingStatus::Dropped => "dropped",
+        ReadingStatus::OnHold => "on_hold",
+        ReadingStatus::PlanToRead => "plan_to_read",
+        ReadingStatus::Reading => "reading",
+    }
+}
+
+// ---------- Bulk Updates ----------
+
+/// The result of one anime entry within a `BulkUpdate`
+#[derive(Debug)]
+pub enum BulkOutcome {
+    /// The entry was updated, containing the user's new list status
+    Success(ListStatus),
+    /// MyAnimeList rejected the change set for this ID, such as an ID that does not exist
+    ValidationFailure(String),
+    /// The request could not be completed, even after retrying
+    HttpFailure(String),
+}
+
+/// Per-ID report returned by `BulkUpdate::run()`, in the same order the IDs were given
+#[derive(Debug, Default)]
+pub struct BulkReport {
+    pub results: Vec<(u32, BulkOutcome)>,
+}
+impl BulkReport {
+    /// Returns the IDs that were updated, with their new list status
+    pub fn successes(&self) -> Vec<(u32, &ListStatus)> {
+        self.results.iter().filter_map(|(id, outcome)| match outcome {
+            BulkOutcome::Success(status) => Some((*id, status)),
+            _ => None,
+        }).collect()
+    }
+    /// Returns the IDs that MyAnimeList rejected, with the reason
+    pub fn validation_failures(&self) -> Vec<(u32, &String)> {
+        self.results.iter().filter_map(|(id, outcome)| match outcome {
+            BulkOutcome::ValidationFailure(e) => Some((*id, e)),
+            _ => None,
+        }).collect()
+    }
+    /// Returns the IDs whose requests failed, with the last error
+    pub fn http_failures(&self) -> Vec<(u32, &String)> {
+        self.results.iter().filter_map(|(id, outcome)| match outcome {
+            BulkOutcome::HttpFailure(e) => Some((*id, e)),
+            _ => None,
+        }).collect()
+    }
+    /// Returns true if every ID was updated
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|(_, outcome)| matches!(outcome, BulkOutcome::Success(_)))
+    }
+}
+
+/// Applies the same `UpdateAnime` change set to many anime IDs.<br>
+/// Requests are sent with a bounded number running at once, and requests that fail from rate limits
+/// (429), server errors (5xx) or connection problems are retried with an exponential backoff.
+pub struct BulkUpdate {
+    entries: Vec<UpdateAnime>,
+    concurrency: usize,
+    retries: u32,
+}
+impl BulkUpdate {
+    /// Takes a list of anime IDs and an `UpdateAnime` to use as a template.<br>
+    /// Only the fields added to the template are used, the template's own ID is ignored.
+    /// Tag changes like `add_tags` are merged with each entry's own tags.
+    pub fn new(ids: Vec<u32>, template: &UpdateAnime) -> Self {
+        BulkUpdate::from_entries(ids.into_iter().map(|id| UpdateAnime { id, ..template.clone() }).collect())
+    }
+    // Each entry has its own change set, used when the changes differ between IDs
+    fn from_entries(entries: Vec<UpdateAnime>) -> Self {
+        BulkUpdate {
+            entries,
+            concurrency: 4,
+            retries: 3,
+        }
+    }
+    /// Takes every anime of an existing MalAnimeSearch, and an `UpdateAnime` to use as a template
+    pub fn from_malanimesearch(search: &MalAnimeSearch, template: &UpdateAnime) -> Self {
+        BulkUpdate::new(search.data.iter().map(|anime| anime.id).collect(), template)
+    }
+    /// Sets how many requests can be sent at the same time. Defaults to 4
+    pub fn concurrency(&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+    /// Sets how many times a failed request will be retried, instead of the client's `RetryPolicy`. Defaults to 3
+    pub fn retries(&mut self, retries: u32) -> &mut Self {
+        self.retries = retries;
+        self
+    }
+    /// Calls the MyAnimeList API to update every ID with the template's fields.<br>
+    /// The user must first be logged in, for the app to have received a Token from MyAnimList.<br>
+    /// A failing ID does not stop the others, each ID's result is in the returned `BulkReport`.
+    /// ### Example usage:
+    /// ```
+    /// use mal_query::myanimelist::{user::{UpdateAnime, BulkUpdate}, models::Status};
+    /// async fn bulk_example() {
+    ///     let report = BulkUpdate::new(vec![1, 5, 6], UpdateAnime::new(0).update_status(Status::Dropped))
+    ///         .concurrency(2)
+    ///         .run()
+    ///         .await
+    ///         .unwrap(); // Assuming the user is logged in
+    ///     assert!(report.is_success());
+    /// }
+    /// ```
+    pub async fn run(&self) -> Result<BulkReport, Box<dyn Error>> {
+        let token = TOKEN.lock()?.clone();
+        if token.is_empty() { return Err("User is not logged in")? }
+
+        let semaphore = Arc::new(Semaphore::new(self.concurrency));
+        let mut tasks = JoinSet::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            let semaphore = semaphore.clone();
+            let entry = entry.clone();
+            let token = token.clone();
+            let retries = self.retries;
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                // Errors are made into Strings first, as Box<dyn Error> can't be sent between threads
+                let fetched = entry.fetch_if_needed().await.map_err(|e| e.to_string());
+                let resolved = match fetched {
+                    Ok(current) => entry.resolve_with(current.as_ref()).map_err(|e| BulkOutcome::ValidationFailure(e.to_string())),
+                    Err(e) => Err(BulkOutcome::HttpFailure(e)),
+                };
+                let outcome = match resolved {
+                    Ok(params) => put_with_retry(entry.id, &params, &token, retries).await,
+                    Err(outcome) => outcome,
+                };
+                (index, entry.id, outcome)
+            });
+        }
+
+        let mut results = Vec::with_capacity(self.entries.len());
+        while let Some(joined) = tasks.join_next().await {
+            results.push(joined?);
+        }
+        results.sort_by_key(|(index, _, _)| *index);
+
+        Ok(BulkReport {
+            results: results.into_iter().map(|(_, id, outcome)| (id, outcome)).collect(),
+        })
+    }
+}
+
+// Sends one entry of a BulkUpdate, retried with the client's policy, for up to `retries` retries
+async fn put_with_retry(id: u32, params: &HashMap<String, String>, token: &str, retries: u32) -> BulkOutcome {
+    let policy = client().retry_policy().clone().max_attempts(retries + 1).clone();
+    let res = match put_list_status(id, params, token, &policy).await {
+        Ok(res) => res,
+        Err(e) => return BulkOutcome::HttpFailure(e.to_string()),
+    };
+    let status = res.status();
+    if status.is_success() {
+        return match res.json::<ListStatus>().await {
+            Ok(data) => BulkOutcome::Success(data),
+            Err(e) => BulkOutcome::HttpFailure(e.to_string()),
+        };
+    }
+    let body = res.text().await.unwrap_or_default();
+    match status {
+        StatusCode::BAD_REQUEST | StatusCode::NOT_FOUND => BulkOutcome::ValidationFailure(body),
+        _ => BulkOutcome::HttpFailure(format!("Request failed with status {status:?}")),
+    }
+}
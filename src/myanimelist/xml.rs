@@ -0,0 +1,436 @@
+use std::{collections::HashMap, error::Error};
+use quick_xml::{events::Event, Reader};
+use super::{builders::{AddFields, UserListBuilder, UserMangaListBuilder}, models::*};
+
+/// The lists read from a MyAnimeList XML export by `parse_xml`.<br>
+/// An export only has anime or only has manga, but both are read, so files can be combined.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct XmlList {
+    /// The `user_name` in the export's `<myinfo>`
+    pub username: String,
+    pub anime: Vec<XmlAnime>,
+    pub manga: Vec<XmlManga>,
+}
+
+/// One `<anime>` of an export. The export has no `updated_at`, so it is left empty in the `list_status`
+#[derive(Debug, Clone, PartialEq)]
+pub struct XmlAnime {
+    pub id: u32,
+    pub title: String,
+    /// `0` when the episode count isn't known
+    pub num_episodes: u32,
+    pub list_status: ListStatus,
+}
+
+/// One `<manga>` of an export. The export has no `updated_at`, so it is left empty in the `list_status`
+#[derive(Debug, Clone, PartialEq)]
+pub struct XmlManga {
+    pub id: u32,
+    pub title: String,
+    /// `0` when the volume count isn't known
+    pub num_volumes: u32,
+    /// `0` when the chapter count isn't known
+    pub num_chapters: u32,
+    pub list_status: MangaListStatus,
+}
+
+/// Reads a user's whole anime list, and creates the XML MyAnimeList exports lists as, which MyAnimeList's own
+/// import (and most other trackers) accept.<br>
+/// The user searched must not be private, unless it is the logged in user (`"@me"`).<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing the XML text
+/// ### Example usage:
+/// ```
+/// use mal_query::myanimelist::xml::export_animelist;
+/// async fn export_example() {
+///     let xml = export_animelist("naginis_api").await.unwrap();
+///     std::fs::write("animelist.xml", xml).unwrap();
+/// }
+/// ```
+pub async fn export_animelist(username: &str) -> Result<String, Box<dyn Error>> {
+    let list = UserListBuilder::new(username)
+        .include_list_status()
+        .add_media_type()
+        .add_num_episodes()
+        .run_all()
+        .await?;
+    Ok(anime_list_xml(&list.data, username))
+}
+
+/// Reads a user's whole manga list, and creates the XML MyAnimeList exports lists as, like `export_animelist`.<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with a success containing the XML text
+pub async fn export_mangalist(username: &str) -> Result<String, Box<dyn Error>> {
+    let list = UserMangaListBuilder::new(username)
+        .include_list_status()
+        .add_num_volumes()
+        .add_num_chapters()
+        .run_all()
+        .await?;
+    Ok(manga_list_xml(&list.data, username))
+}
+
+/// Creates MyAnimeList's anime list export XML, with an `<anime>` for each anime that has a `list_status`.
+/// Others are left out.<br>
+/// The anime should have their `list_status`, and their `media_type` and `num_episodes` for the series details,
+/// such as from `UserListBuilder` with `.include_list_status().add_media_type().add_num_episodes()`.
+pub fn anime_list_xml(anime: &[MalAnimeData], username: &str) -> String {
+    let listed: Vec<(&MalAnimeData, &ListStatus)> = anime
+        .iter()
+        .filter_map(|a| Some((a, a.list_status.as_ref()?)))
+        .collect();
+    let count = |status: Status| listed.iter().filter(|(_, s)| s.status == status).count().to_string();
+
+    let mut xml = Xml::new();
+    xml.open("myinfo");
+    xml.tag("user_id", "0");
+    xml.cdata("user_name", username);
+    xml.tag("user_export_type", "1");
+    xml.tag("user_total_anime", &listed.len().to_string());
+    xml.tag("user_total_watching", &count(Status::Watching));
+    xml.tag("user_total_completed", &count(Status::Completed));
+    xml.tag("user_total_onhold", &count(Status::OnHold));
+    xml.tag("user_total_dropped", &count(Status::Dropped));
+    xml.tag("user_total_plantowatch", &count(Status::PlanToWatch));
+    xml.close("myinfo");
+
+    for (anime, status) in listed {
+        xml.open("anime");
+        xml.tag("series_animedb_id", &anime.id.to_string());
+        xml.cdata("series_title", &anime.title);
+        xml.tag("series_type", anime_type_name(anime.media_type));
+        xml.tag("series_episodes", &anime.num_episodes.unwrap_or(0).to_string());
+        xml.tag("my_id", "0");
+        xml.tag("my_watched_episodes", &status.num_episodes_watched.to_string());
+        xml.tag("my_start_date", &export_date(status.start_date.as_deref()));
+        xml.tag("my_finish_date", &export_date(status.finish_date.as_deref()));
+        xml.tag("my_rated", "");
+        xml.tag("my_score", &status.score.to_string());
+        xml.tag("my_storage", "");
+        xml.tag("my_storage_value", "0.00");
+        xml.tag("my_status", anime_status_name(status.status));
+        xml.cdata("my_comments", status.comments.as_deref().unwrap_or(""));
+        xml.tag("my_times_watched", &status.num_times_rewatched.unwrap_or(0).to_string());
+        xml.tag("my_rewatch_value", &repeat_value(status.rewatch_value));
+        xml.tag("my_priority", priority_name(status.priority));
+        xml.cdata("my_tags", &status.tags.as_deref().unwrap_or_default().join(", "));
+        xml.tag("my_rewatching", if status.is_rewatching { "1" } else { "0" });
+        xml.tag("my_rewatching_ep", "0");
+        xml.tag("my_discuss", "1");
+        xml.tag("my_sns", "default");
+        xml.tag("update_on_import", "1");
+        xml.close("anime");
+    }
+    xml.finish()
+}
+
+/// Creates MyAnimeList's manga list export XML, with a `<manga>` for each manga that has a `list_status`.
+/// Others are left out.<br>
+/// The manga should have their `list_status`, and their `num_volumes` and `num_chapters`, such as from
+/// `UserMangaListBuilder` with `.include_list_status().add_num_volumes().add_num_chapters()`.
+pub fn manga_list_xml(manga: &[MalMangaData], username: &str) -> String {
+    let listed: Vec<(&MalMangaData, &MangaListStatus)> = manga
+        .iter()
+        .filter_map(|m| Some((m, m.list_status.as_ref()?)))
+        .collect();
+    let count = |status: ReadingStatus| listed.iter().filter(|(_, s)| s.status == status).count().to_string();
+
+    let mut xml = Xml::new();
+    xml.open("myinfo");
+    xml.tag("user_id", "0");
+    xml.cdata("user_name", username);
+    xml.tag("user_export_type", "2");
+    xml.tag("user_total_manga", &listed.len().to_string());
+    xml.tag("user_total_reading", &count(ReadingStatus::Reading));
+    xml.tag("user_total_completed", &count(ReadingStatus::Completed));
+    xml.tag("user_total_onhold", &count(ReadingStatus::OnHold));
+    xml.tag("user_total_dropped", &count(ReadingStatus::Dropped));
+    xml.tag("user_total_plantoread", &count(ReadingStatus::PlanToRead));
+    xml.close("myinfo");
+
+    for (manga, status) in listed {
+        xml.open("manga");
+        xml.tag("manga_mangadb_id", &manga.id.to_string());
+        xml.cdata("manga_title", &manga.title);
+        xml.tag("manga_volumes", &manga.num_volumes.unwrap_or(0).to_string());
+        xml.tag("manga_chapters", &manga.num_chapters.unwrap_or(0).to_string());
+        xml.tag("my_id", "0");
+        xml.tag("my_read_volumes", &status.num_volumes_read.to_string());
+        xml.tag("my_read_chapters", &status.num_chapters_read.to_string());
+        xml.tag("my_start_date", &export_date(status.start_date.as_deref()));
+        xml.tag("my_finish_date", &export_date(status.finish_date.as_deref()));
+        xml.cdata("my_scanalation_group", "");
+        xml.tag("my_score", &status.score.to_string());
+        xml.tag("my_storage", "");
+        xml.tag("my_retail_volumes", "0");
+        xml.tag("my_status", manga_status_name(status.status));
+        xml.cdata("my_comments", status.comments.as_deref().unwrap_or(""));
+        xml.tag("my_times_read", &status.num_times_reread.unwrap_or(0).to_string());
+        xml.cdata("my_tags", &status.tags.as_deref().unwrap_or_default().join(", "));
+        xml.tag("my_priority", priority_name(status.priority));
+        xml.tag("my_reread_value", &repeat_value(status.reread_value));
+        xml.tag("my_rereading", if status.is_rereading { "YES" } else { "NO" });
+        xml.tag("my_discuss", "YES");
+        xml.tag("my_sns", "default");
+        xml.tag("update_on_import", "1");
+        xml.close("manga");
+    }
+    xml.finish()
+}
+
+/// Reads a MyAnimeList XML export, such as from `anime_list_xml` or MyAnimeList's own export, into typed entries.<br>
+/// Both the export's names (like `On-Hold` and `HIGH`) and numbers are accepted for statuses, priorities and
+/// rewatch values, and `0000-00-00` dates are read as no date.<br>
+/// Returns a `Result<>`, with an error if the XML is malformed, or an entry has no ID or an unknown status
+/// ### Example usage:
+/// ```
+/// use mal_query::myanimelist::xml::parse_xml;
+/// fn parse_example() {
+///     let xml = std::fs::read_to_string("animelist.xml").unwrap();
+///     let list = parse_xml(&xml).unwrap();
+///     for anime in list.anime {
+///         println!("{}: {:?}", anime.title, anime.list_status.status);
+///     }
+/// }
+/// ```
+pub fn parse_xml(xml: &str) -> Result<XmlList, Box<dyn Error>> {
+    let mut reader = Reader::from_str(xml);
+    let mut list = XmlList::default();
+    // The elements the reader is in, the text of the innermost one, and the fields of the entry being read
+    let mut path: Vec<String> = Vec::new();
+    let mut text = String::new();
+    let mut fields: HashMap<String, String> = HashMap::new();
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                path.push(String::from_utf8(e.name().as_ref().to_vec())?);
+                text.clear();
+            },
+            Event::Text(e) => text.push_str(&e.unescape()?),
+            Event::CData(e) => text.push_str(std::str::from_utf8(&e)?),
+            Event::End(_) => {
+                let name = path.pop().unwrap_or_default();
+                match (name.as_str(), path.last().map(String::as_str)) {
+                    ("anime", Some("myanimelist")) => list.anime.push(anime_entry(&std::mem::take(&mut fields))?),
+                    ("manga", Some("myanimelist")) => list.manga.push(manga_entry(&std::mem::take(&mut fields))?),
+                    ("user_name", Some("myinfo")) => list.username = text.trim().to_string(),
+                    (_, Some("anime" | "manga")) => { fields.insert(name, text.trim().to_string()); },
+                    _ => {},
+                }
+                text.clear();
+            },
+            Event::Eof => break,
+            _ => {},
+        }
+    }
+    Ok(list)
+}
+
+fn anime_entry(fields: &HashMap<String, String>) -> Result<XmlAnime, Box<dyn Error>> {
+    let field = |name: &str| fields.get(name).map(String::as_str).unwrap_or("");
+    let id: u32 = field("series_animedb_id").parse().map_err(|_| "Anime has no valid series_animedb_id")?;
+    // MyAnimeList's older exports used numbers for statuses, 5 was never used
+    let status = match field("my_status") {
+        "Watching" | "1" => Status::Watching,
+        "Completed" | "2" => Status::Completed,
+        "On-Hold" | "3" => Status::OnHold,
+        "Dropped" | "4" => Status::Dropped,
+        "Plan to Watch" | "6" => Status::PlanToWatch,
+        other => return Err(format!("Anime {id} has an unknown my_status {other:?}"))?,
+    };
+    Ok(XmlAnime {
+        id,
+        title: field("series_title").to_string(),
+        num_episodes: number(field("series_episodes")),
+        list_status: ListStatus {
+            status,
+            score: number(field("my_score")),
+            num_episodes_watched: number(field("my_watched_episodes")),
+            is_rewatching: yes(field("my_rewatching")),
+            start_date: import_date(field("my_start_date")),
+            finish_date: import_date(field("my_finish_date")),
+            priority: Some(priority_value(field("my_priority"))),
+            num_times_rewatched: Some(number(field("my_times_watched"))),
+            rewatch_value: Some(repeat_number(field("my_rewatch_value"))),
+            tags: Some(tags(field("my_tags"))),
+            comments: Some(field("my_comments").to_string()),
+            updated_at: String::new(),
+        },
+    })
+}
+
+fn manga_entry(fields: &HashMap<String, String>) -> Result<XmlManga, Box<dyn Error>> {
+    let field = |name: &str| fields.get(name).map(String::as_str).unwrap_or("");
+    let id: u32 = field("manga_mangadb_id").parse().map_err(|_| "Manga has no valid manga_mangadb_id")?;
+    let status = match field("my_status") {
+        "Reading" | "1" => ReadingStatus::Reading,
+        "Completed" | "2" => ReadingStatus::Completed,
+        "On-Hold" | "3" => ReadingStatus::OnHold,
+        "Dropped" | "4" => ReadingStatus::Dropped,
+        "Plan to Read" | "6" => ReadingStatus::PlanToRead,
+        other => return Err(format!("Manga {id} has an unknown my_status {other:?}"))?,
+    };
+    Ok(XmlManga {
+        id,
+        title: field("manga_title").to_string(),
+        num_volumes: number(field("manga_volumes")),
+        num_chapters: number(field("manga_chapters")),
+        list_status: MangaListStatus {
+            status,
+            score: number(field("my_score")),
+            num_volumes_read: number(field("my_read_volumes")),
+            num_chapters_read: number(field("my_read_chapters")),
+            is_rereading: yes(field("my_rereading")),
+            start_date: import_date(field("my_start_date")),
+            finish_date: import_date(field("my_finish_date")),
+            priority: Some(priority_value(field("my_priority"))),
+            num_times_reread: Some(number(field("my_times_read"))),
+            reread_value: Some(repeat_number(field("my_reread_value"))),
+            tags: Some(tags(field("my_tags"))),
+            comments: Some(field("my_comments").to_string()),
+            updated_at: String::new(),
+        },
+    })
+}
+
+// Empty and unreadable numbers are 0, the same as MyAnimeList's export uses for unknown counts
+fn number(value: &str) -> u32 {
+    value.parse().unwrap_or(0)
+}
+
+fn yes(value: &str) -> bool {
+    matches!(value.to_ascii_lowercase().as_str(), "1" | "yes" | "true")
+}
+
+fn tags(value: &str) -> Vec<String> {
+    value.split(',').map(str::trim).filter(|t| !t.is_empty()).map(String::from).collect()
+}
+
+fn priority_value(value: &str) -> u32 {
+    match value.to_ascii_lowercase().as_str() {
+        "high" | "2" => 2,
+        "medium" | "1" => 1,
+        _ => 0,
+    }
+}
+
+fn repeat_number(value: &str) -> u32 {
+    match value.to_ascii_lowercase().as_str() {
+        "very low" => 1,
+        "low" => 2,
+        "medium" => 3,
+        "high" => 4,
+        "very high" => 5,
+        other => number(other).min(5),
+    }
+}
+
+// The API leaves out the unknown parts of a date, where the export has them as 00
+fn import_date(date: &str) -> Option<String> {
+    let parts: Vec<&str> = date.split('-').take_while(|p| !p.trim_start_matches('0').is_empty()).collect();
+    match parts.is_empty() {
+        true => None,
+        false => Some(parts.join("-")),
+    }
+}
+
+// The status names MyAnimeList's export uses, which differ from the API's
+fn anime_status_name(status: Status) -> &'static str {
+    match status {
+        Status::Watching => "Watching",
+        Status::Completed => "Completed",
+        Status::OnHold => "On-Hold",
+        Status::Dropped => "Dropped",
+        Status::PlanToWatch => "Plan to Watch",
+    }
+}
+
+fn manga_status_name(status: ReadingStatus) -> &'static str {
+    match status {
+        ReadingStatus::Reading => "Reading",
+        ReadingStatus::Completed => "Completed",
+        ReadingStatus::OnHold => "On-Hold",
+        ReadingStatus::Dropped => "Dropped",
+        ReadingStatus::PlanToRead => "Plan to Read",
+    }
+}
+
+fn anime_type_name(media_type: Option<AnimeMediaType>) -> &'static str {
+    match media_type {
+        Some(AnimeMediaType::TV) => "TV",
+        Some(AnimeMediaType::OVA) => "OVA",
+        Some(AnimeMediaType::Movie) => "Movie",
+        Some(AnimeMediaType::Special) => "Special",
+        Some(AnimeMediaType::ONA) => "ONA",
+        Some(AnimeMediaType::Music) => "Music",
+        Some(AnimeMediaType::Unknown) | None => "Unknown",
+    }
+}
+
+// The API's priority is 0 to 2
+fn priority_name(priority: Option<u32>) -> &'static str {
+    match priority {
+        Some(2) => "HIGH",
+        Some(1) => "MEDIUM",
+        _ => "LOW",
+    }
+}
+
+// Rewatch and reread values are 1 to 5, and left empty when not set
+fn repeat_value(value: Option<u32>) -> String {
+    match value {
+        Some(v) if v > 0 => v.to_string(),
+        _ => String::new(),
+    }
+}
+
+// The export has full dates, with unknown parts as 00, where the API leaves them out
+fn export_date(date: Option<&str>) -> String {
+    let mut parts: Vec<&str> = date.unwrap_or("").split('-').filter(|p| !p.is_empty()).collect();
+    if parts.is_empty() { return "0000-00-00".to_string() }
+    parts.resize(3, "00");
+    parts.join("-")
+}
+
+// Writes the export's layout: one element per line, indented by depth
+struct Xml {
+    text: String,
+    depth: usize,
+}
+
+impl Xml {
+    fn new() -> Self {
+        Xml {
+            text: "<?xml version=\"1.0\" encoding=\"UTF-8\" ?>\n<myanimelist>\n".to_string(),
+            depth: 1,
+        }
+    }
+    fn open(&mut self, name: &str) {
+        self.line(&format!("<{name}>"));
+        self.depth += 1;
+    }
+    fn close(&mut self, name: &str) {
+        self.depth -= 1;
+        self.line(&format!("</{name}>"));
+    }
+    fn tag(&mut self, name: &str, value: &str) {
+        self.line(&format!("<{name}>{}</{name}>", escape(value)));
+    }
+    // Free text is kept as written in a CDATA section. `]]>` would end the section, so it's split across two
+    fn cdata(&mut self, name: &str, value: &str) {
+        self.line(&format!("<{name}><![CDATA[{}]]></{name}>", value.replace("]]>", "]]]]><![CDATA[>")));
+    }
+    fn line(&mut self, line: &str) {
+        self.text.push_str(&"\t".repeat(self.depth));
+        self.text.push_str(line);
+        self.text.push('\n');
+    }
+    fn finish(mut self) -> String {
+        self.text.push_str("</myanimelist>\n");
+        self.text
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
@@ -0,0 +1,364 @@
+use std::error::Error;
+use chrono::NaiveDate;
+use crate::myanimelist::{self, models::*};
+
+/// An anime in the same shape from any `AnimeProvider`, with only the fields MyAnimeList and Jikan both give.<br>
+/// A field is `None` (or empty) when the provider didn't return it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anime {
+    pub id: u32,
+    pub title: String,
+    pub title_english: Option<String>,
+    pub title_japanese: Option<String>,
+    pub synopsis: Option<String>,
+    pub media_type: Option<AnimeMediaType>,
+    pub status: Option<AiringStatus>,
+    pub episodes: Option<u32>,
+    pub score: Option<f32>,
+    pub rank: Option<u32>,
+    pub popularity: Option<u32>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub season: Option<Season>,
+    pub year: Option<u32>,
+    pub genres: Vec<String>,
+    pub studios: Vec<String>,
+    pub image_url: Option<String>,
+}
+
+impl Anime {
+    /// Whether a field the anime should have is `None` or empty.<br>
+    /// Fields that can be absent for the anime itself aren't counted: the English title, which many don't have,
+    /// the score, start date and season before it airs, the end date and episodes until it has finished,
+    /// and the rank, which unranked anime don't have
+    pub fn is_missing_fields(&self) -> bool {
+        let aired = matches!(self.status, Some(AiringStatus::CurrentlyAiring | AiringStatus::FinishedAiring));
+        let finished = self.status == Some(AiringStatus::FinishedAiring);
+        self.title_japanese.is_none()
+            || self.synopsis.is_none()
+            || self.media_type.is_none()
+            || self.status.is_none()
+            || (finished && self.episodes.is_none())
+            || (aired && self.score.is_none())
+            || self.popularity.is_none()
+            || (aired && self.start_date.is_none())
+            || (finished && self.end_date.is_none())
+            || (aired && (self.season.is_none() || self.year.is_none()))
+            || self.genres.is_empty()
+            || self.studios.is_empty()
+            || self.image_url.is_none()
+    }
+    /// Fills every `None` or empty field from another copy of the same anime, keeping the fields already set
+    pub fn fill_from(&mut self, other: Anime) {
+        self.title_english = self.title_english.take().or(other.title_english);
+        self.title_japanese = self.title_japanese.take().or(other.title_japanese);
+        self.synopsis = self.synopsis.take().or(other.synopsis);
+        self.media_type = self.media_type.or(other.media_type);
+        self.status = self.status.or(other.status);
+        self.episodes = self.episodes.or(other.episodes);
+        self.score = self.score.or(other.score);
+        self.rank = self.rank.or(other.rank);
+        self.popularity = self.popularity.or(other.popularity);
+        self.start_date = self.start_date.or(other.start_date);
+        self.end_date = self.end_date.or(other.end_date);
+        self.season = self.season.or(other.season);
+        self.year = self.year.or(other.year);
+        if self.genres.is_empty() { self.genres = other.genres; }
+        if self.studios.is_empty() { self.studios = other.studios; }
+        self.image_url = self.image_url.take().or(other.image_url);
+    }
+}
+
+// Dates can be partial, such as "2009" or "2009-04", which aren't kept
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date.get(..10)?, "%Y-%m-%d").ok()
+}
+
+fn non_empty(s: String) -> Option<String> {
+    if s.is_empty() { None } else { Some(s) }
+}
+
+impl From<MalAnimeData> for Anime {
+    fn from(data: MalAnimeData) -> Self {
+        let (title_english, title_japanese) = match data.alternative_titles {
+            Some(titles) => (non_empty(titles.en), non_empty(titles.ja)),
+            None => (None, None),
+        };
+        Anime {
+            id: data.id,
+            title: data.title,
+            title_english,
+            title_japanese,
+            synopsis: data.synopsis.and_then(non_empty),
+            media_type: data.media_type,
+            status: data.status,
+            episodes: data.num_episodes.filter(|&n| n > 0),
+            score: data.mean,
+            rank: data.rank,
+            popularity: data.popularity,
+            start_date: data.start_date.as_deref().and_then(parse_date),
+            end_date: data.end_date.as_deref().and_then(parse_date),
+            season: data.start_season.as_ref().map(|s| s.season),
+            year: data.start_season.as_ref().map(|s| s.year),
+            genres: data.genres.unwrap_or_default().into_iter().map(|g| g.name).collect(),
+            studios: data.studios.unwrap_or_default().into_iter().map(|s| s.name).collect(),
+            image_url: non_empty(data.main_picture.large),
+        }
+    }
+}
+
+/// A source of anime data, which `MyAnimeList` and `Jikan` both are.<br>
+/// Code written against it can switch between them, or use both with a `Fallback`.
+/// ### Example usage:
+/// ```
+/// use mal_query::provider::{AnimeProvider, MyAnimeList};
+/// use mal_query::myanimelist::models::RankingType;
+/// async fn provider_example(provider: impl AnimeProvider) {
+///     let top = provider.rankings(RankingType::Airing, 10).await.unwrap();
+///     for anime in top {
+///         println!("{} {:?}", anime.title, anime.score);
+///     }
+/// }
+/// ```
+#[allow(async_fn_in_trait)]
+pub trait AnimeProvider {
+    /// Gets an anime by its MyAnimeList ID
+    async fn get_anime(&self, id: u32) -> Result<Anime, Box<dyn Error>>;
+    /// Searches anime by name, returning up to `limit` results
+    async fn search(&self, query: &str, limit: u32) -> Result<Vec<Anime>, Box<dyn Error>>;
+    /// Gets the anime of a season
+    async fn season(&self, year: u32, season: Season) -> Result<Vec<Anime>, Box<dyn Error>>;
+    /// Gets the top `limit` anime of a ranking
+    async fn rankings(&self, ranking_type: RankingType, limit: u32) -> Result<Vec<Anime>, Box<dyn Error>>;
+}
+
+/// The official MyAnimeList API, which gives every field for `get_anime` and lists
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MyAnimeList;
+
+// The fields of `Anime`, for lists that otherwise only give the ID, title and picture
+const MAL_LIST_FIELDS: &str = "alternative_titles,start_date,end_date,synopsis,mean,rank,popularity,media_type,status,genres,num_episodes,start_season,studios";
+
+impl AnimeProvider for MyAnimeList {
+    async fn get_anime(&self, id: u32) -> Result<Anime, Box<dyn Error>> {
+        Ok(myanimelist::retrieval::get_anime(id).await?.into())
+    }
+    async fn search(&self, query: &str, limit: u32) -> Result<Vec<Anime>, Box<dyn Error>> {
+        let url = format!("/anime?q={query}&limit={limit}&fields={MAL_LIST_FIELDS}");
+        let data = myanimelist::run_search(&myanimelist::api_url(&url)).await?;
+        Ok(data.data.into_iter().map(Anime::from).collect())
+    }
+    async fn season(&self, year: u32, season: Season) -> Result<Vec<Anime>, Box<dyn Error>> {
+        let s: &str = match season {
+            Season::Winter => "winter",
+            Season::Spring => "spring",
+            Season::Summer => "summer",
+            Season::Fall => "fall"
+        };
+        let url = format!("/anime/season/{year}/{s}?limit=500&fields={MAL_LIST_FIELDS}");
+        let data = myanimelist::run_search(&myanimelist::api_url(&url)).await?;
+        Ok(data.data.into_iter().map(Anime::from).collect())
+    }
+    async fn rankings(&self, ranking_type: RankingType, limit: u32) -> Result<Vec<Anime>, Box<dyn Error>> {
+        let r_type: &str = match ranking_type {
+            RankingType::All | RankingType::None => "all",
+            RankingType::Airing => "airing",
+            RankingType::Upcoming => "upcoming",
+            RankingType::TV => "tv",
+            RankingType::OVA => "ova",
+            RankingType::Movie => "movie",
+            RankingType::Special => "special",
+            RankingType::ByPopularity => "bypopularity",
+            RankingType::Favorite => "favorite",
+        };
+        let url = format!("/anime/ranking?ranking_type={r_type}&limit={limit}&fields={MAL_LIST_FIELDS}");
+        let data = myanimelist::run_search(&myanimelist::api_url(&url)).await?;
+        Ok(data.data.into_iter().map(Anime::from).collect())
+    }
+}
+
+/// Tries one provider first, then uses a second for what it couldn't give.<br>
+/// If the first fails, the second's result is used instead. If an anime from the first is missing fields,
+/// they are filled from the second, matched by ID, keeping the first's values where both have one.
+/// ### Example usage:
+/// ```
+/// # #[cfg(feature = "jikan")]
+/// # mod example {
+/// use mal_query::provider::{AnimeProvider, Fallback, Jikan, MyAnimeList};
+/// async fn fallback_example() {
+///     let provider = Fallback::new(MyAnimeList, Jikan);
+///     let fmab = provider.get_anime(5114).await.unwrap();
+///     assert!(!fmab.studios.is_empty());
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fallback<P, S> {
+    primary: P,
+    secondary: S,
+}
+
+impl<P: AnimeProvider, S: AnimeProvider> Fallback<P, S> {
+    /// Takes the provider to try first, and the provider to fall back to
+    pub fn new(primary: P, secondary: S) -> Self {
+        Fallback { primary, secondary }
+    }
+}
+
+impl<P: AnimeProvider, S: AnimeProvider> AnimeProvider for Fallback<P, S> {
+    async fn get_anime(&self, id: u32) -> Result<Anime, Box<dyn Error>> {
+        let mut anime = match self.primary.get_anime(id).await {
+            Ok(anime) => anime,
+            Err(_) => return self.secondary.get_anime(id).await,
+        };
+        if anime.is_missing_fields() {
+            if let Ok(other) = self.secondary.get_anime(id).await {
+                anime.fill_from(other);
+            }
+        }
+        Ok(anime)
+    }
+    async fn search(&self, query: &str, limit: u32) -> Result<Vec<Anime>, Box<dyn Error>> {
+        let primary = self.primary.search(query, limit).await;
+        self.fill_list(primary, self.secondary.search(query, limit)).await
+    }
+    async fn season(&self, year: u32, season: Season) -> Result<Vec<Anime>, Box<dyn Error>> {
+        let primary = self.primary.season(year, season).await;
+        self.fill_list(primary, self.secondary.season(year, season)).await
+    }
+    async fn rankings(&self, ranking_type: RankingType, limit: u32) -> Result<Vec<Anime>, Box<dyn Error>> {
+        let primary = self.primary.rankings(ranking_type, limit).await;
+        self.fill_list(primary, self.secondary.rankings(ranking_type, limit)).await
+    }
+}
+
+impl<P, S> Fallback<P, S> {
+    // The secondary list is only requested when the primary failed or is missing fields
+    async fn fill_list(
+        &self,
+        primary: Result<Vec<Anime>, Box<dyn Error>>,
+        secondary: impl std::future::Future<Output = Result<Vec<Anime>, Box<dyn Error>>>,
+    ) -> Result<Vec<Anime>, Box<dyn Error>> {
+        let mut list = match primary {
+            Ok(list) => list,
+            Err(_) => return secondary.await,
+        };
+        if list.iter().any(Anime::is_missing_fields) {
+            if let Ok(others) = secondary.await {
+                for other in others {
+                    if let Some(anime) = list.iter_mut().find(|a| a.id == other.id) {
+                        anime.fill_from(other);
+                    }
+                }
+            }
+        }
+        Ok(list)
+    }
+}
+
+#[cfg(feature = "jikan")]
+pub use self::jikan_provider::Jikan;
+
+#[cfg(feature = "jikan")]
+mod jikan_provider {
+    use std::error::Error;
+    use crate::jikan::{self, AnimeSearchBuilder, JikanPager, models::*};
+    use crate::myanimelist::models::{AiringStatus, AnimeMediaType, RankingType, Season};
+    use super::{parse_date, Anime, AnimeProvider};
+
+    /// The Jikan API, which needs no client ID. Its lists give every field
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct Jikan;
+
+    impl From<JikanAnime> for Anime {
+        fn from(data: JikanAnime) -> Self {
+            let media_type = data.media_type.as_deref().map(|t| match t {
+                "TV" => AnimeMediaType::TV,
+                "OVA" => AnimeMediaType::OVA,
+                "Movie" => AnimeMediaType::Movie,
+                "Special" => AnimeMediaType::Special,
+                "ONA" => AnimeMediaType::ONA,
+                "Music" => AnimeMediaType::Music,
+                _ => AnimeMediaType::Unknown,
+            });
+            let status = data.status.as_deref().and_then(|s| match s {
+                "Finished Airing" => Some(AiringStatus::FinishedAiring),
+                "Currently Airing" => Some(AiringStatus::CurrentlyAiring),
+                "Not yet aired" => Some(AiringStatus::NotYetAired),
+                _ => None,
+            });
+            let season = data.season.as_deref().and_then(|s| match s {
+                "winter" => Some(Season::Winter),
+                "spring" => Some(Season::Spring),
+                "summer" => Some(Season::Summer),
+                "fall" => Some(Season::Fall),
+                _ => None,
+            });
+            let (start_date, end_date) = match &data.aired {
+                Some(aired) => (
+                    aired.from.as_deref().and_then(parse_date),
+                    aired.to.as_deref().and_then(parse_date),
+                ),
+                None => (None, None),
+            };
+            // MyAnimeList counts themes and demographics as genres
+            let genres = data.genres.into_iter()
+                .chain(data.explicit_genres)
+                .chain(data.themes)
+                .chain(data.demographics)
+                .map(|g| g.name)
+                .collect();
+            Anime {
+                id: data.mal_id,
+                title: data.title,
+                title_english: data.title_english,
+                title_japanese: data.title_japanese,
+                synopsis: data.synopsis,
+                media_type,
+                status,
+                episodes: data.episodes,
+                score: data.score,
+                rank: data.rank,
+                popularity: data.popularity,
+                start_date,
+                end_date,
+                season,
+                year: data.year,
+                genres,
+                studios: data.studios.into_iter().map(|s| s.name).collect(),
+                image_url: data.images.jpg.large_image_url.or(data.images.jpg.image_url),
+            }
+        }
+    }
+
+    impl AnimeProvider for Jikan {
+        async fn get_anime(&self, id: u32) -> Result<Anime, Box<dyn Error>> {
+            Ok(jikan::get_anime(id).await?.into())
+        }
+        async fn search(&self, query: &str, limit: u32) -> Result<Vec<Anime>, Box<dyn Error>> {
+            let mut search = AnimeSearchBuilder::new(query);
+            search.limit(limit.min(25));
+            let data = JikanPager::new(|page| search.run_page(page)).take(limit as usize).await?;
+            Ok(data.into_iter().map(Anime::from).collect())
+        }
+        async fn season(&self, year: u32, season: Season) -> Result<Vec<Anime>, Box<dyn Error>> {
+            let data = JikanPager::new(|page| jikan::get_season(year, season, page)).collect_all().await?;
+            Ok(data.into_iter().map(Anime::from).collect())
+        }
+        async fn rankings(&self, ranking_type: RankingType, limit: u32) -> Result<Vec<Anime>, Box<dyn Error>> {
+            let (kind, filter) = match ranking_type {
+                RankingType::All | RankingType::None => (None, None),
+                RankingType::Airing => (None, Some(TopAnimeFilter::Airing)),
+                RankingType::Upcoming => (None, Some(TopAnimeFilter::Upcoming)),
+                RankingType::TV => (Some(AnimeType::TV), None),
+                RankingType::OVA => (Some(AnimeType::OVA), None),
+                RankingType::Movie => (Some(AnimeType::Movie), None),
+                RankingType::Special => (Some(AnimeType::Special), None),
+                RankingType::ByPopularity => (None, Some(TopAnimeFilter::ByPopularity)),
+                RankingType::Favorite => (None, Some(TopAnimeFilter::Favorite)),
+            };
+            let data = JikanPager::new(|page| jikan::get_top_anime(kind, filter, page)).take(limit as usize).await?;
+            Ok(data.into_iter().map(Anime::from).collect())
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::{collections::VecDeque, time::{Duration, Instant}};
+use tokio::sync::Mutex;
+
+/// A sliding window rate limiter, which can limit requests over several windows at once,
+/// such as 3 requests per second and 60 requests per minute.<br>
+/// Each window keeps when its last requests were sent, so no span of the window's length ever has more requests
+/// than it allows, including the first.
+#[derive(Debug)]
+pub struct RateLimiter {
+    windows: Mutex<Vec<Window>>,
+}
+
+#[derive(Debug)]
+struct Window {
+    requests: usize,
+    length: Duration,
+    // When the requests still inside the window were sent, oldest first
+    sent: VecDeque<Instant>,
+}
+
+impl RateLimiter {
+    /// Takes a list of `(requests, window)` limits, which must all allow a request before it is sent.<br>
+    /// An empty list never waits.
+    pub fn new(limits: &[(u32, Duration)]) -> Self {
+        RateLimiter {
+            windows: Mutex::new(limits
+                .iter()
+                .filter(|(requests, window)| *requests > 0 && !window.is_zero())
+                .map(|(requests, window)| Window {
+                    requests: *requests as usize,
+                    length: *window,
+                    sent: VecDeque::new(),
+                })
+                .collect()),
+        }
+    }
+    /// Waits until every limit allows another request, and counts the request in each.
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut windows = self.windows.lock().await;
+                let now = Instant::now();
+                for window in windows.iter_mut() {
+                    while window.sent.front().is_some_and(|sent| now.duration_since(*sent) >= window.length) {
+                        window.sent.pop_front();
+                    }
+                }
+                // The longest wait of any full window, until its oldest request leaves it
+                let wait = windows
+                    .iter()
+                    .filter(|window| window.sent.len() >= window.requests)
+                    .map(|window| (window.sent[0] + window.length).saturating_duration_since(now))
+                    .max();
+                match wait {
+                    None => {
+                        windows.iter_mut().for_each(|window| window.sent.push_back(now));
+                        return;
+                    },
+                    Some(wait) => wait,
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
@@ -0,0 +1,329 @@
+use std::error::Error;
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState},
+    DefaultTerminal, Frame,
+};
+use crate::myanimelist::{
+    builders::{AddFields, UserListBuilder},
+    models::*,
+    user::UpdateAnime,
+};
+
+/// How the list is ordered, highest first
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ListSort {
+    Score,
+    UpdatedAt,
+}
+
+/// A change to one list entry, made from a key press, to be sent with `UpdateAnime`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edit {
+    pub id: u32,
+    pub change: Change,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// Watches the next episode, completing the anime on its last episode
+    IncrementEpisode,
+    Episodes(u32),
+    Score(u32),
+    Status(Status),
+}
+
+impl Edit {
+    /// The `UpdateAnime` that sends this change
+    pub fn to_update(&self) -> UpdateAnime {
+        let mut update = UpdateAnime::new(self.id);
+        match self.change {
+            Change::IncrementEpisode => update.increment_episode(),
+            Change::Episodes(episodes) => update.update_num_watched_episodes(episodes),
+            // Scores are checked when they're typed
+            Change::Score(score) => update.update_score(score).expect("Score checked by App"),
+            Change::Status(status) => update.update_status(status),
+        };
+        update
+    }
+}
+
+// The statuses `f` cycles through, after showing every status
+const FILTERS: [Status; 5] = [Status::Watching, Status::Completed, Status::OnHold, Status::Dropped, Status::PlanToWatch];
+
+const HELP: &str = "↑/↓ move  f filter  o sort  +/- episode  s score  w/c/h/d/p status  r reload  q quit";
+
+/// The state of the list browser, separate from the terminal so it can be driven by tests.<br>
+/// Key presses that edit an entry return an `Edit`, and the entry is only changed once the update
+/// is confirmed with `apply()`.
+#[derive(Debug)]
+pub struct App {
+    entries: Vec<MalAnimeData>,
+    filter: Option<Status>,
+    sort: ListSort,
+    state: TableState,
+    score_input: Option<String>,
+    message: String,
+    quit: bool,
+}
+
+impl App {
+    /// Takes the user's list, with `list_status`, sorted by score and showing every status
+    pub fn new(entries: Vec<MalAnimeData>) -> Self {
+        let mut app = App {
+            entries,
+            filter: None,
+            sort: ListSort::Score,
+            state: TableState::default(),
+            score_input: None,
+            message: String::new(),
+            quit: false,
+        };
+        app.sort_entries();
+        app.clamp_selection();
+        app
+    }
+    /// Replaces the list, such as after reloading it, keeping the filter and sort
+    pub fn set_entries(&mut self, entries: Vec<MalAnimeData>) {
+        self.entries = entries;
+        self.sort_entries();
+        self.clamp_selection();
+    }
+    /// The entries shown, after the filter
+    pub fn visible(&self) -> Vec<&MalAnimeData> {
+        self.entries
+            .iter()
+            .filter(|a| match (self.filter, &a.list_status) {
+                (None, _) => true,
+                (Some(filter), Some(status)) => status.status == filter,
+                (Some(_), None) => false,
+            })
+            .collect()
+    }
+    /// The highlighted entry
+    pub fn selected(&self) -> Option<&MalAnimeData> {
+        self.visible().get(self.state.selected()?).copied()
+    }
+    pub fn filter(&self) -> Option<Status> {
+        self.filter
+    }
+    pub fn sort(&self) -> ListSort {
+        self.sort
+    }
+    /// The line shown under the list, such as the result of the last edit
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+    pub fn set_message(&mut self, message: &str) {
+        self.message = message.to_string();
+    }
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+    /// Handles a key press, returning the edit to send if the key changes an entry
+    pub fn handle_key(&mut self, key: KeyCode) -> Option<Edit> {
+        if self.score_input.is_some() { return self.handle_score_key(key) }
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Char('f') => {
+                self.filter = match self.filter {
+                    None => Some(FILTERS[0]),
+                    Some(filter) => FILTERS.iter().skip_while(|f| **f != filter).nth(1).copied(),
+                };
+                self.state.select(Some(0));
+                self.clamp_selection();
+            }
+            KeyCode::Char('o') => {
+                self.sort = match self.sort {
+                    ListSort::Score => ListSort::UpdatedAt,
+                    ListSort::UpdatedAt => ListSort::Score,
+                };
+                self.sort_entries();
+            }
+            KeyCode::Char('s') if self.selected().is_some() => self.score_input = Some(String::new()),
+            KeyCode::Char('+') => return self.edit(Change::IncrementEpisode),
+            KeyCode::Char('-') => {
+                let watched = self.selected()?.list_status.as_ref()?.num_episodes_watched;
+                if watched == 0 { return None }
+                return self.edit(Change::Episodes(watched - 1))
+            }
+            KeyCode::Char('w') => return self.edit(Change::Status(Status::Watching)),
+            KeyCode::Char('c') => return self.edit(Change::Status(Status::Completed)),
+            KeyCode::Char('h') => return self.edit(Change::Status(Status::OnHold)),
+            KeyCode::Char('d') => return self.edit(Change::Status(Status::Dropped)),
+            KeyCode::Char('p') => return self.edit(Change::Status(Status::PlanToWatch)),
+            _ => {}
+        }
+        None
+    }
+    /// Updates an entry with the list status MyAnimeList returned for an edit
+    pub fn apply(&mut self, id: u32, list_status: ListStatus) {
+        let selected_id = self.selected().map(|a| a.id);
+        if let Some(entry) = self.entries.iter_mut().find(|a| a.id == id) {
+            entry.list_status = Some(list_status);
+        }
+        self.sort_entries();
+        // Keeps the same entry highlighted, unless it was filtered out
+        if let Some(index) = self.visible().iter().position(|a| Some(a.id) == selected_id) {
+            self.state.select(Some(index));
+        }
+        self.clamp_selection();
+    }
+    /// Draws the list, with the filter and sort above it, and the keys and message below
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let [list_area, footer_area] = Layout::vertical([Constraint::Min(3), Constraint::Length(3)]).areas(frame.area());
+        let filter = self.filter.map(status_name).unwrap_or("all");
+        let sort = match self.sort {
+            ListSort::Score => "score",
+            ListSort::UpdatedAt => "updated",
+        };
+        let rows: Vec<Row> = self.visible()
+            .into_iter()
+            .map(|a| {
+                let status = a.list_status.as_ref();
+                Row::new(vec![
+                    a.title.clone(),
+                    status.map(|s| status_name(s.status)).unwrap_or("-").to_string(),
+                    status.map(|s| s.score.to_string()).unwrap_or_default(),
+                    format!(
+                        "{}/{}",
+                        status.map(|s| s.num_episodes_watched).unwrap_or(0),
+                        a.num_episodes.filter(|&n| n > 0).map(|n| n.to_string()).unwrap_or("?".to_string())
+                    ),
+                ])
+            })
+            .collect();
+        let table = Table::new(rows, [
+                Constraint::Fill(1),
+                Constraint::Length(13),
+                Constraint::Length(5),
+                Constraint::Length(9),
+            ])
+            .header(Row::new(["Title", "Status", "Score", "Progress"]).style(Style::new().add_modifier(Modifier::BOLD)))
+            .block(Block::new().borders(Borders::ALL).title(format!(" Anime list | {filter} | by {sort} ")))
+            .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, list_area, &mut self.state);
+
+        let status_line = match &self.score_input {
+            Some(input) => format!("Score (0-10), Enter to save: {input}"),
+            None => self.message.clone(),
+        };
+        let footer = Paragraph::new(format!("{status_line}\n{HELP}")).block(Block::new().borders(Borders::TOP));
+        frame.render_widget(footer, footer_area);
+    }
+
+    fn handle_score_key(&mut self, key: KeyCode) -> Option<Edit> {
+        let input = self.score_input.as_mut()?;
+        match key {
+            KeyCode::Char(c) if c.is_ascii_digit() && input.len() < 2 => input.push(c),
+            KeyCode::Backspace => { input.pop(); }
+            KeyCode::Esc => self.score_input = None,
+            KeyCode::Enter => {
+                let input = self.score_input.take()?;
+                match input.parse::<u32>() {
+                    Ok(score) if score <= 10 => return self.edit(Change::Score(score)),
+                    _ => self.message = "Score has to be 0-10".to_string(),
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+    fn edit(&self, change: Change) -> Option<Edit> {
+        Some(Edit { id: self.selected()?.id, change })
+    }
+    fn move_selection(&mut self, by: isize) {
+        let len = self.visible().len();
+        if len == 0 { return }
+        let current = self.state.selected().unwrap_or(0) as isize;
+        self.state.select(Some((current + by).clamp(0, len as isize - 1) as usize));
+    }
+    fn clamp_selection(&mut self) {
+        let len = self.visible().len();
+        self.state.select(match len {
+            0 => None,
+            _ => Some(self.state.selected().unwrap_or(0).min(len - 1)),
+        });
+    }
+    fn sort_entries(&mut self) {
+        let sort = self.sort;
+        self.entries.sort_by(|a, b| {
+            let (a_status, b_status) = (a.list_status.as_ref(), b.list_status.as_ref());
+            let order = match sort {
+                ListSort::Score => b_status.map(|s| s.score).cmp(&a_status.map(|s| s.score)),
+                ListSort::UpdatedAt => b_status.map(|s| &s.updated_at).cmp(&a_status.map(|s| &s.updated_at)),
+            };
+            order.then_with(|| a.title.cmp(&b.title))
+        });
+    }
+}
+
+fn status_name(status: Status) -> &'static str {
+    match status {
+        Status::Watching => "watching",
+        Status::Completed => "completed",
+        Status::OnHold => "on_hold",
+        Status::Dropped => "dropped",
+        Status::PlanToWatch => "plan_to_watch",
+    }
+}
+
+async fn load_list() -> Result<Vec<MalAnimeData>, Box<dyn Error>> {
+    let list = UserListBuilder::new("@me")
+        .include_list_status()
+        .add_num_episodes()
+        .run_all()
+        .await?;
+    Ok(list.data)
+}
+
+/// Opens the logged in user's anime list in the terminal, to filter, sort and edit it.<br>
+/// User MUST be loggin in with the `login` function, and have a token generated for them for this to be used.<br>
+/// Async function, function must be called with `.await`<br>
+/// Returns a `Result<>`, with an error if the list couldn't be loaded, or the terminal couldn't be drawn
+pub async fn run() -> Result<(), Box<dyn Error>> {
+    let mut app = App::new(load_list().await?);
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app).await;
+    ratatui::restore();
+    result
+}
+
+async fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> Result<(), Box<dyn Error>> {
+    while !app.should_quit() {
+        terminal.draw(|frame| app.draw(frame))?;
+        // Blocks until a key is pressed, since nothing else updates the screen
+        let Event::Key(key) = event::read()? else { continue };
+        if key.kind != KeyEventKind::Press { continue }
+        match key.code {
+            KeyCode::Char('r') if app.score_input.is_none() => {
+                app.set_message("Reloading...");
+                terminal.draw(|frame| app.draw(frame))?;
+                match load_list().await {
+                    Ok(entries) => {
+                        app.set_entries(entries);
+                        app.set_message("Reloaded");
+                    }
+                    Err(e) => app.set_message(&format!("Error: {e}")),
+                }
+            }
+            code => {
+                let Some(edit) = app.handle_key(code) else { continue };
+                app.set_message("Saving...");
+                terminal.draw(|frame| app.draw(frame))?;
+                match edit.to_update().update().await {
+                    Ok(list_status) => {
+                        app.apply(edit.id, list_status);
+                        app.set_message("Saved");
+                    }
+                    Err(e) => app.set_message(&format!("Error: {e}")),
+                }
+            }
+        }
+    }
+    Ok(())
+}
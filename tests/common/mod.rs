@@ -0,0 +1,15 @@
+use std::sync::OnceLock;
+use mal_query::myanimelist::{self, client::{MalClient, RetryPolicy}, transport::MockTransport};
+
+static MOCK: OnceLock<MockTransport> = OnceLock::new();
+
+// Answers this test binary's MyAnimeList requests from a recording in tests/fixtures/mal, logged in with a test token.
+// Tests in a binary run at once and share the client, so it is only installed by the first
+pub fn mock_mal(recording: &str) -> &'static MockTransport {
+    MOCK.get_or_init(|| {
+        let mock = MockTransport::from_recording(format!("tests/fixtures/mal/{recording}.json")).unwrap();
+        myanimelist::set_client(MalClient::new().transport(&mock).retry(&RetryPolicy::none()));
+        myanimelist::set_token("test_token");
+        mock
+    })
+}
@@ -0,0 +1,63 @@
+use chrono::{NaiveTime, TimeZone, Utc, FixedOffset};
+use mal_query::myanimelist::models::{MalAnimeData, Broadcast, Weekday};
+
+fn airing_anime(day: &str, time: &str) -> MalAnimeData {
+    let json = format!(r#"{{
+        "id": 52991,
+        "title": "Sousou no Frieren",
+        "main_picture": {{ "large": "", "medium": "" }},
+        "status": "currently_airing",
+        "broadcast": {{ "day_of_the_week": "{day}", "start_time": "{time}" }}
+    }}"#);
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn broadcast_is_typed() {
+    let anime = airing_anime("friday", "23:00");
+    let broadcast = anime.broadcast.unwrap();
+    assert_eq!(broadcast.day_of_the_week, Weekday::Friday);
+    assert_eq!(broadcast.start_time, NaiveTime::from_hms_opt(23, 0, 0));
+}
+
+#[test]
+fn unknown_start_time_is_none() {
+    let broadcast: Broadcast = serde_json::from_str(r#"{ "day_of_the_week": "other" }"#).unwrap();
+    assert_eq!(broadcast.day_of_the_week, Weekday::Other);
+    assert_eq!(broadcast.start_time, None);
+    assert_eq!(broadcast.next_after(Utc::now()), None);
+}
+
+#[test]
+fn next_broadcast_converts_from_japan_time() {
+    let anime = airing_anime("friday", "23:00");
+    // Wednesday 2023-10-04 12:00 UTC. Friday 23:00 JST is Friday 14:00 UTC
+    let after = Utc.with_ymd_and_hms(2023, 10, 4, 12, 0, 0).unwrap();
+    assert_eq!(anime.next_broadcast_after(after), Some(Utc.with_ymd_and_hms(2023, 10, 6, 14, 0, 0).unwrap()));
+}
+
+#[test]
+fn next_broadcast_can_be_a_different_day_in_utc() {
+    let anime = airing_anime("saturday", "01:30");
+    // Saturday 01:30 JST is Friday 16:30 UTC
+    let after = Utc.with_ymd_and_hms(2023, 10, 6, 10, 0, 0).unwrap();
+    let next = anime.next_broadcast_after(after).unwrap();
+    assert_eq!(next, Utc.with_ymd_and_hms(2023, 10, 6, 16, 30, 0).unwrap());
+
+    let new_york = FixedOffset::west_opt(4 * 3600).unwrap();
+    assert_eq!(next.with_timezone(&new_york), new_york.with_ymd_and_hms(2023, 10, 6, 12, 30, 0).unwrap());
+}
+
+#[test]
+fn next_broadcast_skips_to_next_week_once_aired() {
+    let anime = airing_anime("friday", "23:00");
+    let aired = Utc.with_ymd_and_hms(2023, 10, 6, 14, 0, 0).unwrap();
+    assert_eq!(anime.next_broadcast_after(aired), Some(Utc.with_ymd_and_hms(2023, 10, 13, 14, 0, 0).unwrap()));
+}
+
+#[test]
+fn only_airing_anime_have_a_next_broadcast() {
+    let mut anime = airing_anime("friday", "23:00");
+    anime.status = None;
+    assert_eq!(anime.next_broadcast(), None);
+}
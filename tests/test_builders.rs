@@ -1,7 +1,11 @@
 use mal_query::myanimelist::{builders::{Builder, AddFields, SearchBuilder, SeasonalBuilder, UserListBuilder}, models::{AiringStatus, Season, Status, Sort}};
 
+mod common;
+use common::mock_mal;
+
 #[tokio::test]
 async fn full_builder_works_as_expected() {
+    let mock = mock_mal("builders");
     let test = Builder::new(9756)
         .add_id()
         .add_title()
@@ -37,8 +41,10 @@ async fn full_builder_works_as_expected() {
         .add_statistics()
         .run()
         .await;
+    let fields = &mock.requests_to("GET", "/v2/anime/9756")[0].query["fields"];
+    assert!(fields.contains("related_anime") && fields.contains("statistics"));
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             assert_eq!(data.id, 9756);
             assert_eq!(data.title, "Mahou Shoujo Madoka★Magica");
@@ -82,13 +88,15 @@ async fn full_builder_works_as_expected() {
 
 #[tokio::test]
 async fn small_builder_works_as_expected() {
+    let mock = mock_mal("builders");
     let test = Builder::new(10620)
         .add_status()
         .add_num_episodes()
         .run()
         .await;
+    assert_eq!(mock.requests_to("GET", "/v2/anime/10620")[0].query["fields"], "status,num_episodes,");
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             assert_eq!(data.id, 10620);
             assert_eq!(data.title, "Mirai Nikki (TV)");
@@ -130,14 +138,17 @@ async fn small_builder_works_as_expected() {
 
 #[tokio::test]
 async fn search_builder_works_as_expected() {
+    let mock = mock_mal("builders");
     let test = SearchBuilder::new("gurashi", 5)
         .add_nsfw()
         .add_rank()
         .run()
         .await;
+    let query = &mock.requests_to("GET", "/v2/anime")[0].query;
+    assert_eq!((query["q"].as_str(), query["limit"].as_str()), ("gurashi", "5"));
 
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             data.data.iter().for_each(|anime| {
                 assert!(anime.title.to_lowercase().contains("gurashi"));
@@ -150,14 +161,16 @@ async fn search_builder_works_as_expected() {
 
 #[tokio::test]
 async fn seasonal_builder_works_as_expected() {
+    let mock = mock_mal("builders");
     let test = SeasonalBuilder::new(2020, Season::Spring)
         .add_start_season()
         .add_start_date()
         .run()
         .await;
+    assert_eq!(mock.requests_to("GET", "/v2/anime/season/2020/spring").len(), 1);
 
     match test {
-        Err(e) => { println!("{e:?}"); assert!(false) },
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             data.data.iter().for_each(|anime| {
                 // I wanted to check if it all was 2020 Spring, but turns out, 
@@ -171,6 +184,7 @@ async fn seasonal_builder_works_as_expected() {
 
 #[tokio::test]
 async fn full_userlist_builder_works_as_intended() {
+    let mock = mock_mal("builders");
     let test = UserListBuilder::new("naginis_api")
         .status(Status::Completed)
         .sort(Sort::ListScore)
@@ -179,9 +193,14 @@ async fn full_userlist_builder_works_as_intended() {
         .include_list_status()
         .run()
         .await;
+    let sent = mock.requests_to("GET", "/v2/users/naginis_api/animelist");
+    let query = &sent.iter().find(|r| r.query.contains_key("sort")).expect("List was not requested").query;
+    assert_eq!(query["sort"], "list_score");
+    assert_eq!((query["limit"].as_str(), query["offset"].as_str()), ("2", "1"));
+    assert!(query["fields"].starts_with("list_status{"));
 
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             assert_eq!(data.data.len(), 1);
         }
@@ -190,13 +209,16 @@ async fn full_userlist_builder_works_as_intended() {
 
 #[tokio::test]
 async fn small_userlist_builder_works_as_intended() {
+    let mock = mock_mal("builders");
     let test = UserListBuilder::new("naginis_api")
         .status(Status::Watching)
         .run()
         .await;
+    let sent = mock.requests_to("GET", "/v2/users/naginis_api/animelist");
+    assert!(sent.iter().any(|r| r.query.get("status").map(String::as_str) == Some("watching")));
 
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             assert_eq!(data.data.len(), 4);
         }
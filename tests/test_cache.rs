@@ -0,0 +1,194 @@
+use std::{
+    sync::{atomic::{AtomicUsize, Ordering}, Arc},
+    time::Duration,
+};
+use mal_query::myanimelist::{
+    cache::{CacheStats, Endpoint, ResponseCache},
+    client::MalClient,
+};
+use tiny_http::{Header, Response, Server};
+
+// A server answering every path with its own path, with an ETag for paths with `etag` in them,
+// and a 404 for paths with `missing`. Returns its URL and how many requests it received
+fn serve() -> (String, Arc<AtomicUsize>) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.server_addr());
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+    std::thread::spawn(move || {
+        for request in server.incoming_requests() {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let path = request.url().to_string();
+            let matched = request.headers().iter().any(|h| h.field.equiv("If-None-Match") && h.value == "\"v1\"");
+            let response = match (path.contains("missing"), path.contains("etag"), matched) {
+                (true, _, _) => Response::from_string("{}").with_status_code(404),
+                (_, true, true) => Response::from_string("").with_status_code(304),
+                (_, true, false) => Response::from_string(path)
+                    .with_header(Header::from_bytes("ETag", "\"v1\"").unwrap()),
+                _ => Response::from_string(path),
+            };
+            let _ = request.respond(response);
+        }
+    });
+    (url, requests)
+}
+
+async fn body(client: &MalClient, url: &str) -> String {
+    client.get(url).await.unwrap().text().await.unwrap()
+}
+
+#[test]
+fn endpoints_from_urls() {
+    let base = "https://api.myanimelist.net/v2";
+    assert_eq!(Endpoint::from_url(&format!("{base}/anime/21?fields=id,title")), Endpoint::Anime);
+    assert_eq!(Endpoint::from_url(&format!("{base}/manga/2")), Endpoint::Manga);
+    assert_eq!(Endpoint::from_url(&format!("{base}/anime?q=One Piece&limit=5")), Endpoint::Search);
+    assert_eq!(Endpoint::from_url(&format!("{base}/anime/season/2023/winter")), Endpoint::Season);
+    assert_eq!(Endpoint::from_url(&format!("{base}/manga/ranking?ranking_type=all")), Endpoint::Ranking);
+    assert_eq!(Endpoint::from_url(&format!("{base}/users/@me/animelist?limit=10")), Endpoint::UserList);
+    assert_eq!(Endpoint::from_url(&format!("{base}/users/@me")), Endpoint::User);
+    assert_eq!(Endpoint::from_url(&format!("{base}/forum/boards")), Endpoint::Other);
+    assert_eq!(Endpoint::from_url("not a url"), Endpoint::Other);
+}
+
+#[tokio::test]
+async fn repeated_requests_are_hits() {
+    let (url, requests) = serve();
+    let cache = ResponseCache::new(10);
+    let client = MalClient::new().cache(&cache).clone();
+
+    assert_eq!(body(&client, &format!("{url}/v2/anime/21")).await, "/v2/anime/21");
+    assert_eq!(body(&client, &format!("{url}/v2/anime/21")).await, "/v2/anime/21");
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+    assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1, revalidated: 0 });
+
+    // Failed responses aren't kept
+    assert_eq!(client.get(&format!("{url}/v2/anime/missing")).await.unwrap().status(), 404);
+    assert_eq!(client.get(&format!("{url}/v2/anime/missing")).await.unwrap().status(), 404);
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn expired_responses_are_revalidated_by_etag() {
+    let (url, requests) = serve();
+    let cache = ResponseCache::new(10).ttl(Endpoint::Anime, Duration::ZERO).clone();
+    let client = MalClient::new().cache(&cache).clone();
+
+    assert_eq!(body(&client, &format!("{url}/v2/anime/1?etag")).await, "/v2/anime/1?etag");
+    assert_eq!(body(&client, &format!("{url}/v2/anime/1?etag")).await, "/v2/anime/1?etag");
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+    assert_eq!(cache.stats(), CacheStats { hits: 0, misses: 1, revalidated: 1 });
+
+    // Without an ETag, an expired response is requested again in full
+    body(&client, &format!("{url}/v2/anime/2")).await;
+    body(&client, &format!("{url}/v2/anime/2")).await;
+    assert_eq!(cache.stats(), CacheStats { hits: 0, misses: 3, revalidated: 1 });
+}
+
+#[tokio::test]
+async fn least_recently_used_is_dropped() {
+    let (url, requests) = serve();
+    let cache = ResponseCache::new(2);
+    let client = MalClient::new().cache(&cache).clone();
+
+    for id in [1, 2, 1, 3, 1] {
+        body(&client, &format!("{url}/v2/anime/{id}")).await;
+    }
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+    // 2 was used least recently, so it was dropped for 3
+    body(&client, &format!("{url}/v2/anime/2")).await;
+    assert_eq!(requests.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn disk_responses_are_kept_between_caches() {
+    let (url, requests) = serve();
+    let dir = std::env::temp_dir().join(format!("mal_query_cache_{}", std::process::id()));
+    let first = MalClient::new().cache(ResponseCache::new(10).disk(&dir)).clone();
+    body(&first, &format!("{url}/v2/manga/2")).await;
+
+    let cache = ResponseCache::new(10).disk(&dir).clone();
+    let second = MalClient::new().cache(&cache).clone();
+    assert_eq!(body(&second, &format!("{url}/v2/manga/2")).await, "/v2/manga/2");
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+    assert_eq!(cache.stats().hits, 1);
+
+    // Invalidating removes the file too
+    cache.invalidate_manga(2);
+    let third = MalClient::new().cache(ResponseCache::new(10).disk(&dir)).clone();
+    body(&third, &format!("{url}/v2/manga/2")).await;
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[tokio::test]
+async fn updates_invalidate_the_entry_and_lists() {
+    let (url, requests) = serve();
+    let cache = ResponseCache::new(10);
+    let client = MalClient::new().cache(&cache).clone();
+    let urls = [
+        format!("{url}/v2/anime/21"),
+        format!("{url}/v2/anime/22"),
+        format!("{url}/v2/users/@me/animelist"),
+    ];
+    for url in &urls { body(&client, url).await; }
+
+    cache.invalidate_anime(21);
+    for url in &urls { body(&client, url).await; }
+    // 22 is still cached
+    assert_eq!(requests.load(Ordering::SeqCst), 5);
+}
+
+#[tokio::test]
+async fn updates_invalidate_responses_with_list_status() {
+    let (url, requests) = serve();
+    let cache = ResponseCache::new(10);
+    let client = MalClient::new().cache(&cache).clone();
+    let urls = [
+        format!("{url}/v2/anime?q=Cowboy&fields=id,my_list_status"),
+        format!("{url}/v2/anime/season/2023/winter?fields=my_list_status"),
+        format!("{url}/v2/anime/ranking?ranking_type=all&fields=my_list_status"),
+        format!("{url}/v2/anime?q=Cowboy&fields=id,title"),
+        format!("{url}/v2/manga?q=Berserk&fields=my_list_status"),
+    ];
+    for url in &urls { body(&client, url).await; }
+
+    cache.invalidate_anime(1);
+    for url in &urls { body(&client, url).await; }
+    // Only the anime responses that asked for the user's list status are requested again
+    assert_eq!(requests.load(Ordering::SeqCst), 8);
+
+    cache.invalidate_manga(2);
+    body(&client, &urls[4]).await;
+    assert_eq!(requests.load(Ordering::SeqCst), 9);
+}
+
+#[tokio::test]
+async fn list_status_responses_expire_like_lists() {
+    let (url, requests) = serve();
+    let cache = ResponseCache::new(10).ttl(Endpoint::UserList, Duration::ZERO).clone();
+    let client = MalClient::new().cache(&cache).clone();
+
+    // The anime's details are kept, but its list status is requested again, as the list's would be
+    for _ in 0..2 {
+        body(&client, &format!("{url}/v2/anime/21")).await;
+        body(&client, &format!("{url}/v2/anime/21?fields=num_episodes,my_list_status")).await;
+    }
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn entries_read_before_updates_skip_the_cache() {
+    use mal_query::myanimelist::{self, transport::MockTransport, user::UpdateAnime};
+    let mock = MockTransport::new();
+    mock.respond("GET", "/v2/anime/21", 200, r#"{"num_episodes":1100,"my_list_status":{"status":"watching","score":0,
+        "num_episodes_watched":4,"is_rewatching":false,"updated_at":"2024-01-04T10:00:00+00:00"}}"#);
+    myanimelist::set_client(MalClient::new().transport(&mock).cache(&ResponseCache::new(10)));
+    myanimelist::set_token("test_token");
+
+    // The entry may have been edited on the website since, so each preview reads it again
+    let update = UpdateAnime::new(21).update_num_watched_episodes(5).clone();
+    assert_eq!(update.preview().await.unwrap().len(), 1);
+    assert_eq!(update.preview().await.unwrap().len(), 1);
+    assert_eq!(mock.requests_to("GET", "/v2/anime/21").len(), 2);
+}
@@ -0,0 +1,46 @@
+use std::fs;
+use chrono::{TimeZone, Utc};
+use mal_query::myanimelist::{calendar::to_icalendar, models::MalAnimeData};
+
+fn watching_fixture() -> Vec<MalAnimeData> {
+    let json = fs::read_to_string("tests/fixtures/calendar/watching.json").unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+// Fixtures are saved with LF line endings, iCalendar uses CRLF
+fn expected_fixture() -> String {
+    fs::read_to_string("tests/fixtures/calendar/watching.ics")
+        .unwrap()
+        .replace("\r\n", "\n")
+        .replace('\n', "\r\n")
+}
+
+#[test]
+fn icalendar_matches_fixture() {
+    let dtstamp = Utc.with_ymd_and_hms(2024, 1, 10, 0, 0, 0).unwrap();
+    let ics = to_icalendar(&watching_fixture(), dtstamp);
+    assert_eq!(ics, expected_fixture());
+}
+
+#[test]
+fn icalendar_is_valid_rfc_5545_text() {
+    let ics = to_icalendar(&watching_fixture(), Utc::now());
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    // Every line ends in CRLF, and is at most 75 octets
+    assert!(!ics.replace("\r\n", "").contains('\n'));
+    ics.split("\r\n").for_each(|line| assert!(line.len() <= 75, "{line}"));
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), 3);
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), ics.matches("END:VEVENT").count());
+    // Required properties of each event
+    for property in ["UID:", "DTSTAMP:", "DTSTART:"] {
+        assert_eq!(ics.matches(&format!("\r\n{property}")).count(), 3);
+    }
+}
+
+#[test]
+fn icalendar_without_airing_anime_is_empty() {
+    let ics = to_icalendar(&[], Utc::now());
+    assert!(!ics.contains("BEGIN:VEVENT"));
+    assert!(ics.contains("VERSION:2.0\r\n"));
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "cli")]
+use std::{env, fs, path::PathBuf, process::{Command, Output}};
+
+// Runs `mal` in its own directory, so no token.txt is found
+fn mal(args: &[&str]) -> Output {
+    let dir: PathBuf = env::temp_dir().join(format!("mal-cli-test-{}", args.join("-").replace(['/', ' '], "_")));
+    fs::create_dir_all(&dir).unwrap();
+    Command::new(env!("CARGO_BIN_EXE_mal"))
+        .args(args)
+        .current_dir(&dir)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn help_succeeds() {
+    let output = mal(&["--help"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("whoami"));
+}
+
+#[test]
+fn invalid_arguments_exit_with_2() {
+    assert_eq!(mal(&["season", "2020", "autumn"]).status.code(), Some(2));
+    assert_eq!(mal(&["update", "1", "--score", "11"]).status.code(), Some(2));
+    assert_eq!(mal(&["update", "1"]).status.code(), Some(2));
+}
+
+#[test]
+fn commands_needing_login_exit_with_3() {
+    assert_eq!(mal(&["whoami"]).status.code(), Some(3));
+    assert_eq!(mal(&["delete", "1"]).status.code(), Some(3));
+    assert_eq!(mal(&["update", "1", "--status", "watching"]).status.code(), Some(3));
+}
+
+#[test]
+fn logout_without_token_succeeds() {
+    let output = mal(&["logout", "--json"]);
+    assert_eq!(output.status.code(), Some(0));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["logged_in"], false);
+}
+
+#[test]
+fn list_shows_the_list_status() {
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let api_url = format!("http://{}/v2", server.server_addr());
+    let handle = std::thread::spawn(move || {
+        let request = server.recv().unwrap();
+        let url = request.url().to_string();
+        let body = r#"{"data":[{"node":{"id":21,"title":"One Piece","num_episodes":0,
+            "main_picture":{"medium":"","large":""}},
+            "list_status":{"status":"watching","score":9,"num_episodes_watched":1100,"is_rewatching":false,
+            "updated_at":"2024-01-04T10:00:00+00:00"}}],"paging":{}}"#;
+        request.respond(tiny_http::Response::from_string(body)).unwrap();
+        url
+    });
+    let output = mal(&["list", "Xinil", "--limit", "1", "--api-url", &api_url]);
+    assert_eq!(output.status.code(), Some(0));
+
+    // MyAnimeList only gives the list status when the fields ask for it
+    assert!(handle.join().unwrap().contains("fields=list_status"));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let row = stdout.lines().find(|line| line.starts_with("21")).unwrap();
+    assert!(row.contains("watching"));
+    assert!(row.contains("1100/-"));
+}
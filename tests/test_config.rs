@@ -0,0 +1,87 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use mal_query::{config::ClientConfig, myanimelist::client::{MalClient, RetryPolicy}};
+use tiny_http::{Response, Server};
+
+// Each request's URL and User-Agent
+type Received = Arc<Mutex<Vec<(String, String)>>>;
+
+// A server that answers every request after `delay`, and keeps what it received.
+// Returns its address, such as `127.0.0.1:1234`
+fn serve(delay: Duration) -> (String, Received) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let address = server.server_addr().to_string();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let received = requests.clone();
+    std::thread::spawn(move || {
+        for request in server.incoming_requests() {
+            let user_agent = request.headers()
+                .iter()
+                .find(|h| h.field.equiv("User-Agent"))
+                .map(|h| h.value.to_string())
+                .unwrap_or_default();
+            received.lock().unwrap().push((request.url().to_string(), user_agent));
+            std::thread::sleep(delay);
+            let _ = request.respond(Response::from_string(r#"{"data":{"mal_id":21}}"#));
+        }
+    });
+    (address, requests)
+}
+
+fn client(config: &ClientConfig) -> MalClient {
+    MalClient::new().config(config).unwrap().retry(&RetryPolicy::none()).clone()
+}
+
+#[tokio::test]
+async fn user_agent_is_sent() {
+    let (address, requests) = serve(Duration::ZERO);
+    let mal = client(ClientConfig::new().user_agent("mal-query-tests/1.0"));
+    mal.get(&format!("http://{address}/v2/anime/21")).await.unwrap();
+    assert_eq!(requests.lock().unwrap()[0], ("/v2/anime/21".to_string(), "mal-query-tests/1.0".to_string()));
+}
+
+#[tokio::test]
+async fn slow_responses_time_out() {
+    let (address, _) = serve(Duration::from_millis(500));
+    let mal = client(ClientConfig::new().timeout(Duration::from_millis(100)));
+    let error = mal.get(&format!("http://{address}/v2/anime/21")).await.unwrap_err();
+    assert!(error.downcast_ref::<reqwest::Error>().is_some_and(|e| e.is_timeout()));
+
+    // Without the timeout, the response is waited for
+    let mal = client(&ClientConfig::new());
+    assert_eq!(mal.get(&format!("http://{address}/v2/anime/21")).await.unwrap().status(), 200);
+}
+
+#[tokio::test]
+async fn requests_go_through_the_proxy() {
+    let (address, requests) = serve(Duration::ZERO);
+    let mal = client(ClientConfig::new().proxy(&format!("http://{address}")).unwrap());
+    // The host doesn't exist, so the request can only be answered by the proxy
+    let res = mal.get("http://api.myanimelist.invalid/v2/anime/21").await.unwrap();
+    assert_eq!(res.status(), 200);
+    assert_eq!(requests.lock().unwrap()[0].0, "http://api.myanimelist.invalid/v2/anime/21");
+}
+
+#[test]
+fn invalid_settings_are_errors() {
+    assert!(ClientConfig::new().root_certificate(b"not a certificate").is_err());
+    assert!(ClientConfig::new().root_certificate_file("tests/fixtures/missing.pem").is_err());
+    assert!(ClientConfig::new().proxy("not a url").is_err());
+}
+
+#[cfg(feature = "jikan")]
+#[tokio::test]
+async fn jikan_uses_the_config() {
+    let (address, requests) = serve(Duration::ZERO);
+    let jikan = mal_query::jikan::JikanClient::new()
+        .config(ClientConfig::new().user_agent("mal-query-tests/1.0"))
+        .unwrap()
+        .base_url(&format!("http://{address}/v4"))
+        .rate_limit(&[])
+        .clone();
+    let anime: serde_json::Value = jikan.get("/anime/21").await.unwrap();
+    assert_eq!(anime["mal_id"], 21);
+    assert_eq!(requests.lock().unwrap()[0], ("/v4/anime/21".to_string(), "mal-query-tests/1.0".to_string()));
+}
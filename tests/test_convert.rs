@@ -0,0 +1,126 @@
+use std::fs;
+use mal_query::convert::{anilist::*, kitsu::*};
+use mal_query::myanimelist::models::*;
+
+fn anime_fixture() -> Vec<MalAnimeData> {
+    let json = fs::read_to_string("tests/fixtures/xml/animelist.json").unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn anilist_scores_convert_between_formats() {
+    assert_eq!(ScoreFormat::Point100.from_mal(8), 80.0);
+    assert_eq!(ScoreFormat::Point100.to_mal(85.0), 9);
+    assert_eq!(ScoreFormat::Point10Decimal.to_mal(7.4), 7);
+    assert_eq!(ScoreFormat::Point5.from_mal(7), 4.0);
+    assert_eq!(ScoreFormat::Point5.to_mal(4.0), 8);
+    assert_eq!(ScoreFormat::Point3.from_mal(6), 2.0);
+    assert_eq!(ScoreFormat::Point3.to_mal(3.0), 9);
+    // Unscored stays unscored in every format
+    for format in [ScoreFormat::Point100, ScoreFormat::Point10Decimal, ScoreFormat::Point10, ScoreFormat::Point5, ScoreFormat::Point3] {
+        assert_eq!(format.from_mal(0), 0.0);
+        assert_eq!(format.to_mal(0.0), 0);
+    }
+}
+
+#[test]
+fn anilist_collection_groups_by_status() {
+    let collection = AniListCollection::from_mal(&anime_fixture(), ScoreFormat::Point100);
+    let groups: Vec<(&str, AniListStatus, usize)> = collection.lists
+        .iter()
+        .map(|l| (l.name.as_str(), l.status, l.entries.len()))
+        .collect();
+    assert_eq!(groups, vec![
+        ("Completed", AniListStatus::Completed, 1),
+        ("Watching", AniListStatus::Current, 1),
+        ("Planning", AniListStatus::Planning, 1),
+    ]);
+    let fmab = &collection.lists[0].entries[0];
+    assert_eq!(fmab.score, 100.0);
+    assert_eq!(fmab.media.id_mal, Some(5114));
+    assert_eq!(fmab.started_at, FuzzyDate { year: Some(2019), month: Some(6), day: None });
+    assert_eq!(fmab.updated_at, Some(1565813462));
+
+    let json: serde_json::Value = serde_json::from_str(&collection.to_json().unwrap()).unwrap();
+    assert_eq!(json["lists"][0]["entries"][0]["completedAt"]["day"], 14);
+    assert_eq!(json["lists"][0]["entries"][0]["media"]["idMal"], 5114);
+}
+
+#[test]
+fn anilist_round_trip_keeps_shared_fields() {
+    let anime = anime_fixture();
+    let json = AniListCollection::from_mal(&anime, ScoreFormat::Point5).to_json().unwrap();
+    let back = AniListCollection::from_json(&json).unwrap().to_mal(ScoreFormat::Point5);
+    assert_eq!(back.len(), 3);
+    let fmab = back.iter().find(|a| a.id == 5114).unwrap();
+    let status = fmab.list_status.as_ref().unwrap();
+    let original = anime[0].list_status.as_ref().unwrap();
+    assert_eq!(fmab.title, "Fullmetal Alchemist: Brotherhood");
+    assert_eq!(status.status, Status::Completed);
+    assert_eq!(status.score, 10);
+    assert_eq!(status.start_date, original.start_date);
+    assert_eq!(status.finish_date, original.finish_date);
+    assert_eq!(status.comments, original.comments);
+    assert_eq!(status.num_times_rewatched, Some(1));
+    assert_eq!(status.updated_at, "2019-08-14T20:11:02+00:00");
+}
+
+#[test]
+fn anilist_repeating_is_rewatching() {
+    let mut status = anime_fixture()[0].list_status.clone().unwrap();
+    status.is_rewatching = true;
+    let entry = AniListEntry::from_list_status(&status, ScoreFormat::Point10);
+    assert_eq!(entry.status, AniListStatus::Repeating);
+    let back = entry.to_list_status(ScoreFormat::Point10);
+    assert_eq!((back.status, back.is_rewatching), (Status::Completed, true));
+    // Entries without a MyAnimeList ID can't be converted back
+    assert!(entry.to_mal(ScoreFormat::Point10).is_err());
+}
+
+#[test]
+fn kitsu_ratings_convert() {
+    assert_eq!(rating_from_mal(0), None);
+    assert_eq!(rating_from_mal(7), Some(14));
+    assert_eq!(rating_to_mal(Some(19)), 10);
+    assert_eq!(rating_to_mal(Some(2)), 1);
+    assert_eq!(rating_to_mal(None), 0);
+}
+
+#[test]
+fn kitsu_library_converts_through_mappings() {
+    let json = fs::read_to_string("tests/fixtures/convert/kitsu_library.json").unwrap();
+    let anime = KitsuLibrary::from_json(&json).unwrap().to_mal();
+    // The third entry has no mapping to MyAnimeList
+    assert_eq!(anime.iter().map(|a| a.id).collect::<Vec<_>>(), vec![5114, 1]);
+
+    let fmab = &anime[0];
+    assert_eq!(fmab.title, "Fullmetal Alchemist: Brotherhood");
+    assert_eq!(fmab.num_episodes, Some(64));
+    let status = fmab.list_status.as_ref().unwrap();
+    assert_eq!(status.status, Status::Completed);
+    assert_eq!(status.score, 10);
+    assert_eq!(status.start_date.as_deref(), Some("2019-06-02"));
+    assert_eq!(status.finish_date.as_deref(), Some("2019-08-14"));
+    assert_eq!(status.comments.as_deref(), Some("Rewatch soon"));
+
+    let bebop = anime[1].list_status.as_ref().unwrap();
+    assert_eq!((bebop.status, bebop.is_rewatching), (Status::Completed, true));
+    assert_eq!(bebop.score, 0);
+}
+
+#[test]
+fn kitsu_round_trip_keeps_shared_fields() {
+    let anime = anime_fixture();
+    let library = KitsuLibrary::from_mal(&anime);
+    assert_eq!(library.data.len(), 3);
+    assert_eq!(library.data[1].attributes.status, KitsuStatus::Current);
+    assert_eq!(library.data[0].attributes.started_at.as_deref(), Some("2019-06-01T00:00:00.000Z"));
+
+    let back = KitsuLibrary::from_json(&library.to_json().unwrap()).unwrap().to_mal();
+    assert_eq!(back.len(), 3);
+    let one_piece = back.iter().find(|a| a.id == 21).unwrap().list_status.as_ref().unwrap();
+    assert_eq!(one_piece.status, Status::Watching);
+    assert_eq!(one_piece.score, 8);
+    assert_eq!(one_piece.num_episodes_watched, 1100);
+    assert_eq!(back[2].list_status.as_ref().unwrap().status, Status::PlanToWatch);
+}
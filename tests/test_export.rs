@@ -0,0 +1,74 @@
+use std::fs;
+use mal_query::myanimelist::{
+    export::{CsvWriter, JsonLinesWriter, ANIME_CSV_COLUMNS},
+    models::*,
+};
+use serde_json::json;
+
+fn anime_fixture() -> Vec<MalAnimeData> {
+    let json = fs::read_to_string("tests/fixtures/xml/animelist.json").unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+fn csv_of(anime: &[MalAnimeData], columns: &[&str]) -> String {
+    let mut csv = CsvWriter::new(Vec::new(), columns);
+    csv.write_all(anime).unwrap();
+    String::from_utf8(csv.finish().unwrap()).unwrap()
+}
+
+#[test]
+fn csv_flattens_nested_fields() {
+    let anime: MalAnimeData = serde_json::from_value(json!({
+        "id": 5114,
+        "title": "Fullmetal Alchemist: Brotherhood",
+        "main_picture": { "medium": "m.jpg", "large": "l.jpg" },
+        "alternative_titles": { "synonyms": ["Hagane no Renkinjutsushi: FA", "FMA:B"], "en": "Fullmetal Alchemist: Brotherhood", "ja": "鋼の錬金術師" },
+        "genres": [{ "id": 1, "name": "Action" }, { "id": 2, "name": "Adventure" }],
+        "studios": [{ "id": 4, "name": "Bones" }],
+        "media_type": "tv",
+        "mean": 9.1,
+    })).unwrap();
+    let csv = csv_of(&[anime], &["id", "alternative_titles.synonyms", "alternative_titles.ja", "genres", "studios", "media_type", "mean", "main_picture.large", "rank"]);
+    assert_eq!(csv, "id,alternative_titles.synonyms,alternative_titles.ja,genres,studios,media_type,mean,main_picture.large,rank\r\n\
+        5114,Hagane no Renkinjutsushi: FA; FMA:B,鋼の錬金術師,Action; Adventure,Bones,tv,9.1,l.jpg,\r\n");
+}
+
+#[test]
+fn csv_quotes_fields() {
+    let csv = csv_of(&anime_fixture()[..1], &["list_status.comments", "list_status.tags", "list_status.start_date"]);
+    let rows: Vec<&str> = csv.split("\r\n").collect();
+    assert_eq!(rows[1], "Best <ending> & more ]]> here,favourites; rewatch,2019-06");
+
+    let mut anime = anime_fixture();
+    anime[0].title = "Say \"hi\", then\nleave".to_string();
+    let csv = csv_of(&anime[..1], &["title"]);
+    assert_eq!(csv, "title\r\n\"Say \"\"hi\"\", then\nleave\"\r\n");
+}
+
+#[test]
+fn csv_default_columns() {
+    let mut out = Vec::new();
+    MalAnimeSearch::new(anime_fixture()).write_csv(&mut out, None).unwrap();
+    let csv = String::from_utf8(out).unwrap();
+    let rows: Vec<&str> = csv.trim_end().split("\r\n").collect();
+    assert_eq!(rows.len(), 5);
+    assert_eq!(rows[0], ANIME_CSV_COLUMNS.join(","));
+    assert!(rows[2].starts_with("21,One Piece,"));
+    assert!(rows[2].ends_with(",watching,8,1100,2024-06-01T10:00:00+00:00"));
+    // Only the header, when there are no rows
+    assert_eq!(csv_of(&[], &["id", "title"]), "id,title\r\n");
+}
+
+#[test]
+fn json_lines_has_one_entry_per_line() {
+    let mut lines = JsonLinesWriter::new(Vec::new());
+    lines.write_all(&anime_fixture()).unwrap();
+    let text = String::from_utf8(lines.finish().unwrap()).unwrap();
+    let entries: Vec<MalAnimeData> = text.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(entries, anime_fixture());
+
+    let mut out = Vec::new();
+    let manga = fs::read_to_string("tests/fixtures/xml/mangalist.json").unwrap();
+    MalMangaSearch::new(serde_json::from_str(&manga).unwrap()).write_json_lines(&mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap().lines().count(), 2);
+}
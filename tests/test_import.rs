@@ -0,0 +1,69 @@
+use std::fs;
+use mal_query::myanimelist::{
+    import::{reconcile, EntryKind, ImportAction},
+    models::*,
+    xml::{anime_list_xml, manga_list_xml, parse_xml},
+};
+
+fn fixture<T: serde::de::DeserializeOwned>(name: &str) -> T {
+    let json = fs::read_to_string(format!("tests/fixtures/xml/{name}")).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn same_list_is_unchanged() {
+    let anime: Vec<MalAnimeData> = fixture("animelist.json");
+    let manga: Vec<MalMangaData> = fixture("mangalist.json");
+    let mut list = parse_xml(&anime_list_xml(&anime, "naginis_api")).unwrap();
+    list.manga = parse_xml(&manga_list_xml(&manga, "naginis_api")).unwrap().manga;
+
+    let plan = reconcile(&list, &anime, &manga);
+    assert_eq!(plan.entries.len(), 5);
+    assert_eq!(plan.unchanged().len(), 5);
+    assert!(plan.entries.iter().all(|e| e.changes.is_empty()));
+}
+
+#[test]
+fn plans_adds_and_updates() {
+    let anime: Vec<MalAnimeData> = fixture("animelist.json");
+    let list = parse_xml(&anime_list_xml(&anime, "naginis_api")).unwrap();
+
+    // The user's list now: One Piece is behind, and Spirited Away isn't on it
+    let mut current: Vec<MalAnimeData> = fixture("animelist.json");
+    let one_piece = current[1].list_status.as_mut().unwrap();
+    one_piece.num_episodes_watched = 1000;
+    one_piece.score = 7;
+    current.remove(2);
+
+    let plan = reconcile(&list, &current, &[]);
+    assert_eq!(plan.unchanged().len(), 1);
+
+    let updates = plan.updates();
+    assert_eq!(updates.len(), 1);
+    assert_eq!((updates[0].kind, updates[0].id), (EntryKind::Anime, 21));
+    let changed: Vec<(&str, Option<&str>, &str)> = updates[0].changes
+        .iter()
+        .map(|c| (c.field.as_str(), c.current.as_deref(), c.new.as_str()))
+        .collect();
+    assert_eq!(changed, vec![("num_watched_episodes", Some("1000"), "1100"), ("score", Some("7"), "8")]);
+
+    let adds = plan.adds();
+    assert_eq!(adds.len(), 1);
+    assert_eq!(adds[0].id, 199);
+    assert_eq!(adds[0].action, ImportAction::Add);
+    assert!(adds[0].changes.iter().all(|c| c.current.is_none()));
+    assert!(adds[0].changes.iter().any(|c| c.field == "status" && c.new == "plan_to_watch"));
+}
+
+#[test]
+fn manga_are_planned_separately() {
+    let manga: Vec<MalMangaData> = fixture("mangalist.json");
+    let list = parse_xml(&manga_list_xml(&manga, "naginis_api")).unwrap();
+    // An anime with the same ID as a manga doesn't count as the manga being listed
+    let anime: Vec<MalAnimeData> = fixture("animelist.json");
+    let plan = reconcile(&list, &anime, &manga[1..]);
+    assert_eq!(plan.adds().len(), 1);
+    assert_eq!(plan.adds()[0].kind, EntryKind::Manga);
+    assert_eq!(plan.adds()[0].title, "Berserk");
+    assert_eq!(plan.unchanged().len(), 1);
+}
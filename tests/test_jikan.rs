@@ -0,0 +1,322 @@
+#![cfg(feature = "jikan")]
+use std::{fs, sync::OnceLock};
+use serde::Deserialize;
+use tiny_http::{Response, Server};
+use mal_query::jikan::{self, JikanClient, JikanPager, models::*};
+use mal_query::myanimelist::models::Season;
+
+// Jikan wraps every response in a `data` field
+#[derive(Deserialize)]
+struct Data<T> {
+    data: T,
+}
+
+fn fixture<T: for<'de> Deserialize<'de>>(name: &str) -> T {
+    let json = fs::read_to_string(format!("tests/fixtures/jikan/{name}.json")).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+static STUB: OnceLock<()> = OnceLock::new();
+
+// Answers this test binary's Jikan requests from the fixtures, with a 404 for any other path.
+// Tests in a binary run at once and share the client, so it is only installed by the first
+fn stub_jikan() {
+    STUB.get_or_init(|| {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/v4", server.server_addr());
+        std::thread::spawn(move || {
+            for request in server.incoming_requests() {
+                let fixture = match request.url() {
+                    "/v4/anime/5114/full" => "anime_full",
+                    "/v4/anime/5114/episodes/1" => "anime_episode",
+                    "/v4/anime/5114/staff" => "anime_staff",
+                    "/v4/anime/5114/news?page=1" => "anime_news",
+                    "/v4/anime/5114/videos" => "anime_videos",
+                    "/v4/anime/5114/pictures" => "anime_pictures",
+                    "/v4/anime/5114/statistics" => "anime_statistics",
+                    "/v4/anime/5114/recommendations" => "anime_recommendations",
+                    "/v4/anime/5114/reviews?page=1" => "anime_reviews",
+                    "/v4/anime/5114/themes" => "anime_themes",
+                    "/v4/anime/5114/external" => "anime_external",
+                    "/v4/manga/25/full" => "manga_full",
+                    "/v4/producers/4/full" => "producer_full",
+                    "/v4/seasons/2009/spring?page=1" => "season",
+                    "/v4/top/anime?page=1&type=movie" => "top_anime_movie",
+                    "/v4/users/Xinil/favorites" => "user_favorites",
+                    _ => {
+                        let _ = request.respond(Response::from_string(r#"{"status":404}"#).with_status_code(404));
+                        continue;
+                    }
+                };
+                let json = fs::read_to_string(format!("tests/fixtures/jikan/{fixture}.json")).unwrap();
+                let _ = request.respond(Response::from_string(json));
+            }
+        });
+        jikan::set_client(JikanClient::new().base_url(&base_url).rate_limit(&[]));
+    });
+}
+
+#[test]
+fn anime_full_fixture_deserializes() {
+    let anime: Data<JikanAnime> = fixture("anime_full");
+    let anime = anime.data;
+    assert_eq!(anime.mal_id, 5114);
+    assert_eq!(anime.media_type, Some("TV".to_string()));
+    assert_eq!(anime.episodes, Some(64));
+    assert_eq!(anime.studios[0].name, "Bones");
+    assert_eq!(anime.relations[0].entry[0].kind, "manga");
+    assert_eq!(anime.theme.unwrap().openings.len(), 1);
+    assert_eq!(anime.streaming[0].name, "Crunchyroll");
+}
+
+#[test]
+fn anime_characters_fixture_deserializes() {
+    let characters: Data<Vec<AnimeCharacter>> = fixture("anime_characters");
+    let edward = &characters.data[0];
+    assert_eq!(edward.character.name, "Elric, Edward");
+    assert_eq!(edward.voice_actors[0].person.mal_id, 81);
+}
+
+#[test]
+fn anime_episodes_fixture_deserializes() {
+    let episodes: JikanPage<Episode> = fixture("anime_episodes");
+    assert!(!episodes.pagination.has_next_page);
+    assert_eq!(episodes.data[0].title, "Fullmetal Alchemist");
+}
+
+#[tokio::test]
+async fn does_get_anime_receive_expected_results() {
+    stub_jikan();
+    let test = jikan::get_anime(5114).await;
+    match test {
+        Err(e) => panic!("{e}"),
+        Ok(data) => {
+            assert_eq!(data.mal_id, 5114);
+            assert_eq!(data.title, "Fullmetal Alchemist: Brotherhood");
+            assert!(!data.relations.is_empty());
+        }
+    }
+}
+
+#[tokio::test]
+async fn does_get_anime_episode_receive_expected_results() {
+    stub_jikan();
+    let test = jikan::get_anime_episode(5114, 1).await;
+    match test {
+        Err(e) => panic!("{e}"),
+        Ok(data) => {
+            assert_eq!(data.mal_id, 1);
+            assert_ne!(data.synopsis, None);
+        }
+    }
+}
+
+#[tokio::test]
+async fn anime_staff_news_and_videos_deserialize() {
+    stub_jikan();
+    let staff = jikan::get_anime_staff(5114).await.unwrap();
+    assert_eq!(staff[0].person.name, "Irie, Yasuhiro");
+    assert_eq!(staff[0].positions[0], "Director");
+
+    let news = jikan::get_anime_news(5114, 1).await.unwrap();
+    assert_eq!(news.data[0].author_username, "Vindstot");
+    assert_eq!(news.data[0].comments, 12);
+
+    let videos = jikan::get_anime_videos(5114).await.unwrap();
+    assert_eq!(videos.promo[0].trailer.youtube_id.as_deref(), Some("--IcmZkvL0Q"));
+    assert_eq!(videos.episodes[0].episode, "Episode 64");
+    assert_eq!(videos.music_videos[0].meta.author.as_deref(), Some("YUI"));
+}
+
+#[tokio::test]
+async fn anime_pictures_statistics_and_recommendations_deserialize() {
+    stub_jikan();
+    let pictures = jikan::get_anime_pictures(5114).await.unwrap();
+    assert_eq!(pictures.len(), 2);
+    assert!(pictures[0].webp.is_some());
+
+    let statistics = jikan::get_anime_statistics(5114).await.unwrap();
+    assert_eq!(statistics.completed, 2812054);
+    assert_eq!(statistics.scores[0], ScoreCount { score: 10, votes: 1192814, percentage: 56.0 });
+
+    let recommendations = jikan::get_anime_recommendations(5114).await.unwrap();
+    assert_eq!(recommendations[0].entry.mal_id, 1535);
+    assert_eq!(recommendations[0].votes, 112);
+}
+
+#[tokio::test]
+async fn anime_reviews_themes_and_external_deserialize() {
+    stub_jikan();
+    let reviews = jikan::get_anime_reviews(5114, 1).await.unwrap();
+    assert!(reviews.pagination.has_next_page);
+    let review = &reviews.data[0];
+    assert_eq!(review.reactions["overall"], 5210);
+    assert_eq!(review.user.username, "Xinil");
+    assert_eq!(review.episodes_watched, None);
+
+    let themes = jikan::get_anime_themes(5114).await.unwrap();
+    assert_eq!(themes.openings.len(), 2);
+    assert_eq!(themes.endings.len(), 1);
+
+    let external = jikan::get_anime_external(5114).await.unwrap();
+    assert_eq!(external[0].name, "Official Site");
+}
+
+#[test]
+fn character_full_fixture_deserializes() {
+    let character: Data<Character> = fixture("character_full");
+    let character = character.data;
+    assert_eq!(character.mal_id, 11);
+    assert_eq!(character.anime[0].anime.mal_id, 5114);
+    assert_eq!(character.manga[0].manga.mal_id, 25);
+    assert_eq!(character.voices[0].person.name, "Park, Romi");
+}
+
+#[test]
+fn person_full_fixture_deserializes() {
+    let person: Data<Person> = fixture("person_full");
+    let person = person.data;
+    assert_eq!(person.name, "Romi Park");
+    assert_eq!(person.anime[0].position, "Theme Song Performance");
+    assert_eq!(person.voices[0].character.mal_id, 11);
+    assert_eq!(person.voices[0].anime.mal_id, 5114);
+}
+
+#[tokio::test]
+async fn does_get_manga_receive_expected_results() {
+    stub_jikan();
+    let test = jikan::get_manga(25).await;
+    match test {
+        Err(e) => panic!("{e}"),
+        Ok(data) => {
+            assert_eq!(data.mal_id, 25);
+            assert!(!data.authors.is_empty());
+        }
+    }
+}
+
+#[tokio::test]
+async fn does_get_producer_receive_expected_results() {
+    stub_jikan();
+    let test = jikan::get_producer(4).await;
+    match test {
+        Err(e) => panic!("{e}"),
+        Ok(data) => {
+            assert_eq!(data.mal_id, 4);
+            assert!(data.titles.iter().any(|title| title.title == "Bones"));
+        }
+    }
+}
+
+// Three pages of two, built in memory, so the pager can be tested without Jikan
+fn numbered_page(page: u32) -> JikanPage<u32> {
+    let json = format!(
+        r#"{{"pagination":{{"last_visible_page":3,"has_next_page":{}}},"data":[{},{}]}}"#,
+        page < 3, page * 2 - 1, page * 2
+    );
+    serde_json::from_str(&json).unwrap()
+}
+
+#[tokio::test]
+async fn pager_reads_every_page_in_order() {
+    let mut requested = Vec::new();
+    let mut pager = JikanPager::new(|page| {
+        requested.push(page);
+        async move { Ok(numbered_page(page)) }
+    });
+    assert_eq!(pager.last_visible_page(), None);
+    assert_eq!(pager.take(3).await.unwrap(), vec![1, 2, 3]);
+    assert_eq!(pager.last_visible_page(), Some(3));
+    assert_eq!(pager.collect_all().await.unwrap(), vec![4, 5, 6]);
+    assert_eq!(pager.next().await.unwrap(), None);
+    assert!(!pager.has_next_page());
+    drop(pager);
+    assert_eq!(requested, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn pager_pages_from_its_start_page() {
+    let mut pager = JikanPager::new(|page| async move { Ok(numbered_page(page)) });
+    pager.start_page(2);
+    let page = pager.next_page().await.unwrap().unwrap();
+    assert_eq!(page.data, vec![3, 4]);
+    assert_eq!(pager.next_page().await.unwrap().unwrap().data, vec![5, 6]);
+    assert!(pager.next_page().await.unwrap().is_none());
+}
+
+#[test]
+fn seasons_list_deserializes() {
+    let json = r#"{"pagination":{"last_visible_page":1,"has_next_page":false},
+        "data":[{"year":2024,"seasons":["winter","spring"]}]}"#;
+    let seasons: Data<Vec<SeasonYear>> = serde_json::from_str(json).unwrap();
+    assert_eq!(seasons.data[0].year, 2024);
+    assert_eq!(seasons.data[0].seasons, vec![Season::Winter, Season::Spring]);
+}
+
+#[tokio::test]
+async fn does_get_top_anime_receive_expected_results() {
+    stub_jikan();
+    let test = jikan::get_top_anime(Some(AnimeType::Movie), None, 1).await;
+    match test {
+        Err(e) => panic!("{e}"),
+        Ok(data) => {
+            assert!(!data.data.is_empty());
+            assert!(data.pagination.has_next_page);
+            assert!(data.data.iter().all(|anime| anime.media_type == Some("Movie".to_string())));
+        }
+    }
+}
+
+#[tokio::test]
+async fn does_get_season_receive_expected_results() {
+    stub_jikan();
+    let test = jikan::get_season(2009, Season::Spring, 1).await;
+    match test {
+        Err(e) => panic!("{e}"),
+        Ok(data) => assert!(data.data.iter().any(|anime| anime.mal_id == 5114)),
+    }
+}
+
+#[test]
+fn user_full_fixture_deserializes() {
+    let user: Data<UserProfile> = fixture("user_full");
+    let user = user.data;
+    assert_eq!(user.username, "Xinil");
+    assert_eq!(user.birthday, None);
+    let statistics = user.statistics.unwrap();
+    assert_eq!(statistics.anime.completed, 301);
+    assert_eq!(statistics.manga.chapters_read, 2104);
+    assert_eq!(user.external[0].name, "Twitter");
+}
+
+#[test]
+fn user_favorites_fixture_deserializes() {
+    let favorites: Data<UserFavorites> = fixture("user_favorites");
+    let favorites = favorites.data;
+    assert_eq!(favorites.anime[0].mal_id, 5114);
+    assert_eq!(favorites.anime[0].start_year, Some(2009));
+    assert_eq!(favorites.characters[0].name, "Elric, Edward");
+    assert!(favorites.manga.is_empty());
+}
+
+#[test]
+fn user_history_and_reviews_fixtures_deserialize() {
+    let history: Data<Vec<HistoryEntry>> = fixture("user_history");
+    assert_eq!(history.data[0].entry.kind, "anime");
+    assert_eq!(history.data[0].increment, 12);
+
+    let reviews: JikanPage<UserReview> = fixture("user_reviews");
+    let review = &reviews.data[0];
+    assert_eq!(review.entry.mal_id, 5114);
+    assert_eq!(review.review.score, 10);
+    assert_eq!(review.review.user.username, "Xinil");
+}
+
+#[tokio::test]
+async fn does_get_user_favorites_receive_expected_results() {
+    stub_jikan();
+    let test = jikan::get_user_favorites("Xinil").await;
+    if let Err(e) = test {
+        panic!("{e}")
+    }
+}
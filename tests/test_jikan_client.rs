@@ -0,0 +1,146 @@
+#![cfg(feature = "jikan")]
+use std::{thread, time::{Duration, Instant}};
+use serde::Deserialize;
+use tiny_http::{Header, Response, Server};
+use mal_query::jikan::{self, JikanClient, RateLimiter};
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Entry {
+    mal_id: u32,
+}
+
+// Serves each response in order, one per request, and returns the base URL and the requested paths
+fn stub(responses: Vec<(u16, Option<&'static str>, &'static str)>) -> (String, thread::JoinHandle<Vec<String>>) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+    let handle = thread::spawn(move || {
+        let mut paths = Vec::new();
+        for (status, retry_after, body) in responses {
+            let request = server.recv().unwrap();
+            paths.push(request.url().to_string());
+            let mut response = Response::from_string(body).with_status_code(status);
+            if let Some(secs) = retry_after {
+                response.add_header(Header::from_bytes("Retry-After", secs).unwrap());
+            }
+            request.respond(response).unwrap();
+        }
+        paths
+    });
+    (base_url, handle)
+}
+
+#[tokio::test]
+async fn client_retries_rate_limits_and_server_errors() {
+    let (base_url, handle) = stub(vec![
+        (429, Some("0"), ""),
+        (503, None, ""),
+        (200, None, r#"{"data":{"mal_id":21}}"#),
+    ]);
+    let mut client = JikanClient::new();
+    client.base_url(&base_url).rate_limit(&[]).backoff(Duration::from_millis(10));
+
+    let entry: Entry = client.get("/anime/21").await.unwrap();
+    assert_eq!(entry, Entry { mal_id: 21 });
+    assert_eq!(handle.join().unwrap(), vec!["/anime/21"; 3]);
+}
+
+#[tokio::test]
+async fn client_gives_up_after_its_retries() {
+    let (base_url, handle) = stub(vec![(500, None, ""), (500, None, "")]);
+    let mut client = JikanClient::new();
+    client.base_url(&base_url).rate_limit(&[]).retries(1).backoff(Duration::from_millis(10));
+
+    assert!(client.get::<Entry>("/anime/21").await.is_err());
+    assert_eq!(handle.join().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn client_does_not_retry_other_errors() {
+    let (base_url, handle) = stub(vec![(404, None, r#"{"status":404}"#)]);
+    let mut client = JikanClient::new();
+    client.base_url(&base_url).rate_limit(&[]);
+
+    assert!(client.get::<Entry>("/anime/0").await.is_err());
+    assert_eq!(handle.join().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn set_client_is_used_by_endpoints() {
+    let (base_url, handle) = stub(vec![(200, None, r#"{"data":{"moreinfo":"Stub"}}"#)]);
+    jikan::set_client(JikanClient::new().base_url(&base_url).rate_limit(&[]));
+
+    let moreinfo = jikan::get_anime_moreinfo(5114).await.unwrap();
+    assert_eq!(moreinfo, Some("Stub".to_string()));
+    assert_eq!(handle.join().unwrap(), vec!["/anime/5114/moreinfo"]);
+}
+
+#[tokio::test]
+async fn client_caps_retry_after_at_the_longest_backoff() {
+    let (base_url, handle) = stub(vec![(429, Some("3600"), ""), (200, None, r#"{"data":{"mal_id":21}}"#)]);
+    let mut client = JikanClient::new();
+    client.base_url(&base_url).rate_limit(&[]).retries(2).backoff(Duration::from_millis(10));
+
+    let start = Instant::now();
+    assert_eq!(client.get::<Entry>("/anime/21").await.unwrap(), Entry { mal_id: 21 });
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert_eq!(handle.join().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn client_retries_connection_errors() {
+    // Nothing listens on the port until after the first attempt is refused
+    let address = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        let server = Server::http(address).unwrap();
+        let request = server.recv().unwrap();
+        request.respond(Response::from_string(r#"{"data":{"mal_id":21}}"#)).unwrap();
+    });
+    let mut client = JikanClient::new();
+    client.base_url(&format!("http://{address}")).rate_limit(&[]).backoff(Duration::from_millis(200));
+
+    assert_eq!(client.get::<Entry>("/anime/21").await.unwrap(), Entry { mal_id: 21 });
+    handle.join().unwrap();
+}
+
+#[tokio::test]
+async fn rate_limiter_waits_for_the_tightest_window() {
+    let limiter = RateLimiter::new(&[(3, Duration::from_millis(300)), (100, Duration::from_secs(60))]);
+    let start = Instant::now();
+    for _ in 0..3 {
+        limiter.acquire().await;
+    }
+    assert!(start.elapsed() < Duration::from_millis(50));
+
+    // The fourth request has to wait for the first to leave the window
+    limiter.acquire().await;
+    assert!(start.elapsed() >= Duration::from_millis(290));
+}
+
+#[tokio::test]
+async fn rate_limiter_never_allows_more_than_a_window() {
+    let limiter = RateLimiter::new(&[(2, Duration::from_millis(300))]);
+    let start = Instant::now();
+    let mut sent = Vec::new();
+    for _ in 0..5 {
+        limiter.acquire().await;
+        sent.push(start.elapsed());
+    }
+    // No span of 300ms has more than 2 requests, so the 3rd waits a window, and the 5th two
+    assert!(sent[1] < Duration::from_millis(50));
+    assert!(sent[2] >= Duration::from_millis(290));
+    assert!(sent[4] >= Duration::from_millis(590));
+    for pair in sent.windows(3) {
+        assert!(pair[2] - pair[0] >= Duration::from_millis(290));
+    }
+}
+
+#[tokio::test]
+async fn rate_limiter_without_limits_never_waits() {
+    let limiter = RateLimiter::new(&[]);
+    let start = Instant::now();
+    for _ in 0..100 {
+        limiter.acquire().await;
+    }
+    assert!(start.elapsed() < Duration::from_millis(50));
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "jikan")]
+use std::thread;
+use tiny_http::{Response, Server};
+use mal_query::jikan::{self, AnimeSearchBuilder, JikanClient, MangaSearchBuilder, models::*};
+use mal_query::myanimelist::models::{Genres, Rating, Season};
+
+const EMPTY_PAGE: &str = r#"{"pagination":{"last_visible_page":1,"has_next_page":false},"data":[]}"#;
+const ANIME: &str = r#"{"data":{"mal_id":1,"url":"","images":{"jpg":{}},"title":"Cowboy Bebop"}}"#;
+const MANGA: &str = r#"{"data":{"mal_id":2,"url":"","images":{"jpg":{}},"title":"Berserk"}}"#;
+
+// Every endpoint shares the one global client, so they're all checked in one test
+#[tokio::test]
+async fn endpoints_request_expected_paths() {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+    let handle = thread::spawn(move || {
+        let mut paths = Vec::new();
+        for request in server.incoming_requests() {
+            let path = request.url().to_string();
+            let body = match path.as_str() {
+                "/random/anime" => ANIME,
+                "/random/manga" => MANGA,
+                "/seasons" | "/users/Xinil/history?type=manga" => r#"{"data":[]}"#,
+                "/users/Xinil/favorites" => r#"{"data":{}}"#,
+                _ => EMPTY_PAGE,
+            };
+            request.respond(Response::from_string(body)).unwrap();
+            paths.push(path);
+            if paths.len() == 18 { break }
+        }
+        paths
+    });
+    jikan::set_client(JikanClient::new().base_url(&base_url).rate_limit(&[]));
+
+    jikan::get_schedules(Some(ScheduleDay::Monday), 1).await.unwrap();
+    jikan::get_schedules(None, 2).await.unwrap();
+    jikan::get_season_now(1).await.unwrap();
+    jikan::get_season_upcoming(3).await.unwrap();
+    jikan::get_season(2009, Season::Spring, 1).await.unwrap();
+    assert!(jikan::get_seasons_list().await.unwrap().is_empty());
+    jikan::get_top_anime(Some(AnimeType::TVSpecial), Some(TopAnimeFilter::ByPopularity), 1).await.unwrap();
+    jikan::get_top_manga(Some(MangaType::LightNovel), None, 2).await.unwrap();
+    jikan::get_top_characters(1).await.unwrap();
+    assert_eq!(jikan::get_random_anime().await.unwrap().title, "Cowboy Bebop");
+    assert_eq!(jikan::get_random_manga().await.unwrap().mal_id, 2);
+    AnimeSearchBuilder::new("fullmetal alchemist")
+        .kind(AnimeType::TV)
+        .start_date("2010")
+        .min_score(8.0)
+        .genres_exclude(&[
+            Genres { id: 12, name: "Hentai".to_string() },
+            Genres { id: 9, name: "Ecchi".to_string() },
+        ])
+        .rating(Rating::PG13)
+        .min_score(8.5)
+        .run()
+        .await
+        .unwrap();
+    MangaSearchBuilder::new("")
+        .status(MangaStatus::Hiatus)
+        .order_by(MangaOrderBy::Chapters)
+        .sort(SortOrder::Descending)
+        .sfw(true)
+        .run_page(4)
+        .await
+        .unwrap();
+    jikan::get_user_favorites("Xinil").await.unwrap();
+    jikan::get_user_history("Xinil", Some(HistoryType::Manga)).await.unwrap();
+    jikan::get_user_friends("Xinil", 2).await.unwrap();
+    jikan::get_magazines("Young & Jump #1+", 1).await.unwrap();
+    jikan::get_magazines("", 2).await.unwrap();
+
+    assert_eq!(handle.join().unwrap(), vec![
+        "/schedules?filter=monday&page=1",
+        "/schedules?page=2",
+        "/seasons/now?page=1",
+        "/seasons/upcoming?page=3",
+        "/seasons/2009/spring?page=1",
+        "/seasons",
+        "/top/anime?page=1&type=tv_special&filter=bypopularity",
+        "/top/manga?page=2&type=lightnovel",
+        "/top/characters?page=1",
+        "/random/anime",
+        "/random/manga",
+        "/anime?q=fullmetal+alchemist&type=tv&start_date=2010&genres_exclude=12%2C9&rating=pg13&min_score=8.5&page=1",
+        "/manga?status=hiatus&order_by=chapters&sort=desc&sfw=true&page=4",
+        "/users/Xinil/favorites",
+        "/users/Xinil/history?type=manga",
+        "/users/Xinil/friends?page=2",
+        "/magazines?q=Young+%26+Jump+%231%2B&page=1",
+        "/magazines?page=2",
+    ]);
+}
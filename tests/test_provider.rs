@@ -0,0 +1,204 @@
+use std::{cell::Cell, error::Error};
+use chrono::NaiveDate;
+use mal_query::myanimelist::models::*;
+use mal_query::provider::{Anime, AnimeProvider, Fallback};
+
+fn anime(id: u32, title: &str) -> Anime {
+    Anime {
+        id,
+        title: title.to_string(),
+        title_english: None,
+        title_japanese: None,
+        synopsis: None,
+        media_type: None,
+        status: None,
+        episodes: None,
+        score: None,
+        rank: None,
+        popularity: None,
+        start_date: None,
+        end_date: None,
+        season: None,
+        year: None,
+        genres: Vec::new(),
+        studios: Vec::new(),
+        image_url: None,
+    }
+}
+
+// A provider with fixed answers, which counts how often it's asked
+struct Stub {
+    anime: Option<Vec<Anime>>,
+    calls: Cell<u32>,
+}
+
+impl Stub {
+    fn new(anime: Option<Vec<Anime>>) -> Self {
+        Stub { anime, calls: Cell::new(0) }
+    }
+    fn list(&self) -> Result<Vec<Anime>, Box<dyn Error>> {
+        self.calls.set(self.calls.get() + 1);
+        self.anime.clone().ok_or("stub failure".into())
+    }
+}
+
+impl AnimeProvider for &Stub {
+    async fn get_anime(&self, id: u32) -> Result<Anime, Box<dyn Error>> {
+        self.list()?.into_iter().find(|a| a.id == id).ok_or("not found".into())
+    }
+    async fn search(&self, _query: &str, _limit: u32) -> Result<Vec<Anime>, Box<dyn Error>> {
+        self.list()
+    }
+    async fn season(&self, _year: u32, _season: Season) -> Result<Vec<Anime>, Box<dyn Error>> {
+        self.list()
+    }
+    async fn rankings(&self, _ranking_type: RankingType, _limit: u32) -> Result<Vec<Anime>, Box<dyn Error>> {
+        self.list()
+    }
+}
+
+fn complete(id: u32, title: &str) -> Anime {
+    Anime {
+        title_english: Some(title.to_string()),
+        title_japanese: Some("ja".to_string()),
+        synopsis: Some("synopsis".to_string()),
+        media_type: Some(AnimeMediaType::TV),
+        status: Some(AiringStatus::FinishedAiring),
+        episodes: Some(12),
+        score: Some(8.0),
+        rank: Some(1),
+        popularity: Some(1),
+        start_date: NaiveDate::from_ymd_opt(2009, 4, 5),
+        end_date: NaiveDate::from_ymd_opt(2010, 7, 4),
+        season: Some(Season::Spring),
+        year: Some(2009),
+        genres: vec!["Action".to_string()],
+        studios: vec!["Bones".to_string()],
+        image_url: Some("image".to_string()),
+        ..anime(id, title)
+    }
+}
+
+#[tokio::test]
+async fn fallback_uses_secondary_when_primary_fails() {
+    let primary = Stub::new(None);
+    let secondary = Stub::new(Some(vec![complete(1, "Secondary")]));
+    let provider = Fallback::new(&primary, &secondary);
+    assert_eq!(provider.get_anime(1).await.unwrap().title, "Secondary");
+    assert_eq!(provider.search("", 10).await.unwrap()[0].title, "Secondary");
+}
+
+#[tokio::test]
+async fn fallback_skips_secondary_when_primary_is_complete() {
+    let primary = Stub::new(Some(vec![complete(1, "Primary")]));
+    let secondary = Stub::new(Some(vec![complete(1, "Secondary")]));
+    let provider = Fallback::new(&primary, &secondary);
+    assert_eq!(provider.get_anime(1).await.unwrap().title, "Primary");
+    assert_eq!(provider.rankings(RankingType::All, 1).await.unwrap()[0].title, "Primary");
+    assert_eq!(secondary.calls.get(), 0);
+}
+
+#[tokio::test]
+async fn fallback_fills_missing_fields_by_id() {
+    let mut partial = anime(1, "Primary");
+    partial.score = Some(9.1);
+    let primary = Stub::new(Some(vec![partial, complete(2, "Primary two")]));
+    let secondary = Stub::new(Some(vec![complete(2, "Secondary two"), complete(1, "Secondary")]));
+    let provider = Fallback::new(&primary, &secondary);
+
+    let list = provider.season(2009, Season::Spring).await.unwrap();
+    assert_eq!(secondary.calls.get(), 1);
+    // The primary's values win, and only missing fields are filled
+    assert_eq!(list[0].title, "Primary");
+    assert_eq!(list[0].score, Some(9.1));
+    assert_eq!(list[0].studios, vec!["Bones"]);
+    assert_eq!(list[1].title, "Primary two");
+}
+
+#[tokio::test]
+async fn fallback_keeps_partial_result_when_secondary_fails() {
+    let primary = Stub::new(Some(vec![anime(1, "Primary")]));
+    let secondary = Stub::new(None);
+    let provider = Fallback::new(&primary, &secondary);
+    let result = provider.get_anime(1).await.unwrap();
+    assert_eq!(result, anime(1, "Primary"));
+}
+
+#[test]
+fn mal_anime_data_converts_to_anime() {
+    let json = r#"{
+        "id": 5114,
+        "title": "Fullmetal Alchemist: Brotherhood",
+        "main_picture": { "large": "https://cdn.myanimelist.net/images/anime/1208/94745l.jpg", "medium": "" },
+        "alternative_titles": { "synonyms": [], "en": "Fullmetal Alchemist: Brotherhood", "ja": "" },
+        "start_date": "2009-04-05",
+        "end_date": "2010",
+        "mean": 9.1,
+        "media_type": "tv",
+        "status": "finished_airing",
+        "num_episodes": 64,
+        "start_season": { "year": 2009, "season": "spring" },
+        "genres": [{ "id": 1, "name": "Action" }]
+    }"#;
+    let data: MalAnimeData = serde_json::from_str(json).unwrap();
+    let anime = Anime::from(data);
+    assert_eq!(anime.title_english.as_deref(), Some("Fullmetal Alchemist: Brotherhood"));
+    assert_eq!(anime.title_japanese, None);
+    assert_eq!(anime.start_date, NaiveDate::from_ymd_opt(2009, 4, 5));
+    assert_eq!(anime.end_date, None);
+    assert_eq!(anime.media_type, Some(AnimeMediaType::TV));
+    assert_eq!(anime.season, Some(Season::Spring));
+    assert_eq!(anime.genres, vec!["Action"]);
+    assert!(anime.studios.is_empty());
+    assert!(anime.is_missing_fields());
+}
+
+#[cfg(feature = "jikan")]
+#[test]
+fn jikan_anime_converts_to_anime() {
+    use mal_query::jikan::models::JikanAnime;
+    #[derive(serde::Deserialize)]
+    struct Data {
+        data: JikanAnime,
+    }
+    let json = std::fs::read_to_string("tests/fixtures/jikan/anime_full.json").unwrap();
+    let data: Data = serde_json::from_str(&json).unwrap();
+    let anime = Anime::from(data.data);
+    assert_eq!(anime.id, 5114);
+    assert_eq!(anime.media_type, Some(AnimeMediaType::TV));
+    assert_eq!(anime.status, Some(AiringStatus::FinishedAiring));
+    assert_eq!(anime.episodes, Some(64));
+    assert_eq!(anime.start_date, NaiveDate::from_ymd_opt(2009, 4, 5));
+    assert_eq!(anime.studios, vec!["Bones"]);
+}
+
+#[tokio::test]
+async fn complete_mal_entries_make_no_secondary_call() {
+    use mal_query::myanimelist::{self, client::{MalClient, RetryPolicy}, transport::MockTransport};
+    use mal_query::provider::MyAnimeList;
+    // An airing show has no end date or final episode count, and an unranked one no rank, but neither is missing them
+    let body = r#"{ "data": [
+        { "node": {
+            "id": 52991, "title": "Sousou no Frieren",
+            "main_picture": { "medium": "", "large": "https://cdn.myanimelist.net/images/anime/1015/138006l.jpg" },
+            "alternative_titles": { "synonyms": [], "en": "Frieren: Beyond Journey's End", "ja": "葬送のフリーレン" },
+            "start_date": "2023-09-29", "synopsis": "synopsis", "mean": 9.1, "popularity": 200,
+            "media_type": "tv", "status": "currently_airing", "num_episodes": 0,
+            "start_season": { "year": 2023, "season": "fall" },
+            "genres": [{ "id": 2, "name": "Adventure" }], "studios": [{ "id": 11, "name": "Madhouse" }]
+        } }
+    ] }"#;
+    let mock = MockTransport::new();
+    mock.respond("GET", "/v2/anime?q=frieren", 200, body);
+    myanimelist::set_client(MalClient::new().transport(&mock).retry(&RetryPolicy::none()));
+    myanimelist::set_token("test_token");
+
+    let secondary = Stub::new(Some(vec![complete(52991, "Secondary")]));
+    let provider = Fallback::new(MyAnimeList, &secondary);
+    let list = provider.search("frieren", 1).await.unwrap();
+    assert_eq!(list[0].studios, vec!["Madhouse"]);
+    assert_eq!(list[0].end_date, None);
+    assert_eq!(list[0].rank, None);
+    assert_eq!(secondary.calls.get(), 0);
+    assert!(mock.requests()[0].query["fields"].contains("studios"));
+}
@@ -1,10 +1,15 @@
 use mal_query::myanimelist::{retrieval::*, models::{Season, RankingType}};
 
+mod common;
+use common::mock_mal;
+
 #[tokio::test]
 async fn does_search_anime_receive_expected_results() {
+    let mock = mock_mal("retrieval");
     let test = search_anime("jujutsu", 3).await;
+    assert_eq!(mock.requests_to("GET", "/v2/anime")[0].query["limit"], "3");
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             assert_eq!(data.data.len(), 3);
             let s = data.titles();
@@ -18,9 +23,11 @@ async fn does_search_anime_receive_expected_results() {
 
 #[tokio::test]
 async fn does_get_season_receive_expected_results() {
+    let mock = mock_mal("retrieval");
     let test = get_season(2023, Season::Fall).await;
+    assert_eq!(mock.requests_to("GET", "/v2/anime/season/2023/fall")[0].query["limit"], "500");
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             let s = data.to_titles();
             println!("{s:?}");
@@ -41,9 +48,11 @@ async fn does_get_season_receive_expected_results() {
 
 #[tokio::test]
 async fn does_get_anime_recieve_expected_results() {
+    let mock = mock_mal("retrieval");
     let test = get_anime(22199).await;
+    assert!(mock.requests_to("GET", "/v2/anime/22199")[0].query["fields"].contains("start_season"));
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             assert_eq!(data.id, 22199);
             assert_eq!(data.title, "Akame ga Kill!");
@@ -57,9 +66,11 @@ async fn does_get_anime_recieve_expected_results() {
 
 #[tokio::test]
 async fn does_get_anime_url_receieve_expected_results() {
+    let mock = mock_mal("retrieval");
     let test = get_anime_from_url("https://myanimelist.net/anime/6594/Katanagatari").await;
+    assert_eq!(mock.requests_to("GET", "/v2/anime/6594").len(), 1);
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             assert_eq!(data.id, 6594);
             assert_eq!(data.title, "Katanagatari");
@@ -73,9 +84,11 @@ async fn does_get_anime_url_receieve_expected_results() {
 
 #[tokio::test]
 async fn does_anime_ranking_receive_expected_results() {
+    let mock = mock_mal("retrieval");
     let test = get_anime_rankings(RankingType::All, 10).await;
+    assert_eq!(mock.requests_to("GET", "/v2/anime/ranking")[0].query["ranking_type"], "all");
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             // Due to the nature of the rankings changing, I believe I can only check this
             let mut rank = 1;
@@ -89,13 +102,15 @@ async fn does_anime_ranking_receive_expected_results() {
 
 #[tokio::test]
 async fn does_get_user_animelist_receieve_expected_results() {
+    let mock = mock_mal("retrieval");
     let test = get_user_animelist("naginis_api", 50).await;
+    assert_eq!(mock.requests_to("GET", "/v2/users/naginis_api/animelist")[0].query["limit"], "50");
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             let akame = data.data.iter().find(|anime| anime.title == "Akame ga Kill!");
             match akame {
-                None => assert!(false),
+                None => panic!("Anime was not in the list"),
                 Some(result) => {
                     assert_eq!(result.title, "Akame ga Kill!");
                     assert_eq!(result.list_status.as_ref().unwrap().score, 10);
@@ -1,10 +1,15 @@
 use mal_query::myanimelist::{retrieval::*, models::{MangaMediaType, MangaRankingType}};
 
+mod common;
+use common::mock_mal;
+
 #[tokio::test]
 async fn does_search_manga_receive_expected_results() {
+    let mock = mock_mal("retrieval_manga");
     let test = search_manga("naruto", 3).await;
+    assert_eq!(mock.requests_to("GET", "/v2/manga")[0].query["q"], "naruto");
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             assert_eq!(data.data.len(), 3);
             let s = data.titles();
@@ -18,9 +23,11 @@ async fn does_search_manga_receive_expected_results() {
 
 #[tokio::test]
 async fn does_get_manga_recieve_expected_results() {
+    let mock = mock_mal("retrieval_manga");
     let test = get_manga(115922).await;
+    assert!(mock.requests_to("GET", "/v2/manga/115922")[0].query["fields"].contains("media_type"));
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             assert_eq!(data.id, 115922);
             assert_eq!(data.title, "Kage no Jitsuryokusha ni Naritakute!");
@@ -31,9 +38,11 @@ async fn does_get_manga_recieve_expected_results() {
 
 #[tokio::test]
 async fn does_get_manga_url_receieve_expected_results() {
+    let mock = mock_mal("retrieval_manga");
     let test = get_manga_from_url("https://myanimelist.net/manga/98971/Suki_x_Suki").await;
+    assert_eq!(mock.requests_to("GET", "/v2/manga/98971").len(), 1);
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             assert_eq!(data.id, 98971);
             assert_eq!(data.title, "Suki x Suki");
@@ -47,9 +56,11 @@ async fn does_get_manga_url_receieve_expected_results() {
 
 #[tokio::test]
 async fn does_manga_ranking_receive_expected_results() {
+    let mock = mock_mal("retrieval_manga");
     let test = get_manga_rankings(MangaRankingType::All, 10).await;
+    assert_eq!(mock.requests_to("GET", "/v2/manga/ranking")[0].query["ranking_type"], "all");
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             // Due to the nature of the rankings changing, I believe I can only check this
             let mut rank = 1;
@@ -63,13 +74,15 @@ async fn does_manga_ranking_receive_expected_results() {
 
 #[tokio::test]
 async fn does_get_user_mangalist_receieve_expected_results() {
+    let mock = mock_mal("retrieval_manga");
     let test = get_user_mangalist("naginis_api", 50).await;
+    assert_eq!(mock.requests_to("GET", "/v2/users/naginis_api/mangalist")[0].query["limit"], "50");
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             let akame = data.data.iter().find(|manga| manga.title == "Akame ga Kill!");
             match akame {
-                None => assert!(false),
+                None => panic!("Manga was not in the list"),
                 Some(result) => {
                     assert_eq!(result.title, "Akame ga Kill!");
                     assert_eq!(result.list_status.as_ref().unwrap().score, 10);
@@ -0,0 +1,181 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use mal_query::myanimelist::{
+    self,
+    client::{MalClient, RetryPolicy},
+    models::Status,
+    user::{delete_anime, UpdateAnime},
+};
+use tiny_http::{Header, Response, Server};
+
+// Each request's method and path, and when it was received
+type Received = Arc<Mutex<Vec<(String, String, Instant)>>>;
+
+// A server that answers with the scripted statuses in order, with a `Retry-After` when one is given,
+// then 200 with `body` once the script runs out. Returns its API URL, and every request it received
+fn serve_api(script: Vec<(u16, Option<&'static str>)>, body: &'static str) -> (String, Received) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let api_url = format!("http://{}/v2", server.server_addr());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let received = requests.clone();
+    std::thread::spawn(move || {
+        let mut script = script.into_iter();
+        for request in server.incoming_requests() {
+            received.lock().unwrap().push((request.method().to_string(), request.url().to_string(), Instant::now()));
+            let response = match script.next() {
+                Some((status, Some(retry_after))) => Response::from_string("{}")
+                    .with_status_code(status)
+                    .with_header(Header::from_bytes("Retry-After", retry_after).unwrap()),
+                Some((status, None)) => Response::from_string("{}").with_status_code(status),
+                None => Response::from_string(body),
+            };
+            let _ = request.respond(response);
+        }
+    });
+    (api_url, requests)
+}
+
+// The same, for reads of `/v2/anime/21`. Returns its URL
+fn serve(script: Vec<(u16, Option<&'static str>)>) -> (String, Received) {
+    let (api_url, requests) = serve_api(script, "{\"id\":21}");
+    (format!("{api_url}/anime/21"), requests)
+}
+
+fn quick_retries(max_attempts: u32) -> MalClient {
+    MalClient::new()
+        .retry(RetryPolicy::new()
+            .max_attempts(max_attempts)
+            .backoff(Duration::from_millis(10), Duration::from_millis(40))
+            .jitter(false)
+        )
+        .clone()
+}
+
+#[tokio::test]
+async fn retries_until_success() {
+    let (url, requests) = serve(vec![(503, None), (429, None), (502, None)]);
+    let res = quick_retries(4).get(&url).await.unwrap();
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.text().await.unwrap(), "{\"id\":21}");
+    assert_eq!(requests.lock().unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn gives_up_after_max_attempts() {
+    let (url, requests) = serve(vec![(500, None), (502, None), (504, None)]);
+    let res = quick_retries(2).get(&url).await.unwrap();
+    assert_eq!(res.status(), 502);
+    assert_eq!(requests.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn other_statuses_are_not_retried() {
+    let (url, requests) = serve(vec![(404, None)]);
+    assert_eq!(quick_retries(3).get(&url).await.unwrap().status(), 404);
+    assert_eq!(requests.lock().unwrap().len(), 1);
+
+    // Unless they are added
+    let (url, requests) = serve(vec![(404, None)]);
+    let client = MalClient::new()
+        .retry(RetryPolicy::new().retry_on(&[404]).backoff(Duration::ZERO, Duration::ZERO))
+        .clone();
+    assert_eq!(client.get(&url).await.unwrap().status(), 200);
+    assert_eq!(requests.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn forbidden_is_only_retried_when_added() {
+    let (url, requests) = serve(vec![(403, None)]);
+    assert_eq!(quick_retries(3).get(&url).await.unwrap().status(), 403);
+    assert_eq!(requests.lock().unwrap().len(), 1);
+
+    let (url, requests) = serve(vec![(403, None)]);
+    let client = MalClient::new()
+        .retry(RetryPolicy::new().retry_on(&[403, 429]).backoff(Duration::ZERO, Duration::ZERO))
+        .clone();
+    assert_eq!(client.get(&url).await.unwrap().status(), 200);
+    assert_eq!(requests.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn retry_after_is_honored() {
+    let (url, requests) = serve(vec![(429, Some("1"))]);
+    let client = MalClient::new()
+        .retry(RetryPolicy::new().backoff(Duration::from_millis(10), Duration::from_secs(2)))
+        .clone();
+    assert_eq!(client.get(&url).await.unwrap().status(), 200);
+    let requests = requests.lock().unwrap();
+    assert!(requests[1].2 - requests[0].2 >= Duration::from_millis(950));
+}
+
+#[tokio::test]
+async fn retry_after_is_capped_at_the_longest_backoff() {
+    let (url, requests) = serve(vec![(503, Some("3600"))]);
+    // The longest backoff is 40ms, so the hour MyAnimeList asked for isn't waited
+    assert_eq!(quick_retries(2).get(&url).await.unwrap().status(), 200);
+    let requests = requests.lock().unwrap();
+    assert!(requests[1].2 - requests[0].2 < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn writes_are_retried() {
+    let status = r#"{"status":"watching","score":0,"num_episodes_watched":4,"is_rewatching":false,"updated_at":"2024-01-04T10:00:00+00:00"}"#;
+    let (api_url, requests) = serve_api(vec![(429, None)], status);
+    myanimelist::set_client(quick_retries(3).api_url(&api_url));
+    myanimelist::set_token("test_token");
+
+    let updated = UpdateAnime::new(21)
+        .update_status(Status::Watching)
+        .update_num_watched_episodes(4)
+        .update()
+        .await
+        .unwrap();
+    assert_eq!(updated.num_episodes_watched, 4);
+    let sent: Vec<(String, String)> = requests.lock().unwrap().iter().map(|(m, p, _)| (m.clone(), p.clone())).collect();
+    let put = ("PUT".to_string(), "/v2/anime/21/my_list_status".to_string());
+    assert_eq!(sent, vec![put.clone(), put]);
+
+    // Deletes are retried the same way
+    let (api_url, requests) = serve_api(vec![(503, None)], "[]");
+    myanimelist::set_client(quick_retries(3).api_url(&api_url));
+    delete_anime(21).await.unwrap();
+    assert_eq!(requests.lock().unwrap().iter().filter(|(method, _, _)| method == "DELETE").count(), 2);
+
+    // A 403 on a write is a refused token, so it isn't retried even when the policy adds it
+    let (api_url, requests) = serve_api(vec![(403, None)], status);
+    myanimelist::set_client(MalClient::new()
+        .api_url(&api_url)
+        .retry(RetryPolicy::new().retry_on(&[403]).backoff(Duration::ZERO, Duration::ZERO))
+    );
+    assert!(UpdateAnime::new(21).update_status(Status::Watching).update().await.is_err());
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn requests_are_rate_limited() {
+    let (url, requests) = serve(vec![]);
+    let client = MalClient::new().rate_limit(&[(2, Duration::from_millis(500))]).clone();
+    for _ in 0..3 { client.get(&url).await.unwrap(); }
+    let requests = requests.lock().unwrap();
+    // The first two are sent at once, and the third waits for the first to leave the window
+    assert!(requests[1].2 - requests[0].2 < Duration::from_millis(100));
+    assert!(requests[2].2 - requests[0].2 >= Duration::from_millis(450));
+}
+
+#[test]
+fn backoff_doubles_up_to_the_limit() {
+    let mut policy = RetryPolicy::new();
+    policy.backoff(Duration::from_millis(100), Duration::from_millis(500)).jitter(false);
+    let delays: Vec<u128> = (1..=5).map(|attempt| policy.delay(attempt).as_millis()).collect();
+    assert_eq!(delays, vec![100, 200, 400, 500, 500]);
+
+    policy.jitter(true);
+    for attempt in 1..=5 {
+        let delay = policy.delay(attempt);
+        let full = Duration::from_millis([100, 200, 400, 500, 500][attempt as usize - 1]);
+        assert!(delay >= full / 2 && delay <= full);
+    }
+    assert_eq!(RetryPolicy::none().get_max_attempts(), 1);
+}
@@ -0,0 +1,217 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+use mal_query::myanimelist::{
+    self,
+    builders::UserListBuilder,
+    client::{MalClient, RetryPolicy},
+    models::*,
+    sync::*,
+};
+use serde_json::json;
+use tiny_http::{Response, Server};
+
+fn entry(id: u32, title: &str, status: &str, watched: u32, updated_at: &str) -> MalAnimeData {
+    serde_json::from_value(json!({
+        "id": id,
+        "title": title,
+        "main_picture": { "medium": "", "large": "" },
+        "list_status": {
+            "status": status,
+            "score": 0,
+            "num_episodes_watched": watched,
+            "is_rewatching": false,
+            "updated_at": updated_at,
+        },
+    })).unwrap()
+}
+
+fn snapshot(entries: &[MalAnimeData]) -> SyncSnapshot {
+    SyncSnapshot {
+        entries: entries.iter().map(|a| (a.id, a.list_status.clone().unwrap())).collect(),
+    }
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("mal_query_sync_{}_{name}", std::process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+const OLD: &str = "2024-01-01T00:00:00+00:00";
+const NEW: &str = "2024-02-01T00:00:00+00:00";
+
+#[test]
+fn one_sided_changes_are_copied() {
+    let base = snapshot(&[entry(1, "Frieren", "watching", 3, OLD), entry(2, "Mushishi", "watching", 5, OLD)]);
+    // A watched more Frieren, B removed Mushishi and added Monster
+    let a = vec![entry(1, "Frieren", "watching", 4, NEW), entry(2, "Mushishi", "watching", 5, OLD)];
+    let b = vec![entry(1, "Frieren", "watching", 3, OLD), entry(3, "Monster", "plan_to_watch", 0, NEW)];
+
+    let plan = plan(&a, &b, &base, ConflictPolicy::Manual);
+    assert!(plan.conflicts.is_empty());
+    assert_eq!(plan.changes.len(), 3);
+    assert_eq!((plan.changes[0].id, plan.changes[0].target), (1, Side::B));
+    assert_eq!(plan.changes[0].action, SyncAction::Set(a[0].list_status.clone().unwrap()));
+    assert_eq!((plan.changes[1].id, plan.changes[1].target, &plan.changes[1].action), (2, Side::A, &SyncAction::Delete));
+    assert_eq!((plan.changes[2].id, plan.changes[2].target), (3, Side::A));
+    assert_eq!(plan.changes[2].title, "Monster");
+}
+
+#[test]
+fn updated_at_alone_is_not_a_change() {
+    let base = snapshot(&[entry(1, "Frieren", "watching", 3, OLD)]);
+    let a = vec![entry(1, "Frieren", "watching", 3, NEW)];
+    let b = vec![entry(1, "Frieren", "watching", 3, OLD)];
+    let plan = plan(&a, &b, &base, ConflictPolicy::NewestWins);
+    assert!(plan.changes.is_empty());
+    assert!(plan.conflicts.is_empty());
+}
+
+#[test]
+fn conflicts_follow_the_policy() {
+    let base = snapshot(&[entry(1, "Frieren", "watching", 3, OLD)]);
+    let a = vec![entry(1, "Frieren", "watching", 5, OLD)];
+    let b = vec![entry(1, "Frieren", "completed", 28, NEW)];
+
+    let newest = plan(&a, &b, &base, ConflictPolicy::NewestWins);
+    assert_eq!(newest.changes.len(), 1);
+    assert_eq!(newest.changes[0].target, Side::A);
+    assert_eq!(newest.changes[0].action, SyncAction::Set(b[0].list_status.clone().unwrap()));
+
+    assert_eq!(plan(&a, &b, &base, ConflictPolicy::PreferA).changes[0].target, Side::B);
+    assert_eq!(plan(&a, &b, &base, ConflictPolicy::PreferB).changes[0].target, Side::A);
+
+    let mut manual = plan(&a, &b, &base, ConflictPolicy::Manual);
+    assert!(manual.changes.is_empty());
+    assert_eq!(manual.conflicts.len(), 1);
+    assert_eq!(manual.conflicts[0].base, base.entries.get(&1).cloned());
+    assert!(!manual.resolve(2, Side::A));
+    assert!(manual.resolve(1, Side::A));
+    assert!(manual.conflicts.is_empty());
+    assert_eq!(manual.changes[0].target, Side::B);
+}
+
+#[test]
+fn newest_wins_keeps_a_change_over_a_delete() {
+    let base = snapshot(&[entry(1, "Frieren", "watching", 3, NEW)]);
+    let a = vec![entry(1, "Frieren", "watching", 4, OLD)];
+    let plan = plan(&a, &[], &base, ConflictPolicy::NewestWins);
+    assert_eq!(plan.changes.len(), 1);
+    assert_eq!(plan.changes[0].target, Side::B);
+    assert!(matches!(plan.changes[0].action, SyncAction::Set(_)));
+}
+
+#[tokio::test]
+async fn syncs_two_files() {
+    let json_path = temp_path("list.json");
+    let xml_path = temp_path("list.xml");
+    let snapshot_path = temp_path("snapshot.json");
+    let list = vec![entry(1, "Frieren", "watching", 3, OLD), entry(2, "Mushishi", "completed", 26, OLD)];
+    fs::write(&json_path, serde_json::to_string(&list).unwrap()).unwrap();
+
+    // The first sync copies everything into the XML file, which doesn't exist yet
+    let mut a = FileSource::new(&json_path);
+    let mut b = FileSource::new(&xml_path);
+    let report = sync(&mut a, &mut b, &snapshot_path, ConflictPolicy::NewestWins).await.unwrap();
+    assert!(report.is_success());
+    assert_eq!(report.successes(), vec![(Side::B, 1), (Side::B, 2)]);
+    assert_eq!(SyncSnapshot::load(&snapshot_path).unwrap(), report.snapshot);
+    assert!(fs::read_to_string(&xml_path).unwrap().contains("<series_title><![CDATA[Frieren]]></series_title>"));
+
+    // Removing an entry from the XML file removes it from the JSON file on the next sync
+    let xml = fs::read_to_string(&xml_path).unwrap();
+    let start = xml.find("\t<anime>").unwrap();
+    let end = xml.find("\t</anime>").unwrap() + "\t</anime>\n".len();
+    fs::write(&xml_path, format!("{}{}", &xml[..start], &xml[end..])).unwrap();
+    let report = sync(&mut a, &mut b, &snapshot_path, ConflictPolicy::NewestWins).await.unwrap();
+    assert_eq!(report.successes(), vec![(Side::A, 1)]);
+    let json: Vec<MalAnimeData> = serde_json::from_str(&fs::read_to_string(&json_path).unwrap()).unwrap();
+    assert_eq!(json.iter().map(|a| a.id).collect::<Vec<u32>>(), vec![2]);
+
+    // Nothing changed since, so nothing is done
+    let report = sync(&mut a, &mut b, &snapshot_path, ConflictPolicy::NewestWins).await.unwrap();
+    assert!(report.results.is_empty());
+    assert_eq!(report.snapshot.entries.keys().collect::<Vec<_>>(), vec![&2]);
+
+    for path in [json_path, xml_path, snapshot_path] { let _ = fs::remove_file(path); }
+}
+
+// A MyAnimeList stub for the logged in user's list, which changes the list as updates and deletes are received.
+// Returns its API URL
+fn serve_mal(mut list: Vec<MalAnimeData>) -> String {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/v2", server.server_addr());
+    std::thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            // Such as /v2/anime/1/my_list_status, or /v2/users/@me/animelist
+            let path = request.url().split('?').next().unwrap_or_default().to_string();
+            let id = path.split('/').nth(3).and_then(|id| id.parse::<u32>().ok());
+            let body = match (request.method().as_str(), id) {
+                ("GET", None) => json!({
+                    "data": list.iter().map(|a| json!({
+                        "node": { "id": a.id, "title": a.title, "main_picture": a.main_picture },
+                        "list_status": a.list_status,
+                    })).collect::<Vec<_>>(),
+                    "paging": {},
+                }),
+                ("PUT", Some(id)) => {
+                    let mut form = String::new();
+                    request.as_reader().read_to_string(&mut form).unwrap();
+                    let form: HashMap<String, String> = url::form_urlencoded::parse(form.as_bytes()).into_owned().collect();
+                    let status = json!({
+                        "status": form["status"],
+                        "score": form["score"].parse::<u32>().unwrap(),
+                        "num_episodes_watched": form["num_watched_episodes"].parse::<u32>().unwrap(),
+                        "is_rewatching": form["is_rewatching"] == "true",
+                        "updated_at": NEW,
+                    });
+                    let mut updated = MalAnimeData { id, ..Default::default() };
+                    updated.list_status = Some(serde_json::from_value(status.clone()).unwrap());
+                    list.retain(|a| a.id != id);
+                    list.push(updated);
+                    status
+                },
+                ("DELETE", Some(id)) => {
+                    list.retain(|a| a.id != id);
+                    json!([])
+                },
+                _ => json!({ "error": "not_found" }),
+            };
+            let _ = request.respond(Response::from_string(body.to_string()));
+        }
+    });
+    url
+}
+
+#[tokio::test]
+async fn syncs_a_file_into_a_mal_account() {
+    let api_url = serve_mal(vec![entry(1, "Frieren", "watching", 3, OLD), entry(2, "Mushishi", "watching", 5, OLD)]);
+    myanimelist::set_client(MalClient::new().api_url(&api_url).retry(&RetryPolicy::none()));
+    myanimelist::set_token("test_token");
+
+    let json_path = temp_path("account.json");
+    let snapshot_path = temp_path("account_snapshot.json");
+    let base = [entry(1, "Frieren", "watching", 3, OLD), entry(2, "Mushishi", "watching", 5, OLD)];
+    snapshot(&base).save(&snapshot_path).unwrap();
+    // Since the last sync, the file watched more of 1, removed 2 and added 3
+    let file = vec![entry(1, "Frieren", "watching", 5, NEW), entry(3, "Monster", "plan_to_watch", 0, NEW)];
+    fs::write(&json_path, serde_json::to_string(&file).unwrap()).unwrap();
+
+    let report = sync(&mut MalAccount, &mut FileSource::new(&json_path), &snapshot_path, ConflictPolicy::NewestWins)
+        .await
+        .unwrap();
+    assert!(report.is_success());
+    assert_eq!(report.successes(), vec![(Side::A, 1), (Side::A, 2), (Side::A, 3)]);
+
+    let account = UserListBuilder::new("@me").include_list_status().run().await.unwrap();
+    let mut entries: Vec<(u32, Status, u32)> = account.data
+        .iter()
+        .map(|a| {
+            let status = a.list_status.as_ref().unwrap();
+            (a.id, status.status, status.num_episodes_watched)
+        })
+        .collect();
+    entries.sort_by_key(|(id, _, _)| *id);
+    assert_eq!(entries, vec![(1, Status::Watching, 5), (3, Status::PlanToWatch, 0)]);
+
+    for path in [json_path, snapshot_path] { let _ = fs::remove_file(path); }
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "tui")]
+use mal_query::myanimelist::models::*;
+use mal_query::tui::{App, Change, Edit, ListSort};
+use ratatui::{backend::TestBackend, crossterm::event::KeyCode, Terminal};
+use serde_json::json;
+
+fn entry(id: u32, title: &str, status: &str, score: u32, watched: u32, updated_at: &str) -> MalAnimeData {
+    serde_json::from_value(json!({
+        "id": id,
+        "title": title,
+        "main_picture": { "medium": "", "large": "" },
+        "num_episodes": 12,
+        "list_status": {
+            "status": status,
+            "score": score,
+            "num_episodes_watched": watched,
+            "is_rewatching": false,
+            "updated_at": updated_at,
+        },
+    })).unwrap()
+}
+
+fn app() -> App {
+    App::new(vec![
+        entry(1, "Low", "watching", 5, 3, "2024-03-01T00:00:00+00:00"),
+        entry(2, "High", "completed", 9, 12, "2024-01-01T00:00:00+00:00"),
+        entry(3, "Middle", "watching", 7, 0, "2024-02-01T00:00:00+00:00"),
+    ])
+}
+
+fn titles(app: &App) -> Vec<&str> {
+    app.visible().iter().map(|a| a.title.as_str()).collect()
+}
+
+#[test]
+fn sorts_by_score_then_updated_at() {
+    let mut app = app();
+    assert_eq!(app.sort(), ListSort::Score);
+    assert_eq!(titles(&app), vec!["High", "Middle", "Low"]);
+    app.handle_key(KeyCode::Char('o'));
+    assert_eq!(app.sort(), ListSort::UpdatedAt);
+    assert_eq!(titles(&app), vec!["Low", "Middle", "High"]);
+}
+
+#[test]
+fn filter_cycles_through_statuses() {
+    let mut app = app();
+    app.handle_key(KeyCode::Char('f'));
+    assert_eq!(app.filter(), Some(Status::Watching));
+    assert_eq!(titles(&app), vec!["Middle", "Low"]);
+    app.handle_key(KeyCode::Char('f'));
+    assert_eq!(app.filter(), Some(Status::Completed));
+    assert_eq!(titles(&app), vec!["High"]);
+    for _ in 0..4 { app.handle_key(KeyCode::Char('f')); }
+    assert_eq!(app.filter(), None);
+    assert_eq!(app.visible().len(), 3);
+}
+
+#[test]
+fn keys_edit_the_selected_entry() {
+    let mut app = app();
+    app.handle_key(KeyCode::Down);
+    assert_eq!(app.selected().unwrap().id, 3);
+    assert_eq!(app.handle_key(KeyCode::Char('+')), Some(Edit { id: 3, change: Change::IncrementEpisode }));
+    assert_eq!(app.handle_key(KeyCode::Char('h')), Some(Edit { id: 3, change: Change::Status(Status::OnHold) }));
+    // Middle hasn't watched anything, so there's nothing to take away
+    assert_eq!(app.handle_key(KeyCode::Char('-')), None);
+    app.handle_key(KeyCode::Down);
+    assert_eq!(app.handle_key(KeyCode::Char('-')), Some(Edit { id: 1, change: Change::Episodes(2) }));
+}
+
+#[test]
+fn score_is_typed_and_checked() {
+    let mut app = app();
+    assert_eq!(app.handle_key(KeyCode::Char('s')), None);
+    // Status keys are typed into the score while it's open
+    assert_eq!(app.handle_key(KeyCode::Char('c')), None);
+    app.handle_key(KeyCode::Char('1'));
+    app.handle_key(KeyCode::Char('1'));
+    assert_eq!(app.handle_key(KeyCode::Enter), None);
+    assert_eq!(app.message(), "Score has to be 0-10");
+
+    app.handle_key(KeyCode::Char('s'));
+    app.handle_key(KeyCode::Char('8'));
+    assert_eq!(app.handle_key(KeyCode::Enter), Some(Edit { id: 2, change: Change::Score(8) }));
+    assert!(!app.should_quit());
+    app.handle_key(KeyCode::Char('q'));
+    assert!(app.should_quit());
+}
+
+#[test]
+fn apply_keeps_the_entry_selected() {
+    let mut app = app();
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Down);
+    assert_eq!(app.selected().unwrap().id, 1);
+    let mut status = app.selected().unwrap().list_status.clone().unwrap();
+    status.score = 10;
+    app.apply(1, status);
+    assert_eq!(titles(&app), vec!["Low", "High", "Middle"]);
+    assert_eq!(app.selected().unwrap().id, 1);
+}
+
+#[test]
+fn draws_the_list() {
+    let mut app = app();
+    let mut terminal = Terminal::new(TestBackend::new(80, 10)).unwrap();
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    let screen: String = terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect();
+    assert!(screen.contains("Anime list | all | by score"));
+    assert!(screen.contains("High"));
+    assert!(screen.contains("12/12"));
+}
@@ -1,9 +1,13 @@
-use mal_query::myanimelist::{user::{UpdateAnime, delete_anime}, models::Status, retrieval::{get_user_animelist, get_anime}};
+use mal_query::myanimelist::{user::{UpdateAnime, UpdateManga, BulkUpdate, delete_anime, get_my_user}, models::{ReadingStatus, Status}, retrieval::{get_user_animelist, get_anime}};
 
-// NOTE: tests require a token to be generated, and it does modify the user's database
+mod common;
+use common::mock_mal;
+
+// NOTE: MyAnimeList is answered from tests/fixtures/mal/users.json, which holds the list after every update
 
 #[tokio::test]
 async fn full_update_works_as_expected() {
+    let mock = mock_mal("users");
     let test = UpdateAnime::new(28851)
         .update_status(Status::Completed)
         .update_is_rewatching(false)
@@ -21,11 +25,16 @@ async fn full_update_works_as_expected() {
         .update_finish_date(2024, 1, 4)
         .update()
         .await;
+    let form = &mock.requests_to("PUT", "/v2/anime/28851/my_list_status")[0].form;
+    assert_eq!(form["num_watched_episodes"], "2000");
+    assert_eq!(form["num_times_rewatched"], "1000");
+    assert_eq!(form["tags"], "test");
+    assert_eq!(form["start_date"], "2023-01-04");
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             assert_eq!(data.status, Status::Completed);
-            assert_eq!(data.is_rewatching, false);
+            assert!(!data.is_rewatching);
             assert_eq!(data.score, 9);
             assert_eq!(data.num_episodes_watched, 1);
             assert_eq!(data.priority, Some(2));
@@ -38,15 +47,15 @@ async fn full_update_works_as_expected() {
 
             let test2 = get_user_animelist("naginis_api", 50).await;
             match test2 {
-                Err(_e) => assert!(false),
+                Err(e) => panic!("{e}"),
                 Ok(data2) => {
                     let result = data2.data.iter().find(|anime| anime.id == 28851);
                     match result {
-                        None => assert!(false),
+                        None => panic!("Anime was not in the list"),
                         Some(anime) => {
                             let status = anime.list_status.as_ref().expect("My List Status was unavailable");
                             assert_eq!(status.status, Status::Completed);
-                            assert_eq!(status.is_rewatching, false);
+                            assert!(!status.is_rewatching);
                             assert_eq!(status.score, 9);
                             assert_eq!(status.num_episodes_watched, 1);
                             assert_eq!(status.priority, Some(2));
@@ -66,16 +75,20 @@ async fn full_update_works_as_expected() {
 
 #[tokio::test]
 async fn small_update_works_as_expected() {
+    let mock = mock_mal("users");
     let test = UpdateAnime::new(35413)
         .update_status(Status::Watching)
         .update_num_watched_episodes(4)
         .update()
         .await;
+    let form = &mock.requests_to("PUT", "/v2/anime/35413/my_list_status")[0].form;
+    assert_eq!(form.len(), 2);
+    assert_eq!((form["status"].as_str(), form["num_watched_episodes"].as_str()), ("watching", "4"));
     match test {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             assert_eq!(data.status, Status::Watching);
-            assert_eq!(data.is_rewatching, false);
+            assert!(!data.is_rewatching);
             assert_eq!(data.score, 0);
             assert_eq!(data.num_episodes_watched, 4);
             assert_eq!(data.priority, Some(0));
@@ -84,15 +97,15 @@ async fn small_update_works_as_expected() {
 
             let test2 = get_user_animelist("naginis_api", 50).await;
             match test2 {
-                Err(_e) => assert!(false),
+                Err(e) => panic!("{e}"),
                 Ok(data2) => {
                     let result = data2.data.iter().find(|anime| anime.id == 35413);
                     match result {
-                        None => assert!(false),
+                        None => panic!("Anime was not in the list"),
                         Some(anime) => {
                             let status = anime.list_status.as_ref().expect("My List Status was unavailable");
                             assert_eq!(status.status, Status::Watching);
-                            assert_eq!(status.is_rewatching, false);
+                            assert!(!status.is_rewatching);
                             assert_eq!(status.score, 0);
                             assert_eq!(status.num_episodes_watched, 4);
                             assert_eq!(status.priority, Some(0));
@@ -108,27 +121,31 @@ async fn small_update_works_as_expected() {
 
 #[tokio::test]
 async fn update_from_malanimedata_works_as_expected() {
+    let mock = mock_mal("users");
     let malanimedata = get_anime(37430).await;
     match malanimedata {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             let test = UpdateAnime::from_malanimedata(&data)
                 .update_status(Status::PlanToWatch)
                 .update()
                 .await;
+            let form = &mock.requests_to("PUT", "/v2/anime/37430/my_list_status")[0].form;
+            assert_eq!(form.len(), 1);
+            assert_eq!(form["status"], "plan_to_watch");
 
             match test {
-                Err(_e) => assert!(false),
+                Err(e) => panic!("{e}"),
                 Ok(data2) => {
                     assert_eq!(data2.status, Status::PlanToWatch);
 
                     let test2 = get_user_animelist("naginis_api", 50).await;
                     match test2 {
-                        Err(_e) => assert!(false),
+                        Err(e) => panic!("{e}"),
                         Ok(data2) => {
                             let result = data2.data.iter().find(|anime| anime.id == 37430);
                             match result {
-                                None => assert!(false),
+                                None => panic!("Anime was not in the list"),
                                 Some(anime) => {
                                     let status = anime.list_status.as_ref().expect("My List Status was unavailable");
                                     assert_eq!(status.status, Status::PlanToWatch);
@@ -145,26 +162,200 @@ async fn update_from_malanimedata_works_as_expected() {
 
 #[tokio::test]
 async fn delete_anime_works_as_expected() {
+    let mock = mock_mal("users");
     let insert = UpdateAnime::new(34881)
         .update_status(Status::OnHold)
         .update()
         .await;
     match insert {
-        Err(_e) => assert!(false),
+        Err(e) => panic!("{e}"),
         Ok(data) => {
             assert_eq!(data.status, Status::OnHold);
 
-            if let Err(_e) = delete_anime(34881).await {
-                assert!(false);
-            }
+            if let Err(e) = delete_anime(34881).await { panic!("{e}") }
+            assert_eq!(mock.requests_to("DELETE", "/v2/anime/34881/my_list_status").len(), 1);
 
             let test2 = get_user_animelist("naginis_api", 50).await;
             match test2 {
-                Err(_e) => assert!(false),
+                Err(e) => panic!("{e}"),
                 Ok(data2) => {
                     assert!(!data2.data.iter().any(|anime| anime.id == 34881));
                 }
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn bulk_update_works_as_expected() {
+    let mock = mock_mal("users");
+    let ids = [1, 5, 6].to_vec();
+    let report = BulkUpdate::new(ids.clone(), UpdateAnime::new(0).update_status(Status::Dropped))
+        .concurrency(2)
+        .run()
+        .await;
+    for id in &ids {
+        let sent = mock.requests_to("PUT", &format!("/v2/anime/{id}/my_list_status"));
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].form["status"], "dropped");
+    }
+    match report {
+        Err(e) => panic!("{e}"),
+        Ok(data) => {
+            assert!(data.is_success());
+            assert_eq!(data.results.iter().map(|(id, _)| *id).collect::<Vec<u32>>(), ids);
+            data.successes().iter().for_each(|(_, status)| assert_eq!(status.status, Status::Dropped));
+
+            let test2 = get_user_animelist("naginis_api", 50).await;
+            match test2 {
+                Err(e) => panic!("{e}"),
+                Ok(data2) => {
+                    for id in ids {
+                        let anime = data2.data.iter().find(|anime| anime.id == id).expect("Anime was not in the list");
+                        let status = anime.list_status.as_ref().expect("My List Status was unavailable");
+                        assert_eq!(status.status, Status::Dropped);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn tag_merge_works_as_expected() {
+    let mock = mock_mal("users");
+    let set = UpdateAnime::new(20)
+        .update_status(Status::PlanToWatch)
+        .update_tags(["keep", "old"].to_vec())
+        .update()
+        .await;
+    if let Err(e) = set { panic!("{e}") }
+
+    let test = UpdateAnime::new(20)
+        .add_tags(["added", "keep"].to_vec())
+        .remove_tags(["keep"].to_vec())
+        .rename_tag("old", "renamed")
+        .update()
+        .await;
+    // The entry's tags were read before the merged tags were sent
+    assert_eq!(mock.requests_to("GET", "/v2/anime/20").len(), 1);
+    assert_eq!(mock.requests_to("PUT", "/v2/anime/20/my_list_status")[1].form["tags"], "renamed,added");
+    match test {
+        Err(e) => panic!("{e}"),
+        Ok(data) => {
+            assert_eq!(data.tags, Some(["renamed".to_string(), "added".to_string()].to_vec()));
+        }
+    }
+}
+
+#[tokio::test]
+async fn preview_and_update_if_changed_work_as_expected() {
+    let mock = mock_mal("users");
+    let set = UpdateAnime::new(19)
+        .update_status(Status::PlanToWatch)
+        .update_score(0)
+        .expect("Score is not valid")
+        .update()
+        .await;
+    if let Err(e) = set { panic!("{e}") }
+
+    let mut update = UpdateAnime::new(19);
+    update.update_status(Status::PlanToWatch)
+        .update_score(7)
+        .expect("Score is not valid");
+    match update.preview().await {
+        Err(e) => panic!("{e}"),
+        Ok(changes) => {
+            assert_eq!(changes.len(), 1);
+            assert_eq!(changes[0].field, "score");
+            assert_eq!(changes[0].current, Some("0".to_string()));
+            assert_eq!(changes[0].new, "7");
+        }
+    }
+    match update.update_if_changed().await {
+        Err(e) => panic!("{e}"),
+        Ok(data) => assert_eq!(data.expect("Update should have been sent").score, 7),
+    }
+    match update.update_if_changed().await {
+        Err(e) => panic!("{e}"),
+        Ok(data) => assert_eq!(data, None),
+    }
+    // Only the first update and the changed one were sent
+    assert_eq!(mock.requests_to("PUT", "/v2/anime/19/my_list_status").len(), 2);
+}
+
+#[tokio::test]
+async fn watch_progress_helpers_work_as_expected() {
+    let mock = mock_mal("users");
+    // Sen to Chihiro no Kamikakushi, a movie with 1 episode
+    let set = UpdateAnime::new(199)
+        .update_status(Status::PlanToWatch)
+        .update_num_watched_episodes(0)
+        .update_is_rewatching(false)
+        .update_num_times_rewatched(0)
+        .update()
+        .await;
+    if let Err(e) = set { panic!("{e}") }
+
+    match UpdateAnime::new(199).increment_episode().update().await {
+        Err(e) => panic!("{e}"),
+        Ok(data) => {
+            assert_eq!(data.status, Status::Completed);
+            assert_eq!(data.num_episodes_watched, 1);
+            assert_ne!(data.finish_date, None);
+        }
+    }
+    // Every episode is watched, so there is no next episode
+    assert!(UpdateAnime::new(199).increment_episode().update().await.is_err());
+
+    match UpdateAnime::new(199).start_rewatch().update().await {
+        Err(e) => panic!("{e}"),
+        Ok(data) => {
+            assert!(data.is_rewatching);
+            assert_eq!(data.num_episodes_watched, 0);
+        }
+    }
+    match UpdateAnime::new(199).mark_completed().update().await {
+        Err(e) => panic!("{e}"),
+        Ok(data) => {
+            assert!(!data.is_rewatching);
+            assert_eq!(data.num_episodes_watched, 1);
+            assert_eq!(data.num_times_rewatched, Some(1));
+        }
+    }
+    // The increment past the last episode was never sent
+    let sent = mock.requests_to("PUT", "/v2/anime/199/my_list_status");
+    assert_eq!(sent.len(), 4);
+    assert_eq!(sent[1].form["status"], "completed");
+    assert_eq!(sent[3].form["num_times_rewatched"], "1");
+}
+
+#[tokio::test]
+async fn get_my_user_works_as_expected() {
+    mock_mal("users");
+    match get_my_user().await {
+        Err(e) => panic!("{e}"),
+        Ok(user) => assert_eq!(user.name, "naginis_api"),
+    }
+}
+
+#[tokio::test]
+async fn manga_update_works_as_expected() {
+    let mock = mock_mal("users");
+    let test = UpdateManga::new(2)
+        .update_status(ReadingStatus::Reading)
+        .update_score(8)
+        .expect("Score is not valid")
+        .update_num_chapters_read(10)
+        .update()
+        .await;
+    match test {
+        Err(e) => panic!("{e}"),
+        Ok(data) => {
+            assert_eq!(data.status, ReadingStatus::Reading);
+            assert_eq!(data.num_chapters_read, 10);
+        }
+    }
+    let form = &mock.requests_to("PUT", "/v2/manga/2/my_list_status")[0].form;
+    assert_eq!((form["status"].as_str(), form["score"].as_str()), ("reading", "8"));
+}
@@ -0,0 +1,89 @@
+use std::fs;
+use mal_query::myanimelist::{models::*, xml::{anime_list_xml, manga_list_xml, parse_xml}};
+
+fn fixture<T: serde::de::DeserializeOwned>(name: &str) -> T {
+    let json = fs::read_to_string(format!("tests/fixtures/xml/{name}")).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn anime_xml_matches_fixture() {
+    let anime: Vec<MalAnimeData> = fixture("animelist.json");
+    let xml = anime_list_xml(&anime, "naginis_api");
+    assert_eq!(xml, fs::read_to_string("tests/fixtures/xml/animelist.xml").unwrap());
+}
+
+#[test]
+fn manga_xml_matches_fixture() {
+    let manga: Vec<MalMangaData> = fixture("mangalist.json");
+    let xml = manga_list_xml(&manga, "naginis_api");
+    assert_eq!(xml, fs::read_to_string("tests/fixtures/xml/mangalist.xml").unwrap());
+}
+
+#[test]
+fn unlisted_anime_are_left_out() {
+    let anime: Vec<MalAnimeData> = fixture("animelist.json");
+    let xml = anime_list_xml(&anime, "naginis_api");
+    assert_eq!(xml.matches("<anime>").count(), 3);
+    assert!(xml.contains("<user_total_anime>3</user_total_anime>"));
+    assert!(!xml.contains("Cowboy Bebop"));
+}
+
+#[test]
+fn exported_xml_parses_back() {
+    let anime: Vec<MalAnimeData> = fixture("animelist.json");
+    let list = parse_xml(&anime_list_xml(&anime, "naginis_api")).unwrap();
+    assert_eq!(list.username, "naginis_api");
+    assert_eq!(list.anime.len(), 3);
+    assert!(list.manga.is_empty());
+
+    let fmab = &list.anime[0];
+    assert_eq!(fmab.id, 5114);
+    assert_eq!(fmab.title, "Fullmetal Alchemist: Brotherhood");
+    assert_eq!(fmab.num_episodes, 64);
+    let expected = ListStatus { updated_at: String::new(), ..anime[0].list_status.clone().unwrap() };
+    assert_eq!(fmab.list_status, expected);
+    assert_eq!(list.anime[2].list_status.start_date, None);
+
+    let manga: Vec<MalMangaData> = fixture("mangalist.json");
+    let list = parse_xml(&manga_list_xml(&manga, "naginis_api")).unwrap();
+    let berserk = &list.manga[0];
+    assert_eq!(berserk.list_status.status, ReadingStatus::Reading);
+    assert_eq!(berserk.list_status.num_chapters_read, 364);
+    assert!(berserk.list_status.is_rereading);
+    assert_eq!(berserk.list_status.start_date.as_deref(), Some("2015"));
+    assert_eq!(berserk.list_status.tags, Some(vec!["dark fantasy".to_string()]));
+}
+
+#[test]
+fn parses_older_exports() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8" ?>
+        <myanimelist>
+            <anime>
+                <series_animedb_id>1</series_animedb_id>
+                <series_title>Cowboy Bebop &amp; friends</series_title>
+                <my_watched_episodes>26</my_watched_episodes>
+                <my_score>9</my_score>
+                <my_status>2</my_status>
+                <my_priority>Medium</my_priority>
+                <my_rewatch_value>Very High</my_rewatch_value>
+                <my_rewatching>YES</my_rewatching>
+            </anime>
+        </myanimelist>"#;
+    let anime = &parse_xml(xml).unwrap().anime[0];
+    assert_eq!(anime.title, "Cowboy Bebop & friends");
+    assert_eq!(anime.list_status.status, Status::Completed);
+    assert_eq!(anime.list_status.priority, Some(1));
+    assert_eq!(anime.list_status.rewatch_value, Some(5));
+    assert!(anime.list_status.is_rewatching);
+    assert_eq!(anime.list_status.finish_date, None);
+}
+
+#[test]
+fn invalid_entries_are_errors() {
+    let no_status = "<myanimelist><anime><series_animedb_id>1</series_animedb_id></anime></myanimelist>";
+    assert!(parse_xml(no_status).is_err());
+    let no_id = "<myanimelist><manga><my_status>Reading</my_status></manga></myanimelist>";
+    assert!(parse_xml(no_id).is_err());
+    assert!(parse_xml("<myanimelist><anime></manga></myanimelist>").is_err());
+}